@@ -3,6 +3,7 @@ fn main() {
         .file("c_code/triangle.c")
         .file("c_code/tricall_report.c")
         .file("c_code/interface_triangle.c")
+        .define("TRITET_LONGJMP_TRIEXIT", None)
         .flag("-Wno-sign-compare")
         .flag("-Wno-unused-parameter")
         .flag("-Wno-unused-but-set-variable")
@@ -12,6 +13,7 @@ fn main() {
         .file("c_code/predicates.cxx")
         .file("c_code/tetgen.cxx")
         .file("c_code/interface_tetgen.cpp")
+        .define("TRITET_THROWING_ASSERT", None)
         .flag("-Wno-int-to-pointer-cast")
         .flag("-Wno-unused-parameter")
         .flag("-Wno-unused-but-set-variable")
@@ -10,6 +10,12 @@ const int32_t TRITET_SUCCESS = 0;
 
 const int32_t TRITET_ERROR_NULL_DATA = 10;
 const int32_t TRITET_ERROR_STRING_CONCAT = 20;
+const int32_t TRITET_ERROR_TRIANGLE_FAILED = 30;
+const int32_t TRITET_ERROR_TETGEN_FAILED = 40;
+const int32_t TRITET_ERROR_TETGEN_OUT_OF_MEMORY = 41;
+const int32_t TRITET_ERROR_TETGEN_INTERNAL_ERROR = 42;
+const int32_t TRITET_ERROR_TETGEN_INVALID_INPUT = 43;
+const int32_t TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED = 44;
 
 const int32_t TRITET_ERROR_NULL_POINT_LIST = 100;
 const int32_t TRITET_ERROR_NULL_SEGMENT_LIST = 200;
@@ -17,6 +23,14 @@ const int32_t TRITET_ERROR_NULL_FACET_LIST = 300;
 const int32_t TRITET_ERROR_NULL_FACET_POLYGON_LIST = 400;
 const int32_t TRITET_ERROR_NULL_REGION_LIST = 500;
 const int32_t TRITET_ERROR_NULL_HOLE_LIST = 600;
+const int32_t TRITET_ERROR_NULL_SEGMENT_MARKER_LIST = 700;
+const int32_t TRITET_ERROR_NULL_FACET_MARKER_LIST = 800;
+const int32_t TRITET_ERROR_NULL_FACE_MIDDLE_LIST = 900;
+const int32_t TRITET_ERROR_NULL_FACET_HOLE_LIST = 1100;
+const int32_t TRITET_ERROR_NULL_TRIANGLE_LIST = 1200;
+const int32_t TRITET_ERROR_NULL_TRIANGLE_AREA_LIST = 1300;
+const int32_t TRITET_ERROR_NULL_TETRAHEDRON_LIST = 1400;
+const int32_t TRITET_ERROR_NULL_TETRAHEDRON_VOLUME_LIST = 1500;
 
 const int32_t TRITET_ERROR_INVALID_POINT_INDEX = 1000;
 const int32_t TRITET_ERROR_INVALID_SEGMENT_INDEX = 2000;
@@ -27,5 +41,7 @@ const int32_t TRITET_ERROR_INVALID_FACET_POINT_INDEX = 6000;
 const int32_t TRITET_ERROR_INVALID_FACET_POINT_ID = 7000;
 const int32_t TRITET_ERROR_INVALID_REGION_INDEX = 8000;
 const int32_t TRITET_ERROR_INVALID_HOLE_INDEX = 9000;
+const int32_t TRITET_ERROR_INVALID_FACET_POLYGON_INDEX = 10000;
+const int32_t TRITET_ERROR_INVALID_FACET_HOLE_INDEX = 11000;
 
 #endif  // CONSTANTS_H
@@ -3,6 +3,7 @@
 #include <stdlib.h>
 
 #include <new>
+#include <vector>
 
 #include "constants.h"
 #include "tetgen.h"
@@ -11,14 +12,168 @@ extern "C" {
 #include "interface_tetgen.h"
 }
 
+// Maps the local corners (a, b) of an edge of a tetrahedron to the corner of its middle node
+const int32_t TETGEN_EDGE_MIDDLE[4][4] = {
+    {-1, 4, 6, 7},
+    {4, -1, 5, 8},
+    {6, 5, -1, 9},
+    {7, 8, 9, -1},
+};
+
+// Maximum number of passes of TetGen to satisfy the volume constraints (see tet_run_refine)
+// Note: this number is also given in the docs of Tetgen::refine_mesh and in error.rs
+const int32_t REFINE_MAX_PASSES = 20;
+
+// Relative margin for the volume constraints to account for the rounding errors of the volumes
+const double REFINE_MARGIN = 1e-10;
+
 void drop_tetgen(struct ExtTetgen *tetgen) {
     if (tetgen == NULL) {
         return;
     }
+    if (tetgen->face_middle_list != NULL) {
+        delete[] tetgen->face_middle_list;
+    }
+    if (tetgen->intersection_list != NULL) {
+        delete[] tetgen->intersection_list;
+    }
     delete tetgen;
 }
 
-struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *facet_npoint, int32_t nregion, int32_t nhole) {
+int32_t set_intersection_list(struct ExtTetgen *tetgen, tetgenio const *output) {
+    // With the d switch, the output holds the intersecting faces only; their markers are the facet
+    // indices (see tet_run_intersection_check). The pairs are found with TetGen's exact test.
+    int32_t nface = output->numberoftrifaces;
+    if (nface < 2 || output->trifacemarkerlist == NULL) {
+        return TRITET_SUCCESS;
+    }
+    tetgenmesh mesh;
+    mesh.macheps = exactinit();
+    std::vector<int32_t> pairs;
+    double *points = output->pointlist;
+    int32_t const *faces = output->trifacelist;
+    int32_t const *markers = output->trifacemarkerlist;
+    for (int32_t i = 0; i < nface; i++) {
+        for (int32_t j = i + 1; j < nface; j++) {
+            enum tetgenmesh::interresult result = mesh.tri_tri_inter(&points[faces[i * 3] * 3],
+                                                                     &points[faces[i * 3 + 1] * 3],
+                                                                     &points[faces[i * 3 + 2] * 3],
+                                                                     &points[faces[j * 3] * 3],
+                                                                     &points[faces[j * 3 + 1] * 3],
+                                                                     &points[faces[j * 3 + 2] * 3]);
+            if (result == tetgenmesh::INTERSECT || result == tetgenmesh::SHAREFACE) {
+                pairs.push_back(markers[i] < markers[j] ? markers[i] : markers[j]);
+                pairs.push_back(markers[i] < markers[j] ? markers[j] : markers[i]);
+            }
+        }
+    }
+    if (pairs.empty()) {
+        return TRITET_SUCCESS;
+    }
+    tetgen->intersection_list = new (std::nothrow) int32_t[pairs.size()];
+    if (tetgen->intersection_list == NULL) {
+        return TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+    }
+    for (size_t k = 0; k < pairs.size(); k++) {
+        tetgen->intersection_list[k] = pairs[k];
+    }
+    tetgen->nintersection = pairs.size() / 2;
+    return TRITET_SUCCESS;
+}
+
+int32_t set_face_middle_list(struct ExtTetgen *tetgen) {
+    // The adjacent tetrahedra (adjtetlist; nn switch) are employed to find the middle nodes
+    if (tetgen->output.numberofcorners != 10 || tetgen->output.adjtetlist == NULL) {
+        return TRITET_SUCCESS;
+    }
+    int32_t nface = tetgen->output.numberoftrifaces;
+    tetgen->face_middle_list = new (std::nothrow) int32_t[nface * 3];
+    if (tetgen->face_middle_list == NULL) {
+        return TRITET_ERROR_NULL_FACE_MIDDLE_LIST;
+    }
+    for (int32_t index = 0; index < nface; index++) {
+        int32_t tet = tetgen->output.adjtetlist[index * 2];
+        if (tet < 0) {
+            tet = tetgen->output.adjtetlist[index * 2 + 1];
+        }
+        for (int32_t k = 0; k < 3; k++) {
+            tetgen->face_middle_list[index * 3 + k] = 0;
+            if (tet < 0) {
+                continue;
+            }
+            int32_t const *corners = &tetgen->output.tetrahedronlist[tet * 10];
+            int32_t a = tetgen->output.trifacelist[index * 3 + k];
+            int32_t b = tetgen->output.trifacelist[index * 3 + (k + 1) % 3];
+            int32_t la = -1;
+            int32_t lb = -1;
+            for (int32_t m = 0; m < 4; m++) {
+                if (corners[m] == a) {
+                    la = m;
+                }
+                if (corners[m] == b) {
+                    lb = m;
+                }
+            }
+            if (la >= 0 && lb >= 0 && la != lb) {
+                tetgen->face_middle_list[index * 3 + k] = corners[TETGEN_EDGE_MIDDLE[la][lb]];
+            }
+        }
+    }
+    return TRITET_SUCCESS;
+}
+
+void clear_tetgenio(tetgenio *io) {
+    io->deinitialize();
+    io->initialize();
+}
+
+void clear_output(struct ExtTetgen *tetgen) {
+    clear_tetgenio(&tetgen->output);
+    if (tetgen->face_middle_list != NULL) {
+        delete[] tetgen->face_middle_list;
+        tetgen->face_middle_list = NULL;
+    }
+}
+
+int32_t run_tetrahedralize(char *command, tetgenio *in, tetgenio *out, tetgenio *addin = NULL) {
+    // TetGen (compiled with TETLIBRARY) throws the exit code instead of terminating the process
+    try {
+        tetrahedralize(command, in, out, addin, NULL);
+    } catch (int status) {
+        clear_tetgenio(out);
+        if (status == 1) {
+            return TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+        }
+        if (status == 2) {
+            return TRITET_ERROR_TETGEN_INTERNAL_ERROR;
+        }
+        if (status == 3) {
+            return TRITET_ERROR_TETGEN_INVALID_INPUT;
+        }
+        return TRITET_ERROR_TETGEN_FAILED;
+    } catch (std::bad_alloc &) {
+        clear_tetgenio(out);
+        return TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+    } catch (...) {
+        clear_tetgenio(out);
+        return TRITET_ERROR_TETGEN_FAILED;
+    }
+    return TRITET_SUCCESS;
+}
+
+int32_t call_tetrahedralize(struct ExtTetgen *tetgen, char *command, tetgenio *in, tetgenio *addin = NULL) {
+    // TetGen would otherwise leak the arrays of a previous run
+    clear_output(tetgen);
+    return run_tetrahedralize(command, in, &tetgen->output, addin);
+}
+
+struct ExtTetgen *new_tetgen(int32_t npoint,
+                             int32_t nfacet,
+                             int32_t const *facet_npolygon,
+                             int32_t const *facet_polygon_npoint,
+                             int32_t const *facet_nhole,
+                             int32_t nregion,
+                             int32_t nhole) {
     if (npoint < 4) {
         return NULL;
     }
@@ -28,6 +183,9 @@ struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *face
     if (tetgen == NULL) {
         return NULL;
     }
+    tetgen->face_middle_list = NULL;
+    tetgen->intersection_list = NULL;
+    tetgen->nintersection = 0;
     try {
         tetgen->input.initialize();
         tetgen->output.initialize();
@@ -53,27 +211,52 @@ struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *face
             drop_tetgen(tetgen);
             return NULL;
         }
-        const int32_t NUM_POLY = 1;
         for (int32_t index = 0; index < nfacet; index++) {
-            // facet polygon
+            tetgenio::init(&tetgen->input.facetlist[index]);
+        }
+        tetgen->input.facetmarkerlist = new (std::nothrow) int32_t[nfacet];
+        if (tetgen->input.facetmarkerlist == NULL) {
+            drop_tetgen(tetgen);
+            return NULL;
+        }
+        for (int32_t index = 0; index < nfacet; index++) {
+            tetgen->input.facetmarkerlist[index] = 0;
+        }
+        int32_t offset = 0;
+        for (int32_t index = 0; index < nfacet; index++) {
+            // facet polygons
             tetgenio::facet *fac = &tetgen->input.facetlist[index];
-            fac->polygonlist = new (std::nothrow) tetgenio::polygon[NUM_POLY];
+            int32_t npolygon = facet_npolygon[index];
+            fac->polygonlist = new (std::nothrow) tetgenio::polygon[npolygon];
             if (fac->polygonlist == NULL) {
                 drop_tetgen(tetgen);
                 return NULL;
             }
-            fac->numberofpolygons = NUM_POLY;
-            fac->numberofholes = 0;
-            fac->holelist = NULL;
-            // face polygon vertices
-            size_t nvertex = facet_npoint[index];
-            tetgenio::polygon *gon = &fac->polygonlist[0];
-            gon->vertexlist = new (std::nothrow) int32_t[nvertex];
-            if (gon->vertexlist == NULL) {
-                drop_tetgen(tetgen);
-                return NULL;
+            fac->numberofpolygons = npolygon;
+            for (int32_t polygon = 0; polygon < npolygon; polygon++) {
+                tetgenio::init(&fac->polygonlist[polygon]);
+            }
+            // facet polygon vertices
+            for (int32_t polygon = 0; polygon < npolygon; polygon++) {
+                size_t nvertex = facet_polygon_npoint[offset + polygon];
+                tetgenio::polygon *gon = &fac->polygonlist[polygon];
+                gon->vertexlist = new (std::nothrow) int32_t[nvertex];
+                if (gon->vertexlist == NULL) {
+                    drop_tetgen(tetgen);
+                    return NULL;
+                }
+                gon->numberofvertices = nvertex;
+            }
+            offset += npolygon;
+            // facet holes
+            fac->numberofholes = facet_nhole[index];
+            if (fac->numberofholes > 0) {
+                fac->holelist = new (std::nothrow) double[fac->numberofholes * 3];
+                if (fac->holelist == NULL) {
+                    drop_tetgen(tetgen);
+                    return NULL;
+                }
             }
-            gon->numberofvertices = nvertex;
         }
     }
 
@@ -117,7 +300,7 @@ int32_t tet_set_point(struct ExtTetgen *tetgen, int32_t index, double x, double
     return TRITET_SUCCESS;
 }
 
-int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m, int32_t p) {
+int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t m, int32_t p) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
     }
@@ -132,11 +315,14 @@ int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m,
     if (fac->polygonlist == NULL) {
         return TRITET_ERROR_NULL_FACET_POLYGON_LIST;
     }
-    if (fac->numberofpolygons != 1) {
+    if (fac->numberofpolygons < 1) {
         return TRITET_ERROR_INVALID_FACET_NUM_POLYGON;
     }
+    if (polygon >= fac->numberofpolygons) {
+        return TRITET_ERROR_INVALID_FACET_POLYGON_INDEX;
+    }
 
-    tetgenio::polygon *gon = &fac->polygonlist[0];
+    tetgenio::polygon *gon = &fac->polygonlist[polygon];
     if (m >= gon->numberofvertices) {
         return TRITET_ERROR_INVALID_FACET_POINT_INDEX;
     }
@@ -148,6 +334,46 @@ int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m,
     return TRITET_SUCCESS;
 }
 
+int32_t tet_set_facet_hole(struct ExtTetgen *tetgen, int32_t index, int32_t hole, double x, double y, double z) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.facetlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_LIST;
+    }
+    if (index >= tetgen->input.numberoffacets) {
+        return TRITET_ERROR_INVALID_FACET_INDEX;
+    }
+
+    tetgenio::facet *fac = &tetgen->input.facetlist[index];
+    if (fac->holelist == NULL) {
+        return TRITET_ERROR_NULL_FACET_HOLE_LIST;
+    }
+    if (hole >= fac->numberofholes) {
+        return TRITET_ERROR_INVALID_FACET_HOLE_INDEX;
+    }
+    fac->holelist[hole * 3] = x;
+    fac->holelist[hole * 3 + 1] = y;
+    fac->holelist[hole * 3 + 2] = z;
+
+    return TRITET_SUCCESS;
+}
+
+int32_t tet_set_facet_marker(struct ExtTetgen *tetgen, int32_t index, int32_t marker) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.facetmarkerlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_MARKER_LIST;
+    }
+    if (index >= tetgen->input.numberoffacets) {
+        return TRITET_ERROR_INVALID_FACET_INDEX;
+    }
+    tetgen->input.facetmarkerlist[index] = marker;
+
+    return TRITET_SUCCESS;
+}
+
 int32_t tet_set_region(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z, int32_t attribute, double max_volume) {
     if (tetgen == NULL) {
         return TRITET_ERROR_NULL_DATA;
@@ -195,17 +421,15 @@ int32_t tet_run_delaunay(struct ExtTetgen *tetgen, int32_t verbose) {
     // Tetrahedralize the points
     // Switches:
     // * `z` -- number everything from zero (z)
+    // * `n` -- output a list of tetrahedron neighbors (n)
     char command[10];
-    strcpy(command, "z");
+    strcpy(command, "zn");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
-    try {
-        tetrahedralize(command, &tetgen->input, &tetgen->output, NULL, NULL);
-    } catch (int32_t status) {
-        printf("status = %d\n", status);  // TODO
-    } catch (...) {
-        return 1;  // TODO
+    int32_t status = call_tetrahedralize(tetgen, command, &tetgen->input);
+    if (status != TRITET_SUCCESS) {
+        return status;
     }
 
     return TRITET_SUCCESS;
@@ -227,8 +451,9 @@ int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_
     // * `p` -- tetrahedralize a piecewise linear complex (PLC)
     // * `z` -- number everything from zero (z)
     // * `A` -- assign a regional attribute to each element (A)
+    // * `nn` -- output a list of tetrahedron neighbors and the tetrahedra adjacent to faces (nn)
     char command[128];
-    strcpy(command, "pzA");
+    strcpy(command, "pzAnn");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
@@ -253,17 +478,306 @@ int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_
     } else {
         strcat(command, "q");
     }
-    try {
-        tetrahedralize(command, &tetgen->input, &tetgen->output, NULL, NULL);
-    } catch (int32_t status) {
-        printf("status = %d\n", status);  // TODO
-    } catch (...) {
-        return 1;  // TODO
+    int32_t status = call_tetrahedralize(tetgen, command, &tetgen->input);
+    if (status != TRITET_SUCCESS) {
+        return status;
     }
 
+    if (o2 == TRITET_TRUE) {
+        return set_face_middle_list(tetgen);
+    }
     return TRITET_SUCCESS;
 }
 
+// Returns the volume of a tetrahedron of a mesh (the first four corners are used)
+double tet_volume(tetgenio const &mesh, int32_t index) {
+    int32_t const *corners = &mesh.tetrahedronlist[index * mesh.numberofcorners];
+    double const *x0 = &mesh.pointlist[corners[0] * 3];
+    double a[3], b[3], c[3];
+    for (int32_t dim = 0; dim < 3; dim++) {
+        a[dim] = mesh.pointlist[corners[1] * 3 + dim] - x0[dim];
+        b[dim] = mesh.pointlist[corners[2] * 3 + dim] - x0[dim];
+        c[dim] = mesh.pointlist[corners[3] * 3 + dim] - x0[dim];
+    }
+    double det = a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0]) + a[2] * (b[0] * c[1] - b[1] * c[0]);
+    return det < 0.0 ? -det / 6.0 : det / 6.0;
+}
+
+// Returns the number of tetrahedra violating the constraint of the original tetrahedron and saves the
+// middle points of their longest edges if a list is given (the index of the original tetrahedron is
+// the first attribute; see tet_run_refine)
+int32_t tet_count_oversized(tetgenio const &mesh, double const *max_volumes, double *points) {
+    std::vector<bool> on_boundary(points != NULL ? mesh.numberofpoints : 0, false);
+    if (points != NULL) {
+        for (int32_t k = 0; k < mesh.numberoftrifaces * 3; k++) {
+            on_boundary[mesh.trifacelist[k]] = true;
+        }
+    }
+    int32_t count = 0;
+    for (int32_t index = 0; index < mesh.numberoftetrahedra; index++) {
+        int32_t parent = (int32_t)mesh.tetrahedronattributelist[index * mesh.numberoftetrahedronattributes];
+        double max_volume = max_volumes[parent];
+        if (max_volume <= 0.0 || tet_volume(mesh, index) <= max_volume * (1.0 - REFINE_MARGIN)) {
+            continue;
+        }
+        if (points != NULL) {
+            // the middle point of the longest interior edge or the centroid (boundary edges are
+            // avoided because TetGen may fail to insert the points on segments)
+            int32_t const *corners = &mesh.tetrahedronlist[index * mesh.numberofcorners];
+            double longest = -1.0;
+            for (int32_t dim = 0; dim < 3; dim++) {
+                points[count * 3 + dim] = 0.0;
+                for (int32_t m = 0; m < 4; m++) {
+                    points[count * 3 + dim] += mesh.pointlist[corners[m] * 3 + dim] / 4.0;
+                }
+            }
+            for (int32_t i = 0; i < 4; i++) {
+                for (int32_t j = i + 1; j < 4; j++) {
+                    if (on_boundary[corners[i]] && on_boundary[corners[j]]) {
+                        continue;
+                    }
+                    double const *a = &mesh.pointlist[corners[i] * 3];
+                    double const *b = &mesh.pointlist[corners[j] * 3];
+                    double length = (a[0] - b[0]) * (a[0] - b[0]) + (a[1] - b[1]) * (a[1] - b[1]) + (a[2] - b[2]) * (a[2] - b[2]);
+                    if (length > longest) {
+                        longest = length;
+                        for (int32_t dim = 0; dim < 3; dim++) {
+                            points[count * 3 + dim] = (a[dim] + b[dim]) / 2.0;
+                        }
+                    }
+                }
+            }
+        }
+        count++;
+    }
+    return count;
+}
+
+int32_t tet_run_refine(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double const *max_volumes, double global_min_angle) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->output.tetrahedronlist == NULL) {
+        return TRITET_ERROR_NULL_TETRAHEDRON_LIST;
+    }
+    if (tetgen->output.numberofcorners != 4) {
+        return TRITET_ERROR_TETGEN_INVALID_INPUT;
+    }
+
+    // Refine mesh
+    // Switches:
+    // * `r` -- refine a previously generated mesh (the boundary faces keep their markers)
+    // * `z` -- number everything from zero (z)
+    // * `nn` -- output a list of tetrahedron neighbors and the tetrahedra adjacent to faces (nn)
+    // * `a` -- read the volume constraints from the tetrahedronvolumelist (a)
+    // Note: the regional attributes are inherited from the input tetrahedra (thus, without `A`)
+    char command[128];
+    strcpy(command, "rznna");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    if (global_min_angle > 0.0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "q%.15f", global_min_angle);
+        if (n >= 32) {
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    } else {
+        strcat(command, "q");
+    }
+
+    // The current output becomes the input mesh (shallow copy; the arrays are now owned by original)
+    tetgenio original = tetgen->output;
+    tetgen->output.initialize();
+
+    // TetGen only enforces the volume constraints approximately: the constraints of new tetrahedra
+    // are enlarged by 20% and the tetrahedra created by flips have no constraint. Thus, the mesh is
+    // refined repeatedly until all tetrahedra satisfy the constraint of the original tetrahedron.
+    // The index of the original tetrahedron is saved as the first attribute, followed by the other
+    // attributes. A zero attribute is appended if there are none because TetGen keeps the faces
+    // between tetrahedra with distinct last attributes (i.e., regions).
+    int32_t ntet = original.numberoftetrahedra;
+    int32_t nattribute = original.numberoftetrahedronattributes;
+    double *attributes = original.tetrahedronattributelist;
+    int32_t nextended = nattribute > 0 ? 1 + nattribute : 2;
+    double *extended = new (std::nothrow) double[ntet * nextended];
+    if (extended == NULL) {
+        tetgen->output = original;
+        original.initialize();
+        return TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+    }
+    for (int32_t index = 0; index < ntet; index++) {
+        extended[index * nextended] = (double)index;
+        for (int32_t k = 1; k < nextended; k++) {
+            extended[index * nextended + k] = nattribute > 0 ? attributes[index * nattribute + k - 1] : 0.0;
+        }
+    }
+    original.tetrahedronattributelist = extended;
+    original.numberoftetrahedronattributes = nextended;
+
+    // The tetrahedra that TetGen does not split (e.g., to protect the boundary) are split by the next
+    // pass at the middle points of their longest edges (additional points; switch `i`)
+    int32_t status = TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED;
+    tetgenio previous;  // output of the previous pass
+    tetgenio midpoints; // middle points of the longest edges of the oversized tetrahedra
+    tetgenio *input = &original;
+    size_t length = strlen(command);
+    for (int32_t pass = 0; pass < REFINE_MAX_PASSES; pass++) {
+        int32_t n = input->numberoftetrahedra;
+        if (input->tetrahedronvolumelist != NULL) {
+            delete[] input->tetrahedronvolumelist;
+        }
+        input->tetrahedronvolumelist = new (std::nothrow) double[n];
+        if (input->tetrahedronvolumelist == NULL) {
+            status = TRITET_ERROR_NULL_TETRAHEDRON_VOLUME_LIST;
+            break;
+        }
+        for (int32_t index = 0; index < n; index++) {
+            int32_t parent = (int32_t)input->tetrahedronattributelist[index * nextended];
+            input->tetrahedronvolumelist[index] = max_volumes[parent] * (1.0 - 2.0 * REFINE_MARGIN);
+        }
+        command[length] = '\0';
+        if (midpoints.numberofpoints > 0) {
+            strcat(command, "i");
+        }
+        status = call_tetrahedralize(tetgen, command, input, &midpoints);
+        if (status != TRITET_SUCCESS) {
+            break;
+        }
+        int32_t noversized = tet_count_oversized(tetgen->output, max_volumes, NULL);
+        if (noversized == 0) {
+            break;
+        }
+        status = TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED;
+        midpoints.deinitialize();
+        midpoints.initialize();
+        midpoints.pointlist = new (std::nothrow) double[noversized * 3];
+        if (midpoints.pointlist == NULL) {
+            status = TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+            break;
+        }
+        midpoints.numberofpoints = noversized;
+        tet_count_oversized(tetgen->output, max_volumes, midpoints.pointlist);
+        previous.deinitialize();
+        previous = tetgen->output;
+        tetgen->output.initialize();
+        input = &previous;
+    }
+
+    // Generate the middle nodes with another pass (without constraints; thus, the mesh is not modified)
+    if (status == TRITET_SUCCESS && o2 == TRITET_TRUE) {
+        previous.deinitialize();
+        previous = tetgen->output;
+        tetgen->output.initialize();
+        strcpy(command, "rznno2");
+        if (verbose == TRITET_FALSE) {
+            strcat(command, "Q");
+        }
+        status = call_tetrahedralize(tetgen, command, &previous);
+        if (status == TRITET_SUCCESS && tet_count_oversized(tetgen->output, max_volumes, NULL) > 0) {
+            status = TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED;
+        }
+    }
+
+    // Restore the original attributes of the input mesh
+    delete[] extended;
+    original.tetrahedronattributelist = attributes;
+    original.numberoftetrahedronattributes = nattribute;
+
+    // The stripped attributes are allocated beforehand to keep the previous mesh if the allocation fails
+    int32_t nout = tetgen->output.numberoftetrahedra;
+    double *stripped = NULL;
+    if (status == TRITET_SUCCESS && nattribute > 0) {
+        stripped = new (std::nothrow) double[nout * nattribute];
+        if (stripped == NULL) {
+            status = TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+        }
+    }
+
+    if (status != TRITET_SUCCESS) {
+        // keep the previous mesh
+        clear_output(tetgen);
+        if (original.tetrahedronvolumelist != NULL) {
+            delete[] original.tetrahedronvolumelist;
+            original.tetrahedronvolumelist = NULL;
+        }
+        tetgen->output = original;
+        original.initialize();
+        return status;
+    }
+
+    // Remove the index of the original tetrahedron (and the appended zero) from the attributes
+    double *output_attributes = tetgen->output.tetrahedronattributelist;
+    if (nattribute > 0) {
+        for (int32_t index = 0; index < nout; index++) {
+            for (int32_t k = 0; k < nattribute; k++) {
+                stripped[index * nattribute + k] = output_attributes[index * nextended + 1 + k];
+            }
+        }
+        tetgen->output.tetrahedronattributelist = stripped;
+    } else {
+        tetgen->output.tetrahedronattributelist = NULL;
+    }
+    tetgen->output.numberoftetrahedronattributes = nattribute;
+    delete[] output_attributes;
+
+    if (o2 == TRITET_TRUE) {
+        return set_face_middle_list(tetgen);
+    }
+    return TRITET_SUCCESS;
+}
+
+int32_t tet_run_intersection_check(struct ExtTetgen *tetgen, int32_t verbose) {
+    if (tetgen == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (tetgen->input.pointlist == NULL) {
+        return TRITET_ERROR_NULL_POINT_LIST;
+    }
+    if (tetgen->input.facetlist == NULL) {
+        return TRITET_ERROR_NULL_FACET_LIST;
+    }
+    if (tetgen->intersection_list != NULL) {
+        delete[] tetgen->intersection_list;
+        tetgen->intersection_list = NULL;
+    }
+    tetgen->nintersection = 0;
+
+    // The facet markers are temporarily replaced by the facet indices to identify the faces
+    int32_t nfacet = tetgen->input.numberoffacets;
+    int32_t *facet_indices = new (std::nothrow) int32_t[nfacet];
+    if (facet_indices == NULL) {
+        return TRITET_ERROR_TETGEN_OUT_OF_MEMORY;
+    }
+    for (int32_t index = 0; index < nfacet; index++) {
+        facet_indices[index] = index;
+    }
+    int32_t *facet_markers = tetgen->input.facetmarkerlist;
+    tetgen->input.facetmarkerlist = facet_indices;
+
+    // Detect intersections
+    // Switches:
+    // * `p` -- read a piecewise linear complex (PLC)
+    // * `d` -- detect self-intersections of facets (no mesh is generated)
+    // * `z` -- number everything from zero (z)
+    char command[8];
+    strcpy(command, "pdz");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    // The output holds the intersecting faces only; thus, a scratch output is employed to keep
+    // the mesh of a previous generation
+    tetgenio output;
+    int32_t status = run_tetrahedralize(command, &tetgen->input, &output);
+    tetgen->input.facetmarkerlist = facet_markers;
+    delete[] facet_indices;
+    if (status == TRITET_SUCCESS) {
+        status = set_intersection_list(tetgen, &output);
+    }
+    return status;
+}
+
 int32_t tet_get_npoint(struct ExtTetgen *tetgen) {
     if (tetgen == NULL) {
         return 0;
@@ -329,3 +843,140 @@ int32_t tet_get_tetrahedron_attribute(struct ExtTetgen *tetgen, int32_t index) {
 
     return 0;
 }
+
+int32_t tet_get_tetrahedron_neighbor(struct ExtTetgen *tetgen, int32_t index, int32_t face) {
+    if (tetgen == NULL) {
+        return -1;
+    }
+    if (index < tetgen->output.numberoftetrahedra && (face == 0 || face == 1 || face == 2 || face == 3) && tetgen->output.neighborlist != NULL) {
+        return tetgen->output.neighborlist[index * 4 + face];
+    } else {
+        return -1;
+    }
+}
+
+int32_t tet_get_nface(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberoftrifaces;
+}
+
+int32_t tet_get_face_corner(struct ExtTetgen *tetgen, int32_t index, int32_t corner) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index >= tetgen->output.numberoftrifaces || tetgen->output.trifacelist == NULL) {
+        return 0;
+    }
+    if (corner == 0 || corner == 1 || corner == 2) {
+        return tetgen->output.trifacelist[index * 3 + corner];
+    }
+    if ((corner == 3 || corner == 4 || corner == 5) && tetgen->face_middle_list != NULL) {
+        return tetgen->face_middle_list[index * 3 + corner - 3];
+    }
+    return 0;
+}
+
+int32_t tet_get_face_marker(struct ExtTetgen *tetgen, int32_t index) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    if (index < tetgen->output.numberoftrifaces && tetgen->output.trifacemarkerlist != NULL) {
+        return tetgen->output.trifacemarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
+double const *tet_get_point_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->output.pointlist;
+}
+
+int32_t const *tet_get_tetrahedron_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->output.tetrahedronlist;
+}
+
+int32_t tet_get_ntetrahedron_attribute(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->output.numberoftetrahedronattributes;
+}
+
+double const *tet_get_tetrahedron_attribute_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->output.tetrahedronattributelist;
+}
+
+int32_t const *tet_get_neighbor_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->output.neighborlist;
+}
+
+int32_t const *tet_get_face_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->output.trifacelist;
+}
+
+int32_t const *tet_get_face_middle_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->face_middle_list;
+}
+
+int32_t const *tet_get_face_marker_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->output.trifacemarkerlist;
+}
+
+int32_t tet_get_nintersection(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return 0;
+    }
+    return tetgen->nintersection;
+}
+
+int32_t const *tet_get_intersection_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->intersection_list;
+}
+
+double const *tet_get_input_point_list(struct ExtTetgen *tetgen) {
+    if (tetgen == NULL) {
+        return NULL;
+    }
+    return tetgen->input.pointlist;
+}
+
+int32_t tet_get_input_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t m) {
+    if (tetgen == NULL || tetgen->input.facetlist == NULL || index < 0 || index >= tetgen->input.numberoffacets) {
+        return -1;
+    }
+    tetgenio::facet *fac = &tetgen->input.facetlist[index];
+    if (fac->polygonlist == NULL || polygon < 0 || polygon >= fac->numberofpolygons) {
+        return -1;
+    }
+    tetgenio::polygon *gon = &fac->polygonlist[polygon];
+    if (gon->vertexlist == NULL || m < 0 || m >= gon->numberofvertices) {
+        return -1;
+    }
+    return gon->vertexlist[m];
+}
@@ -8,15 +8,28 @@
 struct ExtTetgen {
     struct tetgenio input;
     struct tetgenio output;
+    int32_t *face_middle_list;  // three middle nodes per boundary face (o2 only)
+    int32_t *intersection_list; // two facet indices per pair of intersecting faces (intersection check only)
+    int32_t nintersection;      // number of pairs in intersection_list
 };
 
-struct ExtTetgen *new_tetgen(int32_t npoint, int32_t nfacet, int32_t const *facet_npoint, int32_t nregion, int32_t nhole);
+struct ExtTetgen *new_tetgen(int32_t npoint,
+                             int32_t nfacet,
+                             int32_t const *facet_npolygon,
+                             int32_t const *facet_polygon_npoint,
+                             int32_t const *facet_nhole,
+                             int32_t nregion,
+                             int32_t nhole);
 
 void drop_tetgen(struct ExtTetgen *tetgen);
 
 int32_t tet_set_point(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z);
 
-int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t m, int32_t p);
+int32_t tet_set_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t m, int32_t p);
+
+int32_t tet_set_facet_hole(struct ExtTetgen *tetgen, int32_t index, int32_t hole, double x, double y, double z);
+
+int32_t tet_set_facet_marker(struct ExtTetgen *tetgen, int32_t index, int32_t marker);
 
 int32_t tet_set_region(struct ExtTetgen *tetgen, int32_t index, double x, double y, double z, int32_t attribute, double max_volume);
 
@@ -26,6 +39,10 @@ int32_t tet_run_delaunay(struct ExtTetgen *tetgen, int32_t verbose);
 
 int32_t tet_run_tetrahedralize(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double global_max_volume, double global_min_angle);
 
+int32_t tet_run_refine(struct ExtTetgen *tetgen, int32_t verbose, int32_t o2, double const *max_volumes, double global_min_angle);
+
+int32_t tet_run_intersection_check(struct ExtTetgen *tetgen, int32_t verbose);
+
 int32_t tet_get_npoint(struct ExtTetgen *tetgen);
 
 int32_t tet_get_ntetrahedron(struct ExtTetgen *tetgen);
@@ -38,4 +55,36 @@ int32_t tet_get_tetrahedron_corner(struct ExtTetgen *tetgen, int32_t index, int3
 
 int32_t tet_get_tetrahedron_attribute(struct ExtTetgen *tetgen, int32_t index);
 
+int32_t tet_get_tetrahedron_neighbor(struct ExtTetgen *tetgen, int32_t index, int32_t face);
+
+int32_t tet_get_nface(struct ExtTetgen *tetgen);
+
+int32_t tet_get_face_corner(struct ExtTetgen *tetgen, int32_t index, int32_t corner);
+
+int32_t tet_get_face_marker(struct ExtTetgen *tetgen, int32_t index);
+
+double const *tet_get_point_list(struct ExtTetgen *tetgen);
+
+int32_t const *tet_get_tetrahedron_list(struct ExtTetgen *tetgen);
+
+int32_t tet_get_ntetrahedron_attribute(struct ExtTetgen *tetgen);
+
+double const *tet_get_tetrahedron_attribute_list(struct ExtTetgen *tetgen);
+
+int32_t const *tet_get_neighbor_list(struct ExtTetgen *tetgen);
+
+int32_t const *tet_get_face_list(struct ExtTetgen *tetgen);
+
+int32_t const *tet_get_face_middle_list(struct ExtTetgen *tetgen);
+
+int32_t const *tet_get_face_marker_list(struct ExtTetgen *tetgen);
+
+int32_t tet_get_nintersection(struct ExtTetgen *tetgen);
+
+int32_t const *tet_get_intersection_list(struct ExtTetgen *tetgen);
+
+double const *tet_get_input_point_list(struct ExtTetgen *tetgen);
+
+int32_t tet_get_input_facet_point(struct ExtTetgen *tetgen, int32_t index, int32_t polygon, int32_t m);
+
 #endif  // INTERFACE_TETGEN_H
\ No newline at end of file
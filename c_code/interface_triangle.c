@@ -1,6 +1,7 @@
 #include "interface_triangle.h"
 
 #include <inttypes.h>
+#include <setjmp.h>
 #include <stdio.h>
 #include <stdlib.h>
 #include <string.h>
@@ -111,6 +112,37 @@ void free_triangle_data(struct triangulateio *data) {
     zero_triangle_data(data);
 }
 
+// Defined in triangle.c (compiled with TRITET_LONGJMP_TRIEXIT)
+// Triangle calls triexit on failure, which jumps to this buffer instead of terminating the process
+extern _Thread_local jmp_buf *triexit_jump;
+
+int32_t call_triangulate(char *command, struct triangulateio *in, struct triangulateio *out, struct triangulateio *vorout) {
+    // Triangle would otherwise write into the arrays of a previous run
+    free_triangle_data(out);
+    free_triangle_data(vorout);
+
+    jmp_buf env;
+    if (setjmp(env) != 0) {
+        triexit_jump = NULL;
+        // output.holelist and output.regionlist may point to the input (see below)
+        out->regionlist = NULL;
+        out->holelist = NULL;
+        free_triangle_data(out);
+        free_triangle_data(vorout);
+        return TRITET_ERROR_TRIANGLE_FAILED;
+    }
+    triexit_jump = &env;
+    triangulate(command, in, out, vorout);
+    triexit_jump = NULL;
+
+    // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
+    // output.holelist gets the content of input.holelist. Thus, these output variables must be set
+    // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
+    out->regionlist = NULL;
+    out->holelist = NULL;
+    return TRITET_SUCCESS;
+}
+
 struct ExtTriangle *new_triangle(int32_t npoint, int32_t nsegment, int32_t nregion, int32_t nhole) {
     if (npoint < 3) {
         return NULL;
@@ -142,6 +174,12 @@ struct ExtTriangle *new_triangle(int32_t npoint, int32_t nsegment, int32_t nregi
             return NULL;
         }
         triangle->input.numberofsegments = nsegment;
+        triangle->input.segmentmarkerlist = (int32_t *)calloc(nsegment, sizeof(int32_t));
+        if (triangle->input.segmentmarkerlist == NULL) {
+            free_triangle_data(&triangle->input);
+            free(triangle);
+            return NULL;
+        }
     }
 
     // regions
@@ -212,6 +250,20 @@ int32_t set_segment(struct ExtTriangle *triangle, int32_t index, int32_t a, int3
     return TRITET_SUCCESS;
 }
 
+int32_t set_segment_marker(struct ExtTriangle *triangle, int32_t index, int32_t marker) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (triangle->input.segmentmarkerlist == NULL) {
+        return TRITET_ERROR_NULL_SEGMENT_MARKER_LIST;
+    }
+    if (index >= triangle->input.numberofsegments) {
+        return TRITET_ERROR_INVALID_SEGMENT_INDEX;
+    }
+    triangle->input.segmentmarkerlist[index] = marker;
+    return TRITET_SUCCESS;
+}
+
 int32_t set_region(struct ExtTriangle *triangle, int32_t index, double x, double y, int32_t attribute, double max_area) {
     // Shewchuk: If you are using the -A and -a switches simultaneously and wish to assign an attribute
     // to some region without imposing an area constraint, use a negative maximum area.
@@ -257,18 +309,17 @@ int32_t run_delaunay(struct ExtTriangle *triangle, int32_t verbose) {
     // Triangulate the points
     // Switches:
     // * `z` -- number everything from zero (z)
+    // * `e` -- output a list of edges (e)
+    // * `n` -- output a list of triangle neighbors (n)
     char command[10];
-    strcpy(command, "z");
+    strcpy(command, "zen");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
-    triangulate(command, &triangle->input, &triangle->output, NULL);
-
-    // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
-    // output.holelist gets the content of input.holelist. Thus, these output variables must be set
-    // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
-    triangle->output.regionlist = NULL;
-    triangle->output.holelist = NULL;
+    int32_t status = call_triangulate(command, &triangle->input, &triangle->output, NULL);
+    if (status != TRITET_SUCCESS) {
+        return status;
+    }
 
     if (verbose == TRITET_TRUE) {
         report(&triangle->output, 1, 1, 0, 0, 0, 0);
@@ -287,19 +338,18 @@ int32_t run_voronoi(struct ExtTriangle *triangle, int32_t verbose) {
     // Triangulate the points
     // Switches:
     // * `z` -- number everything from zero (z)
+    // * `e` -- output a list of edges (e)
+    // * `n` -- output a list of triangle neighbors (n)
     // * `v` -- Voronoi diagram
     char command[10];
-    strcpy(command, "zv");
+    strcpy(command, "zenv");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
-    triangulate(command, &triangle->input, &triangle->output, &triangle->voronoi);
-
-    // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
-    // output.holelist gets the content of input.holelist. Thus, these output variables must be set
-    // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
-    triangle->output.regionlist = NULL;
-    triangle->output.holelist = NULL;
+    int32_t status = call_triangulate(command, &triangle->input, &triangle->output, &triangle->voronoi);
+    if (status != TRITET_SUCCESS) {
+        return status;
+    }
 
     if (verbose == TRITET_TRUE) {
         report(&triangle->voronoi, 0, 0, 0, 0, 1, 1);
@@ -323,8 +373,10 @@ int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t q
     // * `p` -- write a PSLG (p)
     // * `z` -- number everything from zero (z)
     // * `A` -- assign a regional attribute to each element (A)
+    // * `e` -- output a list of edges (e)
+    // * `n` -- output a list of triangle neighbors (n)
     char command[128];
-    strcpy(command, "pzA");
+    strcpy(command, "pzAen");
     if (verbose == TRITET_FALSE) {
         strcat(command, "Q");
     }
@@ -349,13 +401,74 @@ int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t q
     } else {
         strcat(command, "q");
     }
-    triangulate(command, &triangle->input, &triangle->output, NULL);
+    int32_t status = call_triangulate(command, &triangle->input, &triangle->output, NULL);
+    if (status != TRITET_SUCCESS) {
+        return status;
+    }
 
-    // After triangulate (with -p switch), output.regionlist gets the content of input.regionlist and
-    // output.holelist gets the content of input.holelist. Thus, these output variables must be set
-    // to NULL in order to tell free_data to ignore them and avoid a double-free memory issue.
-    triangle->output.regionlist = NULL;
-    triangle->output.holelist = NULL;
+    if (verbose == TRITET_TRUE) {
+        report(&triangle->output, 1, 1, 0, 0, 0, 0);
+    }
+    return TRITET_SUCCESS;
+}
+
+int32_t run_refine(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double const *max_areas, double global_min_angle) {
+    if (triangle == NULL) {
+        return TRITET_ERROR_NULL_DATA;
+    }
+    if (triangle->output.trianglelist == NULL) {
+        return TRITET_ERROR_NULL_TRIANGLE_LIST;
+    }
+
+    // The current output becomes the input mesh (the output is allocated again by Triangle)
+    struct triangulateio mesh = triangle->output;
+    zero_triangle_data(&triangle->output);
+    mesh.trianglearealist = (double *)malloc(mesh.numberoftriangles * sizeof(double));
+    if (mesh.trianglearealist == NULL) {
+        triangle->output = mesh;
+        return TRITET_ERROR_NULL_TRIANGLE_AREA_LIST;
+    }
+    memcpy(mesh.trianglearealist, max_areas, mesh.numberoftriangles * sizeof(double));
+
+    // Refine mesh
+    // Switches:
+    // * `r` -- refine a previously generated mesh (r)
+    // * `p` -- keep the segments and output them (p)
+    // * `z` -- number everything from zero (z)
+    // * `e` -- output a list of edges (e)
+    // * `n` -- output a list of triangle neighbors (n)
+    // * `a` -- read the area constraints from the trianglearealist (a)
+    // Note: the regional attributes are inherited from the input triangles (thus, without `A`)
+    char command[128];
+    strcpy(command, "rpzena");
+    if (verbose == TRITET_FALSE) {
+        strcat(command, "Q");
+    }
+    if (quadratic == TRITET_TRUE) {
+        strcat(command, "o2");
+    }
+    if (global_min_angle > 0.0) {
+        char buf[32];
+        int32_t n = snprintf(buf, 32, "q%.15f", global_min_angle);
+        if (n >= 32) {
+            free(mesh.trianglearealist);
+            mesh.trianglearealist = NULL;
+            triangle->output = mesh;
+            return TRITET_ERROR_STRING_CONCAT;
+        }
+        strcat(command, buf);
+    } else {
+        strcat(command, "q");
+    }
+    int32_t status = call_triangulate(command, &mesh, &triangle->output, NULL);
+    if (status != TRITET_SUCCESS) {
+        // keep the previous mesh
+        free(mesh.trianglearealist);
+        mesh.trianglearealist = NULL;
+        triangle->output = mesh;
+        return status;
+    }
+    free_triangle_data(&mesh);
 
     if (verbose == TRITET_TRUE) {
         report(&triangle->output, 1, 1, 0, 0, 0, 0);
@@ -417,6 +530,170 @@ int32_t get_triangle_attribute(struct ExtTriangle *triangle, int32_t index) {
     }
 }
 
+int32_t get_triangle_neighbor(struct ExtTriangle *triangle, int32_t index, int32_t side) {
+    if (triangle == NULL) {
+        return -1;
+    }
+    if (index < triangle->output.numberoftriangles && (side == 0 || side == 1 || side == 2) && triangle->output.neighborlist != NULL) {
+        return triangle->output.neighborlist[index * 3 + side];
+    } else {
+        return -1;
+    }
+}
+
+int32_t get_point_marker(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofpoints && triangle->output.pointmarkerlist != NULL) {
+        return triangle->output.pointmarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
+int32_t get_nsegment(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    return triangle->output.numberofsegments;
+}
+
+int32_t get_segment_point(struct ExtTriangle *triangle, int32_t index, int32_t side) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofsegments && (side == 0 || side == 1) && triangle->output.segmentlist != NULL) {
+        return triangle->output.segmentlist[index * 2 + side];
+    } else {
+        return 0;
+    }
+}
+
+int32_t get_segment_marker(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofsegments && triangle->output.segmentmarkerlist != NULL) {
+        return triangle->output.segmentmarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
+double const *get_point_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.pointlist;
+}
+
+int32_t const *get_point_marker_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.pointmarkerlist;
+}
+
+int32_t const *get_triangle_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.trianglelist;
+}
+
+int32_t get_ntriangle_attribute(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    return triangle->output.numberoftriangleattributes;
+}
+
+double const *get_triangle_attribute_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.triangleattributelist;
+}
+
+int32_t const *get_neighbor_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.neighborlist;
+}
+
+int32_t const *get_segment_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.segmentlist;
+}
+
+int32_t const *get_segment_marker_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->output.segmentmarkerlist;
+}
+
+double const *get_input_point_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->input.pointlist;
+}
+
+int32_t const *get_input_segment_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->input.segmentlist;
+}
+
+double const *get_input_region_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->input.regionlist;
+}
+
+double const *get_input_hole_list(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return NULL;
+    }
+    return triangle->input.holelist;
+}
+
+int32_t get_nedge(struct ExtTriangle *triangle) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    return triangle->output.numberofedges;
+}
+
+int32_t get_edge_point(struct ExtTriangle *triangle, int32_t index, int32_t side) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofedges && (side == 0 || side == 1) && triangle->output.edgelist != NULL) {
+        return triangle->output.edgelist[index * 2 + side];
+    } else {
+        return 0;
+    }
+}
+
+int32_t get_edge_marker(struct ExtTriangle *triangle, int32_t index) {
+    if (triangle == NULL) {
+        return 0;
+    }
+    if (index < triangle->output.numberofedges && triangle->output.edgemarkerlist != NULL) {
+        return triangle->output.edgemarkerlist[index];
+    } else {
+        return 0;
+    }
+}
+
 int32_t get_voronoi_npoint(struct ExtTriangle *triangle) {
     if (triangle == NULL) {
         return 0;
@@ -25,6 +25,8 @@ int32_t set_point(struct ExtTriangle *triangle, int32_t index, double x, double
 
 int32_t set_segment(struct ExtTriangle *triangle, int32_t index, int32_t a, int32_t b);
 
+int32_t set_segment_marker(struct ExtTriangle *triangle, int32_t index, int32_t marker);
+
 int32_t set_region(struct ExtTriangle *triangle, int32_t index, double x, double y, int32_t attribute, double max_area);
 
 int32_t set_hole(struct ExtTriangle *triangle, int32_t index, double x, double y);
@@ -35,6 +37,8 @@ int32_t run_voronoi(struct ExtTriangle *triangle, int32_t verbose);
 
 int32_t run_triangulate(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double global_max_area, double global_min_angle);
 
+int32_t run_refine(struct ExtTriangle *triangle, int32_t verbose, int32_t quadratic, double const *max_areas, double global_min_angle);
+
 int32_t get_npoint(struct ExtTriangle *triangle);
 
 int32_t get_ntriangle(struct ExtTriangle *triangle);
@@ -47,6 +51,46 @@ int32_t get_triangle_corner(struct ExtTriangle *triangle, int32_t index, int32_t
 
 int32_t get_triangle_attribute(struct ExtTriangle *triangle, int32_t index);
 
+int32_t get_triangle_neighbor(struct ExtTriangle *triangle, int32_t index, int32_t side);
+
+int32_t get_point_marker(struct ExtTriangle *triangle, int32_t index);
+
+int32_t get_nsegment(struct ExtTriangle *triangle);
+
+int32_t get_segment_point(struct ExtTriangle *triangle, int32_t index, int32_t side);
+
+int32_t get_segment_marker(struct ExtTriangle *triangle, int32_t index);
+
+double const *get_point_list(struct ExtTriangle *triangle);
+
+int32_t const *get_point_marker_list(struct ExtTriangle *triangle);
+
+int32_t const *get_triangle_list(struct ExtTriangle *triangle);
+
+int32_t get_ntriangle_attribute(struct ExtTriangle *triangle);
+
+double const *get_triangle_attribute_list(struct ExtTriangle *triangle);
+
+int32_t const *get_neighbor_list(struct ExtTriangle *triangle);
+
+int32_t const *get_segment_list(struct ExtTriangle *triangle);
+
+int32_t const *get_segment_marker_list(struct ExtTriangle *triangle);
+
+double const *get_input_point_list(struct ExtTriangle *triangle);
+
+int32_t const *get_input_segment_list(struct ExtTriangle *triangle);
+
+double const *get_input_region_list(struct ExtTriangle *triangle);
+
+double const *get_input_hole_list(struct ExtTriangle *triangle);
+
+int32_t get_nedge(struct ExtTriangle *triangle);
+
+int32_t get_edge_point(struct ExtTriangle *triangle, int32_t index, int32_t side);
+
+int32_t get_edge_marker(struct ExtTriangle *triangle, int32_t index);
+
 int32_t get_voronoi_npoint(struct ExtTriangle *triangle);
 
 int32_t get_voronoi_point(struct ExtTriangle *triangle, int32_t index, int32_t dim);
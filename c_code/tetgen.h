@@ -89,6 +89,14 @@
 #include <time.h>
 #include <assert.h> 
 
+// With TRITET_THROWING_ASSERT, a failed assertion throws an internal error code
+//   (see terminatetetgen()) instead of aborting the process.
+
+#ifdef TRITET_THROWING_ASSERT
+#  undef assert
+#  define assert(e) ((e) ? (void) 0 : throw 2)
+#endif
+
 // The types 'intptr_t' and 'uintptr_t' are signed and unsigned integer types,
 //   respectively. They are guaranteed to be the same width as a pointer.
 //   They are defined in <stdint.h> by the C99 Standard.
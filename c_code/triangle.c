@@ -344,6 +344,9 @@
 #include <stdlib.h>
 #include <string.h>
 #include <math.h>
+#ifdef TRITET_LONGJMP_TRIEXIT
+#include <setjmp.h>
+#endif /* TRITET_LONGJMP_TRIEXIT */
 #ifndef NO_TIMER
 #include <sys/time.h>
 #endif /* not NO_TIMER */
@@ -1407,6 +1410,15 @@ REAL area;                                      /* The area of the triangle. */
 /**                                                                         **/
 /**                                                                         **/
 
+#ifdef TRITET_LONGJMP_TRIEXIT
+
+/* If set (by the caller of triangulate()), triexit() jumps to this buffer */
+/*   instead of terminating the process.                                   */
+
+_Thread_local jmp_buf *triexit_jump = (jmp_buf *) NULL;
+
+#endif /* TRITET_LONGJMP_TRIEXIT */
+
 #ifdef ANSI_DECLARATORS
 void triexit(int status)
 #else /* not ANSI_DECLARATORS */
@@ -1415,6 +1427,11 @@ int status;
 #endif /* not ANSI_DECLARATORS */
 
 {
+#ifdef TRITET_LONGJMP_TRIEXIT
+  if (triexit_jump != (jmp_buf *) NULL) {
+    longjmp(*triexit_jump, 1);
+  }
+#endif /* TRITET_LONGJMP_TRIEXIT */
   exit(status);
 }
 
@@ -10012,6 +10029,12 @@ struct behavior *b;
     }
   }
   i++;
+  if (i < 2) {
+    /* A single vertex would make divconqrecurse() recurse endlessly. */
+    printf("Error:  Input vertices are all identical.\n");
+    trifree((VOID *) sortarray);
+    triexit(1);
+  }
   if (b->dwyer) {
     /* Re-sort the array of vertices to accommodate alternating cuts. */
     divider = i >> 1;
@@ -15689,8 +15712,18 @@ char **argv;
 #endif /* not TRILIBRARY */
 
 {
+#ifdef TRITET_LONGJMP_TRIEXIT
+  /* The mesh and the behavior change after setjmp() and are read by the  */
+  /*   cleanup code below after longjmp(). Automatic variables would have */
+  /*   indeterminate values there, and they cannot be volatile because    */
+  /*   they are passed to functions taking non-volatile pointers; thus    */
+  /*   they are kept in (thread-local) static storage instead.            */
+  static _Thread_local struct mesh m;
+  static _Thread_local struct behavior b;
+#else /* not TRITET_LONGJMP_TRIEXIT */
   struct mesh m;
   struct behavior b;
+#endif /* not TRITET_LONGJMP_TRIEXIT */
   REAL *holearray;                                        /* Array of holes. */
   REAL *regionarray;   /* Array of regional attributes and area constraints. */
 #ifndef TRILIBRARY
@@ -15702,11 +15735,32 @@ char **argv;
   struct timeval tv0, tv1, tv2, tv3, tv4, tv5, tv6;
   struct timezone tz;
 #endif /* not NO_TIMER */
+#ifdef TRITET_LONGJMP_TRIEXIT
+  jmp_buf cleanup;
+  jmp_buf *volatile caller;                  /* Read after longjmp(). */
+#endif /* TRITET_LONGJMP_TRIEXIT */
 
 #ifndef NO_TIMER
   gettimeofday(&tv0, &tz);
 #endif /* not NO_TIMER */
 
+#ifdef TRITET_LONGJMP_TRIEXIT
+  /* Release the memory pools before jumping back to the caller if an */
+  /*   error occurs. The pools are zeroed first so that the ones not  */
+  /*   yet allocated are ignored by triangledeinit().                 */
+  memset(&m, 0, sizeof(struct mesh));
+  memset(&b, 0, sizeof(struct behavior));
+  caller = triexit_jump;
+  if (caller != (jmp_buf *) NULL) {
+    if (setjmp(cleanup) != 0) {
+      triexit_jump = caller;
+      triangledeinit(&m, &b);
+      longjmp(*caller, 1);
+    }
+    triexit_jump = &cleanup;
+  }
+#endif /* TRITET_LONGJMP_TRIEXIT */
+
   triangleinit(&m);
 #ifdef TRILIBRARY
   parsecommandline(1, &triswitches, &b);
@@ -16000,6 +16054,9 @@ char **argv;
 #endif /* not REDUCED */
 
   triangledeinit(&m, &b);
+#ifdef TRITET_LONGJMP_TRIEXIT
+  triexit_jump = caller;
+#endif /* TRITET_LONGJMP_TRIEXIT */
 #ifndef TRILIBRARY
   return 0;
 #endif /* not TRILIBRARY */
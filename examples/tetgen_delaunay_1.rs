@@ -1,7 +1,8 @@
 use plotpy::Plot;
-use tritet::{StrError, Tetgen};
+use std::error::Error;
+use tritet::Tetgen;
 
-fn main() -> Result<(), StrError> {
+fn main() -> Result<(), Box<dyn Error>> {
     // allocate data for 8 points
     let mut tetgen = Tetgen::new(8, None, None, None)?;
 
@@ -1,7 +1,8 @@
 use plotpy::Plot;
-use tritet::{write_tet_vtu, StrError, Tetgen};
+use std::error::Error;
+use tritet::{write_tet_vtu, Tetgen};
 
-fn main() -> Result<(), StrError> {
+fn main() -> Result<(), Box<dyn Error>> {
     // allocate data for 16 points and 12 facets
     // (one cube/hole inside another cube)
     let mut tetgen = Tetgen::new(
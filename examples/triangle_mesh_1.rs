@@ -1,7 +1,8 @@
 use plotpy::Plot;
-use tritet::{StrError, Triangle};
+use std::error::Error;
+use tritet::Triangle;
 
-fn main() -> Result<(), StrError> {
+fn main() -> Result<(), Box<dyn Error>> {
     // allocate data for 26 points, 22 segments, and 3 holes
     let mut triangle = Triangle::new(26, Some(22), None, Some(3))?;
 
@@ -40,9 +41,7 @@ fn main() -> Result<(), StrError> {
         .set_point(22, 10.0, 25.0)?
         .set_point(23, 20.0, -10.0)?;
     // two dimples
-    triangle
-        .set_point(24, -50.0, 0.0)?
-        .set_point(25, 50.0, 0.0)?;
+    triangle.set_point(24, -50.0, 0.0)?.set_point(25, 50.0, 0.0)?;
 
     // the outer polyhedron
     triangle
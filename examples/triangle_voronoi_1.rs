@@ -1,7 +1,8 @@
 use plotpy::Plot;
-use tritet::{StrError, Triangle};
+use std::error::Error;
+use tritet::Triangle;
 
-fn main() -> Result<(), StrError> {
+fn main() -> Result<(), Box<dyn Error>> {
     // allocate data for 5 points
     let mut triangle = Triangle::new(100, None, None, None)?;
 
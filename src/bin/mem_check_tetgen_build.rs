@@ -1,5 +1,5 @@
 use std::thread;
-use tritet::{StrError, Tetgen};
+use tritet::{Error, Item, Stage, Tetgen};
 
 fn main() {
     println!("Running Mem Check on Tetgen\n");
@@ -20,99 +20,168 @@ fn main() {
     println!("\nDone\n");
 }
 
-fn run_all() -> Result<(), StrError> {
+fn run_all() -> Result<(), Error> {
     let _tet = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), Some(1))?;
     new_captures_some_errors();
     set_point_captures_some_errors()?;
     set_facet_point_captures_some_errors()?;
+    set_facet_marker_captures_some_errors()?;
     set_region_captures_some_errors()?;
     set_hole_captures_some_errors()?;
     generate_methods_capture_some_errors()?;
+    generate_methods_return_tetgen_errors()?;
     generate_delaunay_works()?;
     generate_mesh_works_1()?;
+    generate_mesh_works_2()?;
+    generate_mesh_works_3()?;
     Ok(())
 }
 
 fn new_captures_some_errors() {
-    assert_eq!(Tetgen::new(3, None, None, None).err(), Some("npoint must be ≥ 4"));
+    assert_eq!(
+        Tetgen::new(3, None, None, None).err(),
+        Some(Error::TooFew {
+            item: Item::Point,
+            count: 3,
+            min: 4
+        })
+    );
     assert_eq!(
         Tetgen::new(4, Some(vec![3, 3, 3]), None, None).err(),
-        Some("nfacet must be ≥ 4")
+        Some(Error::TooFew {
+            item: Item::Facet,
+            count: 3,
+            min: 4
+        })
     );
     assert_eq!(
         Tetgen::new(4, Some(vec![3, 3, 3, 2]), None, None).err(),
-        Some("facet npoint must be ≥ 3")
+        Some(Error::TooFew {
+            item: Item::FacetPoint,
+            count: 2,
+            min: 3
+        })
     );
 }
 
-fn set_point_captures_some_errors() -> Result<(), StrError> {
+fn set_point_captures_some_errors() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(4, None, None, None)?;
     assert_eq!(
         tetgen.set_point(5, 0.0, 0.0, 0.0).err(),
-        Some("index of point is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Point,
+            index: 5,
+            limit: 4
+        })
     );
     Ok(())
 }
 
-fn set_facet_point_captures_some_errors() -> Result<(), StrError> {
+fn set_facet_point_captures_some_errors() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(4, None, None, None)?;
     assert_eq!(
         tetgen.set_facet_point(0, 0, 0).err(),
-        Some("cannot set facet point because facet_npoint is None")
+        Some(Error::NotAllocated { item: Item::Facet })
     );
     let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
     assert_eq!(
         tetgen.set_facet_point(5, 0, 0).err(),
-        Some("index of facet is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Facet,
+            index: 5,
+            limit: 4
+        })
     );
     assert_eq!(
         tetgen.set_facet_point(0, 4, 0).err(),
-        Some("index of facet point is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::FacetPoint,
+            index: 4,
+            limit: 3
+        })
     );
     assert_eq!(
         tetgen.set_facet_point(0, 0, 5).err(),
-        Some("id of facet point is out of bounds")
+        Some(Error::PointIdOutOfBounds {
+            item: Item::Facet,
+            id: 5,
+            npoint: 4
+        })
     );
     Ok(())
 }
 
-fn set_region_captures_some_errors() -> Result<(), StrError> {
+fn set_facet_marker_captures_some_errors() -> Result<(), Error> {
+    let mut tetgen = Tetgen::new(4, None, None, None)?;
+    assert_eq!(
+        tetgen.set_facet_marker(0, -1).err(),
+        Some(Error::NotAllocated { item: Item::Facet })
+    );
+    let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+    assert_eq!(
+        tetgen.set_facet_marker(4, -1).err(),
+        Some(Error::IndexOutOfBounds {
+            item: Item::Facet,
+            index: 4,
+            limit: 4
+        })
+    );
+    Ok(())
+}
+
+fn set_region_captures_some_errors() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(4, None, None, None)?;
     assert_eq!(
         tetgen.set_region(0, 0.33, 0.33, 0.33, 1, Some(0.1)).err(),
-        Some("cannot set region because the number of regions is None")
+        Some(Error::NotAllocated { item: Item::Region })
     );
     let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), None)?;
     assert_eq!(
         tetgen.set_region(1, 0.33, 0.33, 0.33, 1, Some(0.1)).err(),
-        Some("index of region is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Region,
+            index: 1,
+            limit: 1
+        })
     );
     Ok(())
 }
 
-fn set_hole_captures_some_errors() -> Result<(), StrError> {
+fn set_hole_captures_some_errors() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(4, None, None, None)?;
     assert_eq!(
         tetgen.set_hole(0, 0.33, 0.33, 0.33).err(),
-        Some("cannot set hole because the number of holes is None")
+        Some(Error::NotAllocated { item: Item::Hole })
     );
     let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), Some(1))?;
     assert_eq!(
         tetgen.set_hole(1, 0.33, 0.33, 0.33).err(),
-        Some("index of hole is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Hole,
+            index: 1,
+            limit: 1
+        })
     );
     Ok(())
 }
 
-fn generate_methods_capture_some_errors() -> Result<(), StrError> {
+fn generate_methods_capture_some_errors() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
     assert_eq!(
         tetgen.generate_delaunay(false).err(),
-        Some("cannot generate Delaunay tetrahedralization because not all points are set")
+        Some(Error::NotAllSet {
+            item: Item::Point,
+            stage: Stage::TetgenDelaunay,
+            missing: vec![0, 1, 2, 3],
+        })
     );
     assert_eq!(
         tetgen.generate_mesh(false, false, None, None).err(),
-        Some("cannot generate mesh of tetrahedra because not all points are set")
+        Some(Error::NotAllSet {
+            item: Item::Point,
+            stage: Stage::TetgenMesh,
+            missing: vec![0, 1, 2, 3],
+        })
     );
     tetgen
         .set_point(0, 0.0, 0.0, 0.0)?
@@ -121,12 +190,65 @@ fn generate_methods_capture_some_errors() -> Result<(), StrError> {
         .set_point(3, 0.0, 0.0, 1.0)?;
     assert_eq!(
         tetgen.generate_mesh(false, false, None, None).err(),
-        Some("cannot generate mesh of tetrahedra because not all facets are set")
+        Some(Error::NotAllSet {
+            item: Item::Facet,
+            stage: Stage::TetgenMesh,
+            missing: vec![0, 1, 2, 3],
+        })
     );
     Ok(())
 }
 
-fn generate_delaunay_works() -> Result<(), StrError> {
+fn generate_methods_return_tetgen_errors() -> Result<(), Error> {
+    let mut tetgen = Tetgen::new(4, None, None, None)?;
+    tetgen
+        .set_point(0, 1.0, 1.0, 1.0)?
+        .set_point(1, 1.0, 1.0, 1.0)?
+        .set_point(2, 1.0, 1.0, 1.0)?
+        .set_point(3, 1.0, 1.0, 1.0)?;
+    assert_eq!(
+        tetgen.generate_delaunay(false).err().and_then(|e| e.stage()),
+        Some(Stage::TetgenDelaunay)
+    );
+    assert_eq!(tetgen.npoint(), 0);
+    assert_eq!(tetgen.ntet(), 0);
+    // the instance can still be used
+    tetgen
+        .set_point(1, 2.0, 1.0, 1.0)?
+        .set_point(2, 1.0, 2.0, 1.0)?
+        .set_point(3, 1.0, 1.0, 2.0)?;
+    tetgen.generate_delaunay(false)?;
+    assert_eq!(tetgen.npoint(), 4);
+    assert_eq!(tetgen.ntet(), 1);
+
+    // two triangles crossing each other (and the tetrahedron)
+    let mut tetgen = Tetgen::new(6, Some(vec![3, 3, 3, 3, 3]), None, None)?;
+    tetgen
+        .set_point(0, 0.0, 0.0, 0.0)?
+        .set_point(1, 1.0, 0.0, 0.0)?
+        .set_point(2, 0.0, 1.0, 0.0)?
+        .set_point(3, 0.0, 0.0, 1.0)?
+        .set_point(4, -1.0, 0.2, 0.2)?
+        .set_point(5, 2.0, 0.2, 0.2)?;
+    let facets = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3], [4, 5, 3]];
+    for (index, points) in facets.iter().enumerate() {
+        for (m, p) in points.iter().enumerate() {
+            tetgen.set_facet_point(index, m, *p)?;
+        }
+    }
+    assert_eq!(
+        tetgen
+            .generate_mesh(false, false, None, None)
+            .err()
+            .and_then(|e| e.stage()),
+        Some(Stage::TetgenMesh)
+    );
+    assert_eq!(tetgen.npoint(), 0);
+    assert_eq!(tetgen.nface(), 0);
+    Ok(())
+}
+
+fn generate_delaunay_works() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(4, None, None, None)?;
     tetgen
         .set_point(0, 0.0, 0.0, 0.0)?
@@ -139,7 +261,7 @@ fn generate_delaunay_works() -> Result<(), StrError> {
     Ok(())
 }
 
-fn generate_mesh_works_1() -> Result<(), StrError> {
+fn generate_mesh_works_1() -> Result<(), Error> {
     let mut tetgen = Tetgen::new(
         16,
         Some(vec![
@@ -236,5 +358,95 @@ fn generate_mesh_works_1() -> Result<(), StrError> {
     tetgen.generate_mesh(false, false, None, None)?;
     assert_eq!(tetgen.ntet(), 116);
     assert_eq!(tetgen.npoint(), 50);
+
+    // refine half of the tetrahedra and generate the middle nodes
+    let max_volumes: Vec<_> = (0..tetgen.ntet())
+        .map(|tet| if tet % 2 == 0 { 0.01 } else { 0.0 })
+        .collect();
+    tetgen.refine_mesh(false, true, &max_volumes, None)?;
+    assert!(tetgen.ntet() > 116);
+    assert_eq!(tetgen.nnode(), 10);
+    Ok(())
+}
+
+fn generate_mesh_works_2() -> Result<(), Error> {
+    let mut tetgen = Tetgen::new(8, Some(vec![4, 4, 4, 4, 4, 4]), None, None)?;
+    tetgen
+        .set_point(0, 0.0, 0.0, 0.0)?
+        .set_point(1, 1.0, 0.0, 0.0)?
+        .set_point(2, 1.0, 1.0, 0.0)?
+        .set_point(3, 0.0, 1.0, 0.0)?
+        .set_point(4, 0.0, 0.0, 1.0)?
+        .set_point(5, 1.0, 0.0, 1.0)?
+        .set_point(6, 1.0, 1.0, 1.0)?
+        .set_point(7, 0.0, 1.0, 1.0)?;
+    let facets = [
+        [0, 4, 7, 3],
+        [1, 2, 6, 5],
+        [0, 1, 5, 4],
+        [2, 3, 7, 6],
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+    ];
+    for (index, points) in facets.iter().enumerate() {
+        for (m, p) in points.iter().enumerate() {
+            tetgen.set_facet_point(index, m, *p)?;
+        }
+        tetgen.set_facet_marker(index, -(index as i32) - 1)?;
+    }
+    tetgen.generate_mesh(false, true, None, None)?;
+    assert_eq!(tetgen.nface(), 12);
+    Ok(())
+}
+
+fn generate_mesh_works_3() -> Result<(), Error> {
+    let mut facet_polygon_npoint = vec![vec![4]; 11];
+    facet_polygon_npoint[5] = vec![4, 4];
+    let mut facet_nhole = vec![0; 11];
+    facet_nhole[5] = 1;
+    let mut tetgen = Tetgen::new_with_facet_polygons(16, Some(facet_polygon_npoint), Some(facet_nhole), None, None)?;
+    tetgen
+        .set_point(0, 0.0, 0.0, 0.0)?
+        .set_point(1, 1.0, 0.0, 0.0)?
+        .set_point(2, 1.0, 1.0, 0.0)?
+        .set_point(3, 0.0, 1.0, 0.0)?
+        .set_point(4, 0.0, 0.0, 1.0)?
+        .set_point(5, 1.0, 0.0, 1.0)?
+        .set_point(6, 1.0, 1.0, 1.0)?
+        .set_point(7, 0.0, 1.0, 1.0)?
+        .set_point(8, 0.25, 0.25, 1.0)?
+        .set_point(9, 0.75, 0.25, 1.0)?
+        .set_point(10, 0.75, 0.75, 1.0)?
+        .set_point(11, 0.25, 0.75, 1.0)?
+        .set_point(12, 0.25, 0.25, 1.5)?
+        .set_point(13, 0.75, 0.25, 1.5)?
+        .set_point(14, 0.75, 0.75, 1.5)?
+        .set_point(15, 0.25, 0.75, 1.5)?;
+    let facets = [
+        [0, 4, 7, 3],
+        [1, 2, 6, 5],
+        [0, 1, 5, 4],
+        [2, 3, 7, 6],
+        [0, 3, 2, 1],
+        [4, 5, 6, 7],
+        [8, 9, 13, 12],
+        [9, 10, 14, 13],
+        [10, 11, 15, 14],
+        [11, 8, 12, 15],
+        [12, 13, 14, 15],
+    ];
+    for (index, points) in facets.iter().enumerate() {
+        for (m, p) in points.iter().enumerate() {
+            tetgen.set_facet_point(index, m, *p)?;
+        }
+    }
+    tetgen
+        .set_facet_polygon_point(5, 1, 0, 8)?
+        .set_facet_polygon_point(5, 1, 1, 9)?
+        .set_facet_polygon_point(5, 1, 2, 10)?
+        .set_facet_polygon_point(5, 1, 3, 11)?;
+    tetgen.set_facet_hole(5, 0, 0.5, 0.5, 1.0)?;
+    tetgen.generate_mesh(false, true, None, None)?;
+    assert!(tetgen.ntet() > 0);
     Ok(())
 }
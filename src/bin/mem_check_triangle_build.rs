@@ -1,6 +1,6 @@
 use std::thread;
 use std::time::Duration;
-use tritet::{StrError, Triangle};
+use tritet::{Error, Item, Stage, Triangle};
 
 fn main() {
     println!("Running Mem Check on Triangle\n");
@@ -12,9 +12,11 @@ fn main() {
             new_captures_some_errors();
             set_point_captures_some_errors().unwrap();
             set_segment_captures_some_errors().unwrap();
+            set_segment_marker_captures_some_errors().unwrap();
             set_region_captures_some_errors().unwrap();
             set_hole_captures_some_errors().unwrap();
             generate_methods_capture_some_errors().unwrap();
+            generate_methods_return_triangle_errors().unwrap();
             delaunay().unwrap();
             voronoi().unwrap();
             mesh().unwrap();
@@ -31,81 +33,142 @@ fn main() {
 }
 
 fn new_captures_some_errors() {
-    assert_eq!(Triangle::new(2, None, None, None).err(), Some("npoint must be ≥ 3"));
+    assert_eq!(
+        Triangle::new(2, None, None, None).err(),
+        Some(Error::TooFew {
+            item: Item::Point,
+            count: 2,
+            min: 3
+        })
+    );
     assert_eq!(
         Triangle::new(3, Some(2), None, None).err(),
-        Some("nsegment must be ≥ 3")
+        Some(Error::TooFew {
+            item: Item::Segment,
+            count: 2,
+            min: 3
+        })
     );
 }
 
-fn set_point_captures_some_errors() -> Result<(), StrError> {
+fn set_point_captures_some_errors() -> Result<(), Error> {
     let mut triangle = Triangle::new(3, None, None, None)?;
     assert_eq!(
         triangle.set_point(4, 0.0, 0.0).err(),
-        Some("index of point is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Point,
+            index: 4,
+            limit: 3
+        })
     );
     Ok(())
 }
 
-fn set_segment_captures_some_errors() -> Result<(), StrError> {
+fn set_segment_captures_some_errors() -> Result<(), Error> {
     let mut triangle = Triangle::new(3, None, None, None)?;
     assert_eq!(
         triangle.set_segment(0, 0, 1).err(),
-        Some("cannot set segment because the number of segments is None")
+        Some(Error::NotAllocated { item: Item::Segment })
     );
     let mut triangle = Triangle::new(3, Some(3), None, None)?;
     assert_eq!(
         triangle.set_segment(4, 0, 1).err(),
-        Some("index of segment is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Segment,
+            index: 4,
+            limit: 3
+        })
     );
     assert_eq!(
         triangle.set_segment(0, 0, 4).err(),
-        Some("id of segment point is out of bounds")
+        Some(Error::PointIdOutOfBounds {
+            item: Item::Segment,
+            id: 4,
+            npoint: 3
+        })
+    );
+    Ok(())
+}
+
+fn set_segment_marker_captures_some_errors() -> Result<(), Error> {
+    let mut triangle = Triangle::new(3, None, None, None)?;
+    assert_eq!(
+        triangle.set_segment_marker(0, -10).err(),
+        Some(Error::NotAllocated { item: Item::Segment })
+    );
+    let mut triangle = Triangle::new(3, Some(3), None, None)?;
+    assert_eq!(
+        triangle.set_segment_marker(4, -10).err(),
+        Some(Error::IndexOutOfBounds {
+            item: Item::Segment,
+            index: 4,
+            limit: 3
+        })
     );
     Ok(())
 }
 
-fn set_region_captures_some_errors() -> Result<(), StrError> {
+fn set_region_captures_some_errors() -> Result<(), Error> {
     let mut triangle = Triangle::new(3, None, None, None)?;
     assert_eq!(
         triangle.set_region(0, 0.33, 0.33, 1, Some(0.1)).err(),
-        Some("cannot set region because the number of regions is None")
+        Some(Error::NotAllocated { item: Item::Region })
     );
     let mut triangle = Triangle::new(3, Some(3), Some(1), None)?;
     assert_eq!(
         triangle.set_region(1, 0.33, 0.33, 1, Some(0.1)).err(),
-        Some("index of region is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Region,
+            index: 1,
+            limit: 1
+        })
     );
     Ok(())
 }
 
-fn set_hole_captures_some_errors() -> Result<(), StrError> {
+fn set_hole_captures_some_errors() -> Result<(), Error> {
     let mut triangle = Triangle::new(3, None, None, None)?;
     assert_eq!(
         triangle.set_hole(0, 0.33, 0.33).err(),
-        Some("cannot set hole because the number of holes is None")
+        Some(Error::NotAllocated { item: Item::Hole })
     );
     let mut triangle = Triangle::new(3, Some(3), Some(1), Some(1))?;
     assert_eq!(
         triangle.set_hole(1, 0.33, 0.33).err(),
-        Some("index of hole is out of bounds")
+        Some(Error::IndexOutOfBounds {
+            item: Item::Hole,
+            index: 1,
+            limit: 1
+        })
     );
     Ok(())
 }
 
-fn generate_methods_capture_some_errors() -> Result<(), StrError> {
+fn generate_methods_capture_some_errors() -> Result<(), Error> {
     let mut triangle = Triangle::new(3, Some(3), None, None)?;
     assert_eq!(
         triangle.generate_delaunay(false).err(),
-        Some("cannot generate Delaunay triangulation because not all points are set")
+        Some(Error::NotAllSet {
+            item: Item::Point,
+            stage: Stage::TriangleDelaunay,
+            missing: vec![0, 1, 2],
+        })
     );
     assert_eq!(
         triangle.generate_voronoi(false).err(),
-        Some("cannot generate Voronoi tessellation because not all points are set")
+        Some(Error::NotAllSet {
+            item: Item::Point,
+            stage: Stage::TriangleVoronoi,
+            missing: vec![0, 1, 2],
+        })
     );
     assert_eq!(
         triangle.generate_mesh(false, false, None, None).err(),
-        Some("cannot generate mesh of triangles because not all points are set")
+        Some(Error::NotAllSet {
+            item: Item::Point,
+            stage: Stage::TriangleMesh,
+            missing: vec![0, 1, 2],
+        })
     );
     triangle
         .set_point(0, 0.0, 0.0)?
@@ -113,12 +176,41 @@ fn generate_methods_capture_some_errors() -> Result<(), StrError> {
         .set_point(2, 0.0, 1.0)?;
     assert_eq!(
         triangle.generate_mesh(false, false, None, None).err(),
-        Some("cannot generate mesh of triangles because not all segments are set")
+        Some(Error::NotAllSet {
+            item: Item::Segment,
+            stage: Stage::TriangleMesh,
+            missing: vec![0, 1, 2],
+        })
     );
     Ok(())
 }
 
-fn delaunay() -> Result<(), StrError> {
+fn generate_methods_return_triangle_errors() -> Result<(), Error> {
+    let mut triangle = Triangle::new(3, None, None, None)?;
+    triangle
+        .set_point(0, 1.0, 1.0)?
+        .set_point(1, 1.0, 1.0)?
+        .set_point(2, 1.0, 1.0)?;
+    assert_eq!(
+        triangle.generate_delaunay(false).err().and_then(|e| e.stage()),
+        Some(Stage::TriangleDelaunay)
+    );
+    assert_eq!(triangle.npoint(), 0);
+    assert_eq!(triangle.ntriangle(), 0);
+    assert_eq!(
+        triangle.generate_voronoi(false).err().and_then(|e| e.stage()),
+        Some(Stage::TriangleVoronoi)
+    );
+    assert_eq!(triangle.voronoi_npoint(), 0);
+    // the instance can still be used
+    triangle.set_point(1, 2.0, 1.0)?.set_point(2, 1.0, 2.0)?;
+    triangle.generate_delaunay(false)?;
+    assert_eq!(triangle.npoint(), 3);
+    assert_eq!(triangle.ntriangle(), 1);
+    Ok(())
+}
+
+fn delaunay() -> Result<(), Error> {
     let mut delaunay = Triangle::new(15, None, None, None)?;
     delaunay
         .set_point(0, 0.0, 0.0)?
@@ -139,7 +231,7 @@ fn delaunay() -> Result<(), StrError> {
     delaunay.generate_delaunay(false)
 }
 
-fn voronoi() -> Result<(), StrError> {
+fn voronoi() -> Result<(), Error> {
     let mut voronoi = Triangle::new(100, None, None, None)?;
     voronoi
         .set_point(0, 0.0476694, 0.809168)?
@@ -245,7 +337,7 @@ fn voronoi() -> Result<(), StrError> {
     voronoi.generate_voronoi(false)
 }
 
-fn mesh() -> Result<(), StrError> {
+fn mesh() -> Result<(), Error> {
     // allocate data for 26 points, 22 segments, 1 region, and 3 holes
     let mut mesh = Triangle::new(26, Some(22), Some(1), Some(3))?;
 
@@ -308,6 +400,11 @@ fn mesh() -> Result<(), StrError> {
     // two nostril segments
     mesh.set_segment(20, 20, 21)?.set_segment(21, 22, 23)?;
 
+    // mark the outer boundary
+    for index in 0..8 {
+        mesh.set_segment_marker(index, -1)?;
+    }
+
     // region
     mesh.set_region(0, 0.0, 0.0, 1, None)?;
 
@@ -317,5 +414,12 @@ fn mesh() -> Result<(), StrError> {
         .set_hole(2, 50.0, 50.0)?; // right eye
 
     // generate mesh without constraints
-    mesh.generate_mesh(false, true, None, None)
+    mesh.generate_mesh(false, false, None, None)?;
+
+    // refine the triangles on the left side and generate the middle nodes
+    let max_areas: Vec<_> = mesh
+        .triangle_points()
+        .map(|coords| if coords[0][0] < 0.0 { 50.0 } else { 0.0 })
+        .collect();
+    mesh.refine_mesh(false, true, &max_areas, None)
 }
@@ -0,0 +1,158 @@
+use crate::{Error, Item, Stage};
+
+/// Number of bits in each word of the bitset
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Tracks which items (e.g., points or segments) have been set
+///
+/// The items are allocated by the c-code with `malloc`; thus, an item that has never been
+/// set holds garbage. This bitset records each index written by the `set_*` functions,
+/// in any order, so the generators can refuse to run with uninitialized data.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct BitSet {
+    words: Vec<u64>, // the flags packed into words
+    len: usize,      // number of items
+    count: usize,    // number of items already set
+}
+
+impl BitSet {
+    /// Allocates a new instance with all flags cleared
+    pub(crate) fn new(len: usize) -> Self {
+        BitSet {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+            count: 0,
+        }
+    }
+
+    /// Marks an item as set
+    ///
+    /// **Note:** The index must be smaller than the number of items.
+    pub(crate) fn insert(&mut self, index: usize) {
+        let mask = 1 << (index % WORD_BITS);
+        let word = &mut self.words[index / WORD_BITS];
+        if *word & mask == 0 {
+            *word |= mask;
+            self.count += 1;
+        }
+    }
+
+    /// Returns whether an item has been set or not
+    pub(crate) fn contains(&self, index: usize) -> bool {
+        index < self.len && self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+
+    /// Returns whether all items have been set or not
+    pub(crate) fn is_full(&self) -> bool {
+        self.count == self.len
+    }
+
+    /// Returns the indices of the items that have not been set yet
+    pub(crate) fn missing(&self) -> Vec<usize> {
+        (0..self.len).filter(|index| !self.contains(*index)).collect()
+    }
+
+    /// Returns an error listing the missing items if not all items have been set
+    pub(crate) fn check(&self, item: Item, stage: Stage) -> Result<(), Error> {
+        if self.is_full() {
+            return Ok(());
+        }
+        Err(Error::NotAllSet {
+            item,
+            stage,
+            missing: self.missing(),
+        })
+    }
+}
+
+/// Returns an error listing the indices of the incomplete sets (e.g., facets with missing points)
+pub(crate) fn check_each(sets: &[BitSet], item: Item, stage: Stage) -> Result<(), Error> {
+    let missing: Vec<_> = (0..sets.len()).filter(|index| !sets[*index].is_full()).collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(Error::NotAllSet { item, stage, missing })
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{check_each, BitSet};
+    use crate::{Error, Item, Stage};
+
+    #[test]
+    fn new_works() {
+        let set = BitSet::new(0);
+        assert!(set.is_full());
+        assert_eq!(set.missing(), &[] as &[usize]);
+        let set = BitSet::new(130);
+        assert_eq!(set.words.len(), 3);
+        assert!(!set.is_full());
+        assert_eq!(set.missing().len(), 130);
+    }
+
+    #[test]
+    fn insert_works_in_any_order() {
+        let mut set = BitSet::new(130);
+        for index in (0..130).rev().filter(|i| i % 2 == 0) {
+            set.insert(index);
+        }
+        set.insert(64); // again
+        assert_eq!(set.count, 65);
+        assert!(set.contains(0));
+        assert!(!set.contains(1));
+        assert!(set.contains(128));
+        assert!(!set.contains(129));
+        assert!(!set.contains(130));
+        assert_eq!(set.missing(), (0..130).filter(|i| i % 2 == 1).collect::<Vec<_>>());
+        for index in (0..130).filter(|i| i % 2 == 1) {
+            set.insert(index);
+        }
+        assert!(set.is_full());
+        assert_eq!(set.missing(), &[] as &[usize]);
+    }
+
+    #[test]
+    fn check_works() {
+        let mut set = BitSet::new(4);
+        set.insert(3);
+        set.insert(1);
+        assert_eq!(
+            set.check(Item::Point, Stage::TriangleMesh).err(),
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TriangleMesh,
+                missing: vec![0, 2],
+            })
+        );
+        set.insert(0);
+        set.insert(2);
+        assert_eq!(set.check(Item::Point, Stage::TriangleMesh), Ok(()));
+    }
+
+    #[test]
+    fn check_each_works() {
+        let mut sets = vec![BitSet::new(3), BitSet::new(0), BitSet::new(2)];
+        assert_eq!(
+            check_each(&sets, Item::Facet, Stage::TetgenMesh).err(),
+            Some(Error::NotAllSet {
+                item: Item::Facet,
+                stage: Stage::TetgenMesh,
+                missing: vec![0, 2],
+            })
+        );
+        sets[2].insert(0);
+        sets[2].insert(1);
+        assert_eq!(
+            check_each(&sets, Item::Facet, Stage::TetgenMesh).err(),
+            Some(Error::NotAllSet {
+                item: Item::Facet,
+                stage: Stage::TetgenMesh,
+                missing: vec![0],
+            })
+        );
+        sets[0] = BitSet::new(0);
+        assert_eq!(check_each(&sets, Item::Facet, Stage::TetgenMesh), Ok(()));
+    }
+}
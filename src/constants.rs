@@ -2,6 +2,12 @@ pub(crate) const TRITET_SUCCESS: i32 = 0;
 
 pub(crate) const TRITET_ERROR_NULL_DATA: i32 = 10;
 pub(crate) const TRITET_ERROR_STRING_CONCAT: i32 = 20;
+pub(crate) const TRITET_ERROR_TRIANGLE_FAILED: i32 = 30;
+pub(crate) const TRITET_ERROR_TETGEN_FAILED: i32 = 40;
+pub(crate) const TRITET_ERROR_TETGEN_OUT_OF_MEMORY: i32 = 41;
+pub(crate) const TRITET_ERROR_TETGEN_INTERNAL_ERROR: i32 = 42;
+pub(crate) const TRITET_ERROR_TETGEN_INVALID_INPUT: i32 = 43;
+pub(crate) const TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED: i32 = 44;
 
 pub(crate) const TRITET_ERROR_NULL_POINT_LIST: i32 = 100;
 pub(crate) const TRITET_ERROR_NULL_SEGMENT_LIST: i32 = 200;
@@ -9,6 +15,14 @@ pub(crate) const TRITET_ERROR_NULL_FACET_LIST: i32 = 300;
 pub(crate) const TRITET_ERROR_NULL_FACET_POLYGON_LIST: i32 = 400;
 pub(crate) const TRITET_ERROR_NULL_REGION_LIST: i32 = 500;
 pub(crate) const TRITET_ERROR_NULL_HOLE_LIST: i32 = 600;
+pub(crate) const TRITET_ERROR_NULL_SEGMENT_MARKER_LIST: i32 = 700;
+pub(crate) const TRITET_ERROR_NULL_FACET_MARKER_LIST: i32 = 800;
+pub(crate) const TRITET_ERROR_NULL_FACE_MIDDLE_LIST: i32 = 900;
+pub(crate) const TRITET_ERROR_NULL_FACET_HOLE_LIST: i32 = 1100;
+pub(crate) const TRITET_ERROR_NULL_TRIANGLE_LIST: i32 = 1200;
+pub(crate) const TRITET_ERROR_NULL_TRIANGLE_AREA_LIST: i32 = 1300;
+pub(crate) const TRITET_ERROR_NULL_TETRAHEDRON_LIST: i32 = 1400;
+pub(crate) const TRITET_ERROR_NULL_TETRAHEDRON_VOLUME_LIST: i32 = 1500;
 
 pub(crate) const TRITET_ERROR_INVALID_POINT_INDEX: i32 = 1000;
 pub(crate) const TRITET_ERROR_INVALID_SEGMENT_INDEX: i32 = 2000;
@@ -19,6 +33,8 @@ pub(crate) const TRITET_ERROR_INVALID_FACET_POINT_INDEX: i32 = 6000;
 pub(crate) const TRITET_ERROR_INVALID_FACET_POINT_ID: i32 = 7000;
 pub(crate) const TRITET_ERROR_INVALID_REGION_INDEX: i32 = 8000;
 pub(crate) const TRITET_ERROR_INVALID_HOLE_INDEX: i32 = 9000;
+pub(crate) const TRITET_ERROR_INVALID_FACET_POLYGON_INDEX: i32 = 10000;
+pub(crate) const TRITET_ERROR_INVALID_FACET_HOLE_INDEX: i32 = 11000;
 
 /// Maps indices used in this library (tritet) to indices used in Triangle
 ///
@@ -35,38 +51,33 @@ pub(crate) const TRITET_ERROR_INVALID_HOLE_INDEX: i32 = 9000;
 /// ```
 pub(crate) const TRITET_TO_TRIANGLE: [usize; 6] = [0, 1, 2, 5, 3, 4];
 
-/// Maps indices used in this library (tritet) to indices used in Tetgen
+/// Maps indices used in Triangle to indices used in this library (tritet)
+///
+/// This is the inverse of [TRITET_TO_TRIANGLE].
+pub(crate) const TRIANGLE_TO_TRITET: [usize; 6] = [0, 1, 2, 4, 5, 3];
+
+/// Maps the side indices used in this library (tritet) to the neighbor indices used in Triangle
+///
+/// Triangle lists the neighbor opposite to each corner, whereas tritet numbers the sides
+/// following the middle nodes; i.e., side `s` contains the middle node `3 + s`.
 ///
 /// ```text
-///       This library (tritet)                          Tetgen
-///               NODES                                  CORNERS
-///             |                                         |
-///             3                                         3
-///            /|`.                                      /|`.
-///            ||  `,                                    ||  `,
-///           / |    ',                                 / |    ',
-///           | |      \                                | |      \
-///          /  |       `.                             /  |       `.
-///          |  |         `,                           |  |         `,
-///         /   7            9                        /   5            4
-///         |   |             \                       |   |             \
-///        /    |              `.                    /    |              `.
-///        |    |                ',                  |    |                ',
-///       8     |                  \                8     |                  \
-///       |     0 ,,_               `.              |     0 ,,_               `.
-///      |     /     ``'-., 6         `.           |     /     ``'-., 9         `.
-///      |    /               `''-.,,_  ',         |    /               `''-.,,_  ',
-///     |    /                        ``'2 ,,     |    /                        ``'2 ,,
-///     |   '                       ,.-``         |   '                       ,.-``
-///    |   4                   _,-'`             |   6                   _,-'`
-///    ' /                 ,.'`                  ' /                 ,.'`
-///   | /             _ 5 `                     | /             _ 7 `
-///   '/          ,-'`                          '/          ,-'`
-///  |/      ,.-``                             |/      ,.-``
-///  /  _,-``                                  /  _,-``
-/// 1 '`                                      1 '`
+/// This library (tritet)      Triangle
+///         SIDES              NEIGHBORS
+///           2                    2
+///          / \                  / \
+///         /   \                /   \
+///        2     1              1     0
+///       /       \            /       \
+///      /         \          /         \
+///     0-----0-----1        0-----2-----1
 /// ```
-pub(crate) const TRITET_TO_TETGEN: [usize; 10] = [0, 1, 2, 3, 6, 7, 9, 5, 8, 4];
+pub(crate) const TRITET_TO_TRIANGLE_SIDE: [usize; 3] = [2, 0, 1];
+
+/// Maps the neighbor indices used in Triangle to the side indices used in this library (tritet)
+///
+/// This is the inverse of [TRITET_TO_TRIANGLE_SIDE].
+pub(crate) const TRIANGLE_TO_TRITET_SIDE: [usize; 3] = [1, 2, 0];
 
 #[cfg(feature = "plotpy")]
 /// Defines a set of "light" colors
@@ -82,5 +93,7 @@ pub(crate) const DARK_COLORS: [&'static str; 12] = [
     "#2f3b22", "#152d32",
 ];
 
+pub(crate) const VTK_TRIANGLE: i32 = 5;
+pub(crate) const VTK_QUADRATIC_TRIANGLE: i32 = 22;
 pub(crate) const VTK_TETRA: i32 = 10;
 pub(crate) const VTK_QUADRATIC_TETRA: i32 = 24;
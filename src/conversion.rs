@@ -6,11 +6,51 @@ pub(crate) fn to_i32(num: usize) -> i32 {
     i32::try_from(num).unwrap()
 }
 
+/// Converts an array allocated by the c-code to a slice (empty if the pointer is null)
+///
+/// # Safety
+///
+/// The pointer must be valid for `len` elements while the returned slice is alive.
+#[inline]
+pub(crate) unsafe fn to_slice<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr, len)
+    }
+}
+
+/// Formats a number with a given number of significant digits, like printf's %g
+pub(crate) fn to_g(value: f64, precision: usize) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return format!("{}", value);
+    }
+    let scientific = format!("{:.*e}", precision - 1, value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap();
+    let exponent: i32 = exponent.parse().unwrap();
+    if exponent < -4 || exponent >= precision as i32 {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim_zeros(mantissa), sign, exponent.abs())
+    } else {
+        let decimals = (precision as i32 - 1 - exponent) as usize;
+        trim_zeros(&format!("{:.*}", decimals, value)).to_string()
+    }
+}
+
+/// Removes the trailing zeros after the decimal point
+fn trim_zeros(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::to_i32;
+    use super::{to_g, to_i32, to_slice};
 
     #[test]
     fn usize_to_i32_works() {
@@ -19,4 +59,27 @@ mod tests {
         let m_i32 = to_i32(x.len());
         assert_eq!(m_i32, 2_i32);
     }
+
+    #[test]
+    fn to_slice_works() {
+        let x = [1.0, 2.0, 3.0];
+        unsafe {
+            assert_eq!(to_slice(x.as_ptr(), 2), &[1.0, 2.0]);
+            assert_eq!(to_slice(x.as_ptr(), 0), &[] as &[f64]);
+            assert_eq!(to_slice(std::ptr::null::<f64>(), 3), &[] as &[f64]);
+        }
+    }
+
+    #[test]
+    fn to_g_works() {
+        assert_eq!(to_g(0.0, 5), "0");
+        assert_eq!(to_g(1.0, 5), "1");
+        assert_eq!(to_g(-2.5, 5), "-2.5");
+        assert_eq!(to_g(1.1547005, 5), "1.1547");
+        assert_eq!(to_g(123456.0, 5), "1.2346e+05");
+        assert_eq!(to_g(99999.9, 5), "1e+05");
+        assert_eq!(to_g(0.0001234567, 5), "0.00012346");
+        assert_eq!(to_g(0.00001234567, 5), "1.2346e-05");
+        assert_eq!(to_g(f64::INFINITY, 5), "inf");
+    }
 }
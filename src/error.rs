@@ -0,0 +1,483 @@
+use crate::constants;
+use crate::{PlcReport, PslgReport};
+use std::fmt;
+use std::sync::Arc;
+
+/// Identifies the kind of item (e.g., point or segment) referred to by an error
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Item {
+    /// An input point (vertex)
+    Point,
+
+    /// A segment of a PSLG given to Triangle
+    Segment,
+
+    /// A region (with an attribute and maximum area or volume)
+    Region,
+
+    /// A hole (a point inside the region to be removed)
+    Hole,
+
+    /// A facet of a PLC given to Tetgen
+    Facet,
+
+    /// A polygon of a facet
+    FacetPolygon,
+
+    /// A point of a facet polygon
+    FacetPoint,
+
+    /// A hole of a facet (a point inside the part of the facet to be removed)
+    FacetHole,
+}
+
+/// Identifies the generator (and algorithm) in which an error occurred
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Stage {
+    /// Triangle's Delaunay triangulation
+    TriangleDelaunay,
+
+    /// Triangle's Voronoi tessellation
+    TriangleVoronoi,
+
+    /// Triangle's mesh generation
+    TriangleMesh,
+
+    /// Triangle's refinement of a previously generated mesh
+    TriangleRefine,
+
+    /// Tetgen's Delaunay tetrahedralization
+    TetgenDelaunay,
+
+    /// Tetgen's mesh generation
+    TetgenMesh,
+
+    /// Tetgen's refinement of a previously generated mesh
+    TetgenRefine,
+}
+
+/// Holds the errors returned by this library
+///
+/// The error carries the context of the failure, such as the index and limit of an
+/// out-of-bounds item, or the status code returned by the c-code. This type implements
+/// [std::error::Error]; thus, it can be converted to `Box<dyn Error>` with the `?` operator.
+///
+/// ```
+/// use tritet::{Error, Item, Triangle};
+///
+/// let mut triangle = Triangle::new(3, None, None, None).unwrap();
+/// assert_eq!(
+///     triangle.set_point(3, 0.0, 0.0).err(),
+///     Some(Error::IndexOutOfBounds {
+///         item: Item::Point,
+///         index: 3,
+///         limit: 3
+///     })
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The number of items passed down to the constructor is too small
+    TooFew { item: Item, count: usize, min: usize },
+
+    /// The items cannot be set because their number was not passed down to the constructor
+    NotAllocated { item: Item },
+
+    /// The index of an item is out of bounds (`index ≥ limit`)
+    IndexOutOfBounds { item: Item, index: usize, limit: usize },
+
+    /// The ID of a point defining an item (e.g., segment) is out of bounds (`id ≥ npoint`)
+    PointIdOutOfBounds { item: Item, id: usize, npoint: usize },
+
+    /// Some items have not been set before calling a generator (`missing` lists their indices)
+    NotAllSet {
+        item: Item,
+        stage: Stage,
+        missing: Vec<usize>,
+    },
+
+    /// The generator (Triangle or Tetgen) failed; e.g., because the input data is invalid
+    GeneratorFailed { stage: Stage, status: i32 },
+
+    /// The c-code returned an unexpected status code
+    Internal { status: i32 },
+
+    /// The c-code could not allocate memory
+    AllocationFailed,
+
+    /// An input argument is invalid
+    InvalidInput(&'static str),
+
+    /// The Planar Straight Line Graph (PSLG) given to Triangle is invalid (see [crate::Triangle::validate_pslg])
+    InvalidPslg(Box<PslgReport>),
+
+    /// The Piecewise Linear Complex (PLC) given to Tetgen is invalid (see [crate::Tetgen::validate_plc])
+    InvalidPlc(Box<PlcReport>),
+
+    /// A file does not follow the expected format
+    InvalidFile { line: usize, message: &'static str },
+
+    /// An input/output operation failed (`path` is None if not operating on a file)
+    Io {
+        message: &'static str,
+        path: Option<String>,
+        source: IoError,
+    },
+}
+
+/// Defines the former error type (a message), replaced by [Error]
+#[deprecated(note = "use tritet::Error instead")]
+pub type StrError = Error;
+
+/// Holds an [std::io::Error] such that [Error] can be cloned and compared
+///
+/// Two values are equal if they have the same [std::io::ErrorKind] and message.
+#[derive(Clone, Debug)]
+pub struct IoError(Arc<std::io::Error>);
+
+impl IoError {
+    /// Returns the kind of the input/output error
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.0.kind()
+    }
+
+    /// Returns a reference to the input/output error
+    pub fn get_ref(&self) -> &std::io::Error {
+        &self.0
+    }
+}
+
+impl From<std::io::Error> for IoError {
+    fn from(error: std::io::Error) -> Self {
+        IoError(Arc::new(error))
+    }
+}
+
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error {
+    /// Returns the status code returned by the c-code, if any
+    pub fn status(&self) -> Option<i32> {
+        match self {
+            Error::GeneratorFailed { status, .. } => Some(*status),
+            Error::Internal { status } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Returns the stage (generator) in which the error occurred, if any
+    pub fn stage(&self) -> Option<Stage> {
+        match self {
+            Error::NotAllSet { stage, .. } => Some(*stage),
+            Error::GeneratorFailed { stage, .. } => Some(*stage),
+            _ => None,
+        }
+    }
+}
+
+impl Item {
+    fn name(&self) -> &'static str {
+        match self {
+            Item::Point => "point",
+            Item::Segment => "segment",
+            Item::Region => "region",
+            Item::Hole => "hole",
+            Item::Facet => "facet",
+            Item::FacetPolygon => "facet polygon",
+            Item::FacetPoint => "facet point",
+            Item::FacetHole => "facet hole",
+        }
+    }
+}
+
+impl Stage {
+    fn generator(&self) -> &'static str {
+        match self {
+            Stage::TriangleDelaunay | Stage::TriangleVoronoi | Stage::TriangleMesh | Stage::TriangleRefine => {
+                "Triangle"
+            }
+            Stage::TetgenDelaunay | Stage::TetgenMesh | Stage::TetgenRefine => "Tetgen",
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Stage::TriangleDelaunay => "Delaunay triangulation",
+            Stage::TriangleVoronoi => "Voronoi tessellation",
+            Stage::TriangleMesh => "mesh of triangles",
+            Stage::TriangleRefine => "refined mesh of triangles",
+            Stage::TetgenDelaunay => "Delaunay tetrahedralization",
+            Stage::TetgenMesh => "mesh of tetrahedra",
+            Stage::TetgenRefine => "refined mesh of tetrahedra",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// Maximum number of missing indices listed by the error message
+const MAX_LISTED_MISSING: usize = 10;
+
+/// Describes a status code returned by the c-code
+fn describe_status(status: i32) -> &'static str {
+    match status {
+        constants::TRITET_ERROR_NULL_DATA => "found NULL data",
+        constants::TRITET_ERROR_STRING_CONCAT => "cannot write string with commands",
+        constants::TRITET_ERROR_TRIANGLE_FAILED => "the input data may be invalid",
+        constants::TRITET_ERROR_TETGEN_FAILED => "the input data may be invalid",
+        constants::TRITET_ERROR_TETGEN_OUT_OF_MEMORY => "out of memory",
+        constants::TRITET_ERROR_TETGEN_INTERNAL_ERROR => {
+            "internal error; the input data may be invalid (e.g., intersecting facets)"
+        }
+        constants::TRITET_ERROR_TETGEN_INVALID_INPUT => "the input data is invalid",
+        constants::TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED => {
+            "the max volumes cannot be satisfied after 20 refinement passes"
+        }
+        constants::TRITET_ERROR_NULL_POINT_LIST => "found NULL point list",
+        constants::TRITET_ERROR_NULL_SEGMENT_LIST => "found NULL segment list",
+        constants::TRITET_ERROR_NULL_FACET_LIST => "found NULL facet list",
+        constants::TRITET_ERROR_NULL_FACET_POLYGON_LIST => "found NULL facet polygon list",
+        constants::TRITET_ERROR_NULL_REGION_LIST => "found NULL region list",
+        constants::TRITET_ERROR_NULL_HOLE_LIST => "found NULL hole list",
+        constants::TRITET_ERROR_NULL_SEGMENT_MARKER_LIST => "found NULL segment marker list",
+        constants::TRITET_ERROR_NULL_FACET_MARKER_LIST => "found NULL facet marker list",
+        constants::TRITET_ERROR_NULL_FACE_MIDDLE_LIST => "cannot allocate the list of middle nodes of faces",
+        constants::TRITET_ERROR_NULL_FACET_HOLE_LIST => "found NULL facet hole list",
+        constants::TRITET_ERROR_NULL_TRIANGLE_LIST => "found NULL triangle list",
+        constants::TRITET_ERROR_NULL_TRIANGLE_AREA_LIST => "cannot allocate the list of triangle area constraints",
+        constants::TRITET_ERROR_NULL_TETRAHEDRON_LIST => "found NULL tetrahedron list",
+        constants::TRITET_ERROR_NULL_TETRAHEDRON_VOLUME_LIST => {
+            "cannot allocate the list of tetrahedron volume constraints"
+        }
+        constants::TRITET_ERROR_INVALID_POINT_INDEX => "found invalid point index",
+        constants::TRITET_ERROR_INVALID_SEGMENT_INDEX => "found invalid segment index",
+        constants::TRITET_ERROR_INVALID_SEGMENT_POINT_ID => "found invalid segment point id",
+        constants::TRITET_ERROR_INVALID_FACET_INDEX => "found invalid facet index",
+        constants::TRITET_ERROR_INVALID_FACET_NUM_POLYGON => "found invalid facet number of polygon",
+        constants::TRITET_ERROR_INVALID_FACET_POINT_INDEX => "found invalid facet point index",
+        constants::TRITET_ERROR_INVALID_FACET_POINT_ID => "found invalid facet point id",
+        constants::TRITET_ERROR_INVALID_REGION_INDEX => "found invalid region index",
+        constants::TRITET_ERROR_INVALID_HOLE_INDEX => "found invalid hole index",
+        constants::TRITET_ERROR_INVALID_FACET_POLYGON_INDEX => "found invalid facet polygon index",
+        constants::TRITET_ERROR_INVALID_FACET_HOLE_INDEX => "found invalid facet hole index",
+        _ => "some error occurred",
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TooFew { item, count, min } => {
+                write!(f, "the number of {}s must be ≥ {} (found {})", item, min, count)
+            }
+            Error::NotAllocated { item } => {
+                write!(f, "cannot set {} because the number of {}s is None", item, item)
+            }
+            Error::IndexOutOfBounds { item, index, limit } => {
+                write!(f, "index of {} is out of bounds ({} ≥ {})", item, index, limit)
+            }
+            Error::PointIdOutOfBounds { item, id, npoint } => {
+                write!(f, "id of {} point is out of bounds ({} ≥ {})", item, id, npoint)
+            }
+            Error::NotAllSet { item, stage, missing } => {
+                write!(
+                    f,
+                    "cannot generate {} because not all {}s are set (missing: ",
+                    stage, item
+                )?;
+                for (i, index) in missing.iter().take(MAX_LISTED_MISSING).enumerate() {
+                    write!(f, "{}{}", if i == 0 { "" } else { ", " }, index)?;
+                }
+                if missing.len() > MAX_LISTED_MISSING {
+                    write!(f, ", ... and {} more", missing.len() - MAX_LISTED_MISSING)?;
+                }
+                write!(f, ")")
+            }
+            Error::GeneratorFailed { stage, status } => write!(
+                f,
+                "{} failed to generate the {}: {} (status = {})",
+                stage.generator(),
+                stage,
+                describe_status(*status),
+                status
+            ),
+            Error::Internal { status } => {
+                write!(f, "INTERNAL ERROR: {} (status = {})", describe_status(*status), status)
+            }
+            Error::AllocationFailed => write!(f, "INTERNAL ERROR: cannot allocate memory"),
+            Error::InvalidInput(message) => write!(f, "{}", message),
+            Error::InvalidPslg(report) => write!(f, "the PSLG is invalid: {}", report),
+            Error::InvalidPlc(report) => write!(f, "the PLC is invalid: {}", report),
+            Error::InvalidFile { line, message } => write!(f, "{} (line {})", message, line),
+            Error::Io { message, path, source } => match path {
+                Some(path) => write!(f, "{} {}: {}", message, path, source),
+                None => write!(f, "{}: {}", message, source),
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source.get_ref()),
+            _ => None,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Error, Item, Stage};
+    use crate::constants;
+
+    #[test]
+    fn display_works() {
+        let error = Error::IndexOutOfBounds {
+            item: Item::FacetPoint,
+            index: 4,
+            limit: 3,
+        };
+        assert_eq!(format!("{}", error), "index of facet point is out of bounds (4 ≥ 3)");
+        let error = Error::NotAllSet {
+            item: Item::Segment,
+            stage: Stage::TriangleMesh,
+            missing: vec![0, 3],
+        };
+        assert_eq!(
+            format!("{}", error),
+            "cannot generate mesh of triangles because not all segments are set (missing: 0, 3)"
+        );
+        let error = Error::NotAllSet {
+            item: Item::Point,
+            stage: Stage::TetgenDelaunay,
+            missing: (0..15).collect(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "cannot generate Delaunay tetrahedralization because not all points are set \
+             (missing: 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, ... and 5 more)"
+        );
+        let error = Error::GeneratorFailed {
+            stage: Stage::TetgenMesh,
+            status: constants::TRITET_ERROR_TETGEN_OUT_OF_MEMORY,
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Tetgen failed to generate the mesh of tetrahedra: out of memory (status = 41)"
+        );
+        let error = Error::GeneratorFailed {
+            stage: Stage::TriangleRefine,
+            status: constants::TRITET_ERROR_TRIANGLE_FAILED,
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Triangle failed to generate the refined mesh of triangles: the input data may be invalid (status = 30)"
+        );
+        let error = Error::GeneratorFailed {
+            stage: Stage::TetgenRefine,
+            status: constants::TRITET_ERROR_TETGEN_INTERNAL_ERROR,
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Tetgen failed to generate the refined mesh of tetrahedra: internal error; \
+             the input data may be invalid (e.g., intersecting facets) (status = 42)"
+        );
+        let error = Error::GeneratorFailed {
+            stage: Stage::TetgenRefine,
+            status: constants::TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED,
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Tetgen failed to generate the refined mesh of tetrahedra: \
+             the max volumes cannot be satisfied after 20 refinement passes (status = 44)"
+        );
+        let error = Error::Internal {
+            status: constants::TRITET_ERROR_NULL_DATA,
+        };
+        assert_eq!(format!("{}", error), "INTERNAL ERROR: found NULL data (status = 10)");
+        let error = Error::InvalidFile {
+            line: 7,
+            message: "cannot parse segment",
+        };
+        assert_eq!(format!("{}", error), "cannot parse segment (line 7)");
+        let error = Error::Io {
+            message: "cannot read file",
+            path: Some("/tmp/data.node".to_string()),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found").into(),
+        };
+        assert_eq!(format!("{}", error), "cannot read file /tmp/data.node: not found");
+        let error = Error::Io {
+            message: "cannot write VTU data",
+            path: None,
+            source: std::io::Error::new(std::io::ErrorKind::WriteZero, "full").into(),
+        };
+        assert_eq!(format!("{}", error), "cannot write VTU data: full");
+    }
+
+    #[test]
+    fn accessors_work() {
+        let error = Error::GeneratorFailed {
+            stage: Stage::TriangleVoronoi,
+            status: constants::TRITET_ERROR_TRIANGLE_FAILED,
+        };
+        assert_eq!(error.status(), Some(30));
+        assert_eq!(error.stage(), Some(Stage::TriangleVoronoi));
+        let error = Error::NotAllocated { item: Item::Hole };
+        assert_eq!(error.status(), None);
+        assert_eq!(error.stage(), None);
+    }
+
+    #[test]
+    fn conversions_work() {
+        fn run() -> Result<(), Box<dyn std::error::Error>> {
+            Err(Error::AllocationFailed)?;
+            Ok(())
+        }
+        let error = run().err().unwrap();
+        assert_eq!(error.to_string(), "INTERNAL ERROR: cannot allocate memory");
+        assert!(error.downcast_ref::<Error>().is_some());
+        #[allow(deprecated)]
+        let error: super::StrError = Error::AllocationFailed;
+        assert_eq!(error, Error::AllocationFailed);
+    }
+
+    #[test]
+    fn source_works() {
+        use std::error::Error as _;
+        let error = Error::Io {
+            message: "cannot read file",
+            path: Some("/tmp/data.node".to_string()),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found").into(),
+        };
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "not found");
+        let io_error = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io_error.kind(), std::io::ErrorKind::NotFound);
+        let clone = error.clone();
+        assert_eq!(clone, error);
+        assert!(Error::AllocationFailed.source().is_none());
+    }
+}
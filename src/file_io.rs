@@ -0,0 +1,279 @@
+use crate::Error;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Returns an input/output error regarding a file
+pub(crate) fn io_error(message: &'static str, path: &Path, error: io::Error) -> Error {
+    Error::Io {
+        message,
+        path: Some(path.display().to_string()),
+        source: error.into(),
+    }
+}
+
+/// Creates a buffered file, creating the parent directory if needed
+pub(crate) fn create_file<P>(full_path: &P) -> Result<BufWriter<File>, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    // create directory
+    let path = Path::new(full_path);
+    if let Some(p) = path.parent() {
+        fs::create_dir_all(p).map_err(|e| io_error("cannot create directory", p, e))?;
+    }
+
+    // create file
+    let file = File::create(path).map_err(|e| io_error("cannot create file", path, e))?;
+    Ok(BufWriter::new(file))
+}
+
+/// Flushes and syncs a file created by [create_file]
+pub(crate) fn close_file<P>(writer: BufWriter<File>, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let path = Path::new(full_path);
+    let file = writer
+        .into_inner()
+        .map_err(|e| io_error("cannot write file", path, e.into_error()))?;
+    file.sync_all().map_err(|e| io_error("cannot sync file", path, e))
+}
+
+/// Writes a buffer (text or bytes) to a file, creating the parent directory if needed
+pub(crate) fn write_file<P, B>(full_path: &P, buffer: &B) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+    B: AsRef<[u8]> + ?Sized,
+{
+    let mut writer = create_file(full_path)?;
+    writer
+        .write_all(buffer.as_ref())
+        .map_err(|e| io_error("cannot write file", Path::new(full_path), e))?;
+    close_file(writer, full_path)
+}
+
+/// Reads the whole contents of a file
+pub(crate) fn read_file<P>(full_path: &P) -> Result<String, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let path = Path::new(full_path);
+    fs::read_to_string(path).map_err(|e| io_error("cannot read file", path, e))
+}
+
+/// Reads the whole contents of a (binary) file
+pub(crate) fn read_file_bytes<P>(full_path: &P) -> Result<Vec<u8>, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let path = Path::new(full_path);
+    fs::read(path).map_err(|e| io_error("cannot read file", path, e))
+}
+
+/// Iterates over the data lines of the text formats used by Triangle and Tetgen
+///
+/// Comments start with `#` and run to the end of the line, unless the instance is allocated by
+/// [DataLines::without_comments]. Blank lines are skipped. Fields are separated by spaces or tabs.
+pub(crate) struct DataLines<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    line: usize,    // (1-based) number of the line returned last
+    comments: bool, // the text after `#` is a comment
+}
+
+impl<'a> DataLines<'a> {
+    /// Allocates a new instance
+    pub(crate) fn new(contents: &'a str) -> Self {
+        DataLines {
+            lines: contents.lines().enumerate(),
+            line: 0,
+            comments: true,
+        }
+    }
+
+    /// Allocates a new instance for formats without comments (e.g., Gmsh's .msh)
+    pub(crate) fn without_comments(contents: &'a str) -> Self {
+        DataLines {
+            lines: contents.lines().enumerate(),
+            line: 0,
+            comments: false,
+        }
+    }
+
+    /// Returns the fields of the next data line, if any
+    pub(crate) fn next_fields(&mut self) -> Option<Vec<&'a str>> {
+        for (i, text) in self.lines.by_ref() {
+            let data = match text.find('#') {
+                Some(pos) if self.comments => &text[..pos],
+                _ => text,
+            };
+            let fields: Vec<&str> = data.split_whitespace().collect();
+            if !fields.is_empty() {
+                self.line = i + 1;
+                return Some(fields);
+            }
+        }
+        None
+    }
+
+    /// Returns the fields of the next data line, requiring at least `min_fields` fields
+    pub(crate) fn expect_fields(&mut self, min_fields: usize, message: &'static str) -> Result<Vec<&'a str>, Error> {
+        match self.next_fields() {
+            Some(fields) => {
+                if fields.len() < min_fields {
+                    return Err(self.error(message));
+                }
+                Ok(fields)
+            }
+            None => Err(Error::InvalidFile {
+                line: self.line + 1,
+                message,
+            }),
+        }
+    }
+
+    /// Parses a field of the line returned last
+    pub(crate) fn parse<T: FromStr>(&self, field: &str, message: &'static str) -> Result<T, Error> {
+        field.parse::<T>().map_err(|_| self.error(message))
+    }
+
+    /// Returns an error referring to the line returned last
+    pub(crate) fn error(&self, message: &'static str) -> Error {
+        Error::InvalidFile {
+            line: self.line,
+            message,
+        }
+    }
+}
+
+/// Reads the points section of .node or .poly files (Triangle and Tetgen)
+///
+/// Returns the coordinates and the number of the first point (0 or 1).
+pub(crate) fn read_node_section(lines: &mut DataLines, ndim: usize) -> Result<(Vec<Vec<f64>>, usize), Error> {
+    let fields = lines.expect_fields(1, "cannot read the points header")?;
+    let npoint: usize = lines.parse(fields[0], "cannot parse the number of points")?;
+    if let Some(field) = fields.get(1) {
+        let dim: usize = lines.parse(field, "cannot parse the space dimension")?;
+        if dim != ndim {
+            return Err(lines.error(if ndim == 2 {
+                "the space dimension must be 2"
+            } else {
+                "the space dimension must be 3"
+            }));
+        }
+    }
+    // the number of points comes from the file; thus, it is not used to preallocate memory
+    let mut points = Vec::new();
+    let mut first_id = 0;
+    for index in 0..npoint {
+        let fields = lines.expect_fields(1 + ndim, "cannot read point")?;
+        if index == 0 {
+            first_id = lines.parse(fields[0], "cannot parse the number of point")?;
+            if first_id > 1 {
+                return Err(lines.error("the number of the first point must be 0 or 1"));
+            }
+        }
+        let mut x = vec![0.0; ndim];
+        for dim in 0..ndim {
+            x[dim] = lines.parse(fields[1 + dim], "cannot parse the coordinates of point")?;
+        }
+        points.push(x);
+    }
+    Ok((points, first_id))
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{read_file, read_node_section, write_file, DataLines};
+    use crate::Error;
+
+    #[test]
+    fn write_and_read_file_work() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_write_and_read_file.txt";
+        write_file(file_path, "hello\n")?;
+        assert_eq!(read_file(file_path)?, "hello\n");
+        match read_file("/tmp/tritet/__does_not_exist__.txt").err() {
+            Some(Error::Io { message, path, source }) => {
+                assert_eq!(message, "cannot read file");
+                assert_eq!(path.as_deref(), Some("/tmp/tritet/__does_not_exist__.txt"));
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+            }
+            _ => panic!("an Io error should be returned"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn data_lines_work() -> Result<(), Error> {
+        let contents = "# comment\n\n  3 2 # three points\n0 1.5\t2\n";
+        let mut lines = DataLines::new(contents);
+        let fields = lines.expect_fields(2, "cannot read header")?;
+        assert_eq!(fields, &["3", "2"]);
+        let n: usize = lines.parse(fields[0], "cannot parse number")?;
+        assert_eq!(n, 3);
+        assert_eq!(
+            lines.parse::<usize>("x", "cannot parse number").err(),
+            Some(Error::InvalidFile {
+                line: 3,
+                message: "cannot parse number"
+            })
+        );
+        assert_eq!(
+            lines.expect_fields(4, "cannot read point").err(),
+            Some(Error::InvalidFile {
+                line: 4,
+                message: "cannot read point"
+            })
+        );
+        assert_eq!(
+            lines.expect_fields(1, "cannot read segment").err(),
+            Some(Error::InvalidFile {
+                line: 5,
+                message: "cannot read segment"
+            })
+        );
+        let mut lines = DataLines::without_comments("# not a comment\n1 10 \"a#b\"\n");
+        assert_eq!(lines.next_fields(), Some(vec!["#", "not", "a", "comment"]));
+        assert_eq!(lines.next_fields(), Some(vec!["1", "10", "\"a#b\""]));
+        assert_eq!(lines.next_fields(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn read_node_section_works() -> Result<(), Error> {
+        let mut lines = DataLines::new("2 3 0 0\n1 0.0 1.0 2.0\n2 3.0 4.0 5.0\n");
+        let (points, first_id) = read_node_section(&mut lines, 3)?;
+        assert_eq!(points, &[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+        assert_eq!(first_id, 1);
+        let mut lines = DataLines::new("2 3 0 0\n");
+        assert_eq!(
+            read_node_section(&mut lines, 2).err(),
+            Some(Error::InvalidFile {
+                line: 1,
+                message: "the space dimension must be 2"
+            })
+        );
+        let mut lines = DataLines::new("1 2 0 0\n2 0.0 1.0\n");
+        assert_eq!(
+            read_node_section(&mut lines, 2).err(),
+            Some(Error::InvalidFile {
+                line: 2,
+                message: "the number of the first point must be 0 or 1"
+            })
+        );
+        // the number of points is not used to preallocate memory
+        let mut lines = DataLines::new("99999999999999999 2 0 0\n0 0.0 0.0\n");
+        assert_eq!(
+            read_node_section(&mut lines, 2).err(),
+            Some(Error::InvalidFile {
+                line: 3,
+                message: "cannot read point"
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,1090 @@
+use crate::file_io::{read_file, write_file, DataLines};
+use crate::{Error, Tetgen, Triangle};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsStr;
+use std::fmt::Write;
+
+/// Defines the version of Gmsh's .msh files (ASCII)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MshVersion {
+    /// The legacy format, still used by many solvers
+    V2_2,
+
+    /// The current format, with entities and blocks of nodes and elements
+    V4_1,
+}
+
+/// Holds the elements of a physical group with the same type (to be written to a .msh file)
+struct MshBlock {
+    dim: usize,             // dimension of the elements
+    physical: i32,          // physical tag (zero means no physical group)
+    element_type: usize,    // Gmsh element type
+    nodes: Vec<Vec<usize>>, // point IDs of each element (Gmsh ordering)
+}
+
+/// Holds an element read from a .msh file
+struct MshElement {
+    element_type: usize, // Gmsh element type
+    physical: i32,       // physical tag (zero means no physical group)
+    nodes: Vec<usize>,   // node tags
+}
+
+/// Holds the coordinates of the nodes read from a .msh file (node tag → x-y-z)
+type MshNodes = HashMap<usize, [f64; 3]>;
+
+/// Holds the IDs of the points of the PSLG or PLC (node tag → point ID)
+type PointIds = HashMap<usize, usize>;
+
+/// Gmsh element types
+const MSH_LINE: usize = 1;
+const MSH_TRIANGLE: usize = 2;
+const MSH_TETRAHEDRON: usize = 4;
+const MSH_LINE3: usize = 8;
+const MSH_TRIANGLE6: usize = 9;
+const MSH_TETRAHEDRON10: usize = 11;
+
+/// Maps tritet's ordering of the nodes of tetrahedra to Gmsh's ordering (the last two middle nodes are swapped)
+const TRITET_TO_GMSH_TET: [usize; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 9, 8];
+
+/// Writes the generated mesh of triangles as a Gmsh's .msh file (ASCII)
+///
+/// The file format is described in [Gmsh's manual](https://gmsh.info/doc/texinfo/gmsh.html#MSH-file-format).
+/// The points are numbered from one. The attribute of each triangle (see [Triangle::triangle_attribute])
+/// is written as the physical tag of the triangle, and the segments are written as lines with their
+/// marker (see [Triangle::segment_marker]) as the physical tag. The 6-node
+/// triangles and the corresponding 3-node lines follow Gmsh's ordering.
+///
+/// # Input
+///
+/// * `version` -- is the version of the file format
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Gmsh considers that elements with physical tag 0 do not belong to any physical group. Thus, use
+/// region attributes and segment markers different than zero if the physical groups are needed.
+pub fn write_tri_msh<P>(triangle: &Triangle, version: MshVersion, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ntriangle = triangle.ntriangle();
+    if ntriangle < 1 {
+        return Err(Error::InvalidInput("there are no triangles to write"));
+    }
+    let nnode = triangle.nnode();
+    let points: Vec<[f64; 3]> = (0..triangle.npoint())
+        .map(|index| [triangle.point(index, 0), triangle.point(index, 1), 0.0])
+        .collect();
+
+    // triangles (the nodes are numbered as in Gmsh)
+    let cell_type = if nnode == 3 { MSH_TRIANGLE } else { MSH_TRIANGLE6 };
+    let mut blocks = BTreeMap::new();
+    let mut middle_nodes = HashMap::new();
+    for index in 0..ntriangle {
+        let nodes: Vec<usize> = (0..nnode).map(|m| triangle.triangle_node(index, m)).collect();
+        if nnode == 6 {
+            for (m, (a, b)) in [(0, 1), (1, 2), (2, 0)].iter().enumerate() {
+                let key = (usize::min(nodes[*a], nodes[*b]), usize::max(nodes[*a], nodes[*b]));
+                middle_nodes.insert(key, nodes[3 + m]);
+            }
+        }
+        add_to_block(
+            &mut blocks,
+            2,
+            triangle.triangle_attribute(index) as i32,
+            cell_type,
+            nodes,
+        );
+    }
+
+    // segments
+    for index in 0..triangle.nsegment() {
+        let marker = triangle.segment_marker(index);
+        let a = triangle.segment_point(index, 0);
+        let b = triangle.segment_point(index, 1);
+        match middle_nodes.get(&(usize::min(a, b), usize::max(a, b))) {
+            Some(c) => add_to_block(&mut blocks, 1, marker, MSH_LINE3, vec![a, b, *c]),
+            None => add_to_block(&mut blocks, 1, marker, MSH_LINE, vec![a, b]),
+        }
+    }
+    let blocks: Vec<MshBlock> = blocks.into_values().collect();
+    write_file(full_path, &msh_buffer(&points, &blocks, version))
+}
+
+/// Writes the generated mesh of tetrahedra as a Gmsh's .msh file (ASCII)
+///
+/// The file format is described in [Gmsh's manual](https://gmsh.info/doc/texinfo/gmsh.html#MSH-file-format).
+/// The points are numbered from one. The attribute of each tetrahedron (see [Tetgen::tet_attribute])
+/// is written as the physical tag of the tetrahedron, and the faces on the boundary are written as triangles
+/// with their marker (see [Tetgen::face_marker]) as the physical tag. The 10-node
+/// tetrahedra and the 6-node triangles follow Gmsh's ordering.
+///
+/// # Input
+///
+/// * `version` -- is the version of the file format
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Gmsh considers that elements with physical tag 0 do not belong to any physical group. Thus, use
+/// region attributes and facet markers different than zero if the physical groups are needed.
+pub fn write_tet_msh<P>(tetgen: &Tetgen, version: MshVersion, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ntet = tetgen.ntet();
+    if ntet < 1 {
+        return Err(Error::InvalidInput("there are no tetrahedra to write"));
+    }
+    let nnode = tetgen.nnode();
+    let points: Vec<[f64; 3]> = (0..tetgen.npoint())
+        .map(|index| [tetgen.point(index, 0), tetgen.point(index, 1), tetgen.point(index, 2)])
+        .collect();
+
+    // tetrahedra
+    let (cell_type, face_type, face_nnode) = if nnode == 4 {
+        (MSH_TETRAHEDRON, MSH_TRIANGLE, 3)
+    } else {
+        (MSH_TETRAHEDRON10, MSH_TRIANGLE6, 6)
+    };
+    let mut blocks = BTreeMap::new();
+    for index in 0..ntet {
+        let nodes = (0..nnode)
+            .map(|m| tetgen.tet_node(index, TRITET_TO_GMSH_TET[m]))
+            .collect();
+        add_to_block(&mut blocks, 3, tetgen.tet_attribute(index) as i32, cell_type, nodes);
+    }
+
+    // faces (the nodes are numbered as in Gmsh)
+    for index in 0..tetgen.nface() {
+        let marker = tetgen.face_marker(index);
+        let nodes = (0..face_nnode).map(|m| tetgen.face_node(index, m)).collect();
+        add_to_block(&mut blocks, 2, marker, face_type, nodes);
+    }
+    let blocks: Vec<MshBlock> = blocks.into_values().collect();
+    write_file(full_path, &msh_buffer(&points, &blocks, version))
+}
+
+/// Reads a Gmsh's .msh file (ASCII; version 2.2 or 4.1) and allocates a Triangle with the Planar Straight Line Graph (PSLG)
+///
+/// The lines (2-node or 3-node) become the segments, and their physical tags become the segment markers.
+/// Only the points on the lines and the points given by point elements are kept, and they are renumbered
+/// from zero in the order of the node tags. If the file also contains triangles or quadrilaterals, one
+/// region is defined at the centroid of the first element of each physical group (with a positive tag),
+/// using the physical tag as the attribute.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// The z-coordinates are ignored. Holes cannot be defined in .msh files; thus they must be set afterwards if needed.
+pub fn read_msh_triangle<P>(full_path: &P) -> Result<Triangle, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let (nodes, elements) = read_msh(full_path)?;
+
+    // segments and regions
+    let mut segments = Vec::new();
+    let mut regions = BTreeMap::new();
+    let mut used = BTreeSet::new();
+    for element in &elements {
+        match element_dim_and_ncorner(element.element_type) {
+            Some((0, _)) => {
+                used.insert(element.nodes[0]);
+            }
+            Some((1, _)) => {
+                used.insert(element.nodes[0]);
+                used.insert(element.nodes[1]);
+                segments.push((element.nodes[0], element.nodes[1], element.physical));
+            }
+            Some((2, ncorner)) if element.physical > 0 && !regions.contains_key(&element.physical) => {
+                regions.insert(element.physical, centroid(&nodes, &element.nodes[..ncorner])?);
+            }
+            _ => (),
+        }
+    }
+    let (points, ids) = renumber(&nodes, &used)?;
+
+    // allocate and set the data
+    let mut triangle = Triangle::new(
+        points.len(),
+        if segments.is_empty() {
+            None
+        } else {
+            Some(segments.len())
+        },
+        if regions.is_empty() { None } else { Some(regions.len()) },
+        None,
+    )?;
+    for (index, x) in points.iter().enumerate() {
+        triangle.set_point(index, x[0], x[1])?;
+    }
+    for (index, (a, b, marker)) in segments.iter().enumerate() {
+        triangle.set_segment(index, ids[a], ids[b])?;
+        if *marker != 0 {
+            triangle.set_segment_marker(index, *marker)?;
+        }
+    }
+    for (index, (physical, x)) in regions.iter().enumerate() {
+        triangle.set_region(index, x[0], x[1], *physical as usize, None)?;
+    }
+    Ok(triangle)
+}
+
+/// Reads a Gmsh's .msh file (ASCII; version 2.2 or 4.1) and allocates a Tetgen with the Piecewise Linear Complex (PLC)
+///
+/// The triangles and quadrilaterals (of any order) become the facets, and their physical tags become the facet
+/// markers. Only the points on the facets and the points given by point elements are kept, and they are renumbered
+/// from zero in the order of the node tags. If the file also contains volume elements (tetrahedra, hexahedra,
+/// prisms, or pyramids), one region is defined at the centroid of the first element of each physical group
+/// (with a positive tag), using the physical tag as the attribute.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Each facet is a single triangle or quadrilateral. Holes cannot be defined in .msh files; thus they must be set
+/// afterwards if needed.
+pub fn read_msh_tetgen<P>(full_path: &P) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let (nodes, elements) = read_msh(full_path)?;
+
+    // facets and regions
+    let mut facets = Vec::new();
+    let mut regions = BTreeMap::new();
+    let mut used = BTreeSet::new();
+    for element in &elements {
+        match element_dim_and_ncorner(element.element_type) {
+            Some((0, _)) => {
+                used.insert(element.nodes[0]);
+            }
+            Some((2, ncorner)) => {
+                used.extend(element.nodes[..ncorner].iter());
+                facets.push((&element.nodes[..ncorner], element.physical));
+            }
+            Some((3, ncorner)) if element.physical > 0 && !regions.contains_key(&element.physical) => {
+                regions.insert(element.physical, centroid(&nodes, &element.nodes[..ncorner])?);
+            }
+            _ => (),
+        }
+    }
+    let (points, ids) = renumber(&nodes, &used)?;
+
+    // allocate and set the data
+    let mut tetgen = Tetgen::new(
+        points.len(),
+        if facets.is_empty() {
+            None
+        } else {
+            Some(facets.iter().map(|(corners, _)| corners.len()).collect())
+        },
+        if regions.is_empty() { None } else { Some(regions.len()) },
+        None,
+    )?;
+    for (index, x) in points.iter().enumerate() {
+        tetgen.set_point(index, x[0], x[1], x[2])?;
+    }
+    for (index, (corners, marker)) in facets.iter().enumerate() {
+        for (m, tag) in corners.iter().enumerate() {
+            tetgen.set_facet_point(index, m, ids[tag])?;
+        }
+        if *marker != 0 {
+            tetgen.set_facet_marker(index, *marker)?;
+        }
+    }
+    for (index, (physical, x)) in regions.iter().enumerate() {
+        tetgen.set_region(index, x[0], x[1], x[2], *physical as usize, None)?;
+    }
+    Ok(tetgen)
+}
+
+/// Adds an element to the block with the same dimension, physical tag, and type
+fn add_to_block(
+    blocks: &mut BTreeMap<(usize, i32, usize), MshBlock>,
+    dim: usize,
+    physical: i32,
+    element_type: usize,
+    nodes: Vec<usize>,
+) {
+    blocks
+        .entry((dim, physical, element_type))
+        .or_insert_with(|| MshBlock {
+            dim,
+            physical,
+            element_type,
+            nodes: Vec::new(),
+        })
+        .nodes
+        .push(nodes);
+}
+
+/// Generates the contents of a .msh file
+///
+/// The blocks must be sorted by dimension and physical tag. One entity is created
+/// for each pair of dimension and physical tag.
+fn msh_buffer(points: &[[f64; 3]], blocks: &[MshBlock], version: MshVersion) -> String {
+    // entities: (dim, physical) → tag (numbered from one for each dimension)
+    let mut entities: BTreeMap<(usize, i32), usize> = BTreeMap::new();
+    let mut nentity = [0; 4];
+    for block in blocks {
+        entities.entry((block.dim, block.physical)).or_insert_with(|| {
+            nentity[block.dim] += 1;
+            nentity[block.dim]
+        });
+    }
+    let nelement: usize = blocks.iter().map(|block| block.nodes.len()).sum();
+    let max_dim = blocks.iter().map(|block| block.dim).max().unwrap_or(0);
+    let mut buffer = String::new();
+    match version {
+        MshVersion::V2_2 => {
+            write!(&mut buffer, "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n").unwrap();
+
+            // nodes
+            writeln!(&mut buffer, "$Nodes\n{}", points.len()).unwrap();
+            for (index, x) in points.iter().enumerate() {
+                writeln!(&mut buffer, "{} {} {} {}", index + 1, x[0], x[1], x[2]).unwrap();
+            }
+            writeln!(&mut buffer, "$EndNodes").unwrap();
+
+            // elements: id type ntag physical entity nodes...
+            writeln!(&mut buffer, "$Elements\n{}", nelement).unwrap();
+            let mut id = 1;
+            for block in blocks {
+                let entity = entities[&(block.dim, block.physical)];
+                for nodes in &block.nodes {
+                    write!(
+                        &mut buffer,
+                        "{} {} 2 {} {}",
+                        id, block.element_type, block.physical, entity
+                    )
+                    .unwrap();
+                    for p in nodes {
+                        write!(&mut buffer, " {}", p + 1).unwrap();
+                    }
+                    writeln!(&mut buffer).unwrap();
+                    id += 1;
+                }
+            }
+            writeln!(&mut buffer, "$EndElements").unwrap();
+        }
+        MshVersion::V4_1 => {
+            write!(&mut buffer, "$MeshFormat\n4.1 0 8\n$EndMeshFormat\n").unwrap();
+
+            // entities: tag bounding-box physical-tags bounding-entities
+            writeln!(&mut buffer, "$Entities\n0 {} {} {}", nentity[1], nentity[2], nentity[3]).unwrap();
+            for ((dim, physical), tag) in &entities {
+                let mut min = [f64::MAX; 3];
+                let mut max = [f64::MIN; 3];
+                for block in blocks.iter().filter(|b| b.dim == *dim && b.physical == *physical) {
+                    for p in block.nodes.iter().flatten() {
+                        for i in 0..3 {
+                            min[i] = f64::min(min[i], points[*p][i]);
+                            max[i] = f64::max(max[i], points[*p][i]);
+                        }
+                    }
+                }
+                write!(
+                    &mut buffer,
+                    "{} {} {} {} {} {} {}",
+                    tag, min[0], min[1], min[2], max[0], max[1], max[2]
+                )
+                .unwrap();
+                if *physical == 0 {
+                    writeln!(&mut buffer, " 0 0").unwrap();
+                } else {
+                    writeln!(&mut buffer, " 1 {} 0", physical).unwrap();
+                }
+            }
+            writeln!(&mut buffer, "$EndEntities").unwrap();
+
+            // nodes: a single block assigned to the first entity with the highest dimension
+            let npoint = points.len();
+            writeln!(
+                &mut buffer,
+                "$Nodes\n1 {} 1 {}\n{} 1 0 {}",
+                npoint, npoint, max_dim, npoint
+            )
+            .unwrap();
+            for index in 0..npoint {
+                writeln!(&mut buffer, "{}", index + 1).unwrap();
+            }
+            for x in points {
+                writeln!(&mut buffer, "{} {} {}", x[0], x[1], x[2]).unwrap();
+            }
+            writeln!(&mut buffer, "$EndNodes").unwrap();
+
+            // elements: one block per dimension, physical tag, and type
+            writeln!(&mut buffer, "$Elements\n{} {} 1 {}", blocks.len(), nelement, nelement).unwrap();
+            let mut id = 1;
+            for block in blocks {
+                let entity = entities[&(block.dim, block.physical)];
+                writeln!(
+                    &mut buffer,
+                    "{} {} {} {}",
+                    block.dim,
+                    entity,
+                    block.element_type,
+                    block.nodes.len()
+                )
+                .unwrap();
+                for nodes in &block.nodes {
+                    write!(&mut buffer, "{}", id).unwrap();
+                    for p in nodes {
+                        write!(&mut buffer, " {}", p + 1).unwrap();
+                    }
+                    writeln!(&mut buffer).unwrap();
+                    id += 1;
+                }
+            }
+            writeln!(&mut buffer, "$EndElements").unwrap();
+        }
+    }
+    buffer
+}
+
+/// Returns the dimension and the number of corners of a Gmsh element type (None if not supported)
+fn element_dim_and_ncorner(element_type: usize) -> Option<(usize, usize)> {
+    match element_type {
+        15 => Some((0, 1)),          // point
+        1 | 8 => Some((1, 2)),       // lines
+        2 | 9 => Some((2, 3)),       // triangles
+        3 | 10 | 16 => Some((2, 4)), // quadrilaterals
+        4 | 11 => Some((3, 4)),      // tetrahedra
+        5 | 12 | 17 => Some((3, 8)), // hexahedra
+        6 | 13 | 18 => Some((3, 6)), // prisms
+        7 | 14 | 19 => Some((3, 5)), // pyramids
+        _ => None,
+    }
+}
+
+/// Returns the number of nodes of a Gmsh element type (None if not supported)
+fn element_nnode(element_type: usize) -> Option<usize> {
+    match element_type {
+        15 => Some(1),
+        1 => Some(2),
+        2 | 8 => Some(3),
+        3 | 4 => Some(4),
+        7 => Some(5),
+        6 | 9 => Some(6),
+        5 | 16 => Some(8),
+        10 => Some(9),
+        11 => Some(10),
+        19 => Some(13),
+        14 => Some(14),
+        18 => Some(15),
+        13 => Some(18),
+        17 => Some(20),
+        12 => Some(27),
+        _ => None,
+    }
+}
+
+/// Returns the centroid of the nodes given by their tags
+fn centroid(nodes: &MshNodes, tags: &[usize]) -> Result<[f64; 3], Error> {
+    let mut x = [0.0; 3];
+    for tag in tags {
+        let coords = nodes.get(tag).ok_or(Error::InvalidInput(
+            "the node tag of element is not defined in the .msh file",
+        ))?;
+        for i in 0..3 {
+            x[i] += coords[i] / (tags.len() as f64);
+        }
+    }
+    Ok(x)
+}
+
+/// Returns the coordinates of the used nodes and the map from node tag to point ID
+fn renumber(nodes: &MshNodes, used: &BTreeSet<usize>) -> Result<(Vec<[f64; 3]>, PointIds), Error> {
+    let mut points = Vec::with_capacity(used.len());
+    let mut ids = HashMap::new();
+    for tag in used {
+        let x = nodes.get(tag).ok_or(Error::InvalidInput(
+            "the node tag of element is not defined in the .msh file",
+        ))?;
+        ids.insert(*tag, points.len());
+        points.push(*x);
+    }
+    Ok((points, ids))
+}
+
+/// Reads the nodes (tag → coordinates) and the elements of a .msh file
+fn read_msh<P>(full_path: &P) -> Result<(MshNodes, Vec<MshElement>), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let contents = read_file(full_path)?;
+    let mut lines = DataLines::without_comments(&contents); // .msh files have no comments
+    let mut version = None;
+    let mut entities: HashMap<(usize, usize), i32> = HashMap::new(); // (dim, tag) → physical
+    let mut nodes = HashMap::new();
+    let mut elements = Vec::new();
+    while let Some(fields) = lines.next_fields() {
+        match fields[0] {
+            "$MeshFormat" => {
+                let fields = lines.expect_fields(3, "cannot read the mesh format")?;
+                if fields[1] != "0" {
+                    return Err(lines.error("binary .msh files are not supported"));
+                }
+                version = match fields[0] {
+                    "2" | "2.0" | "2.1" | "2.2" => Some(MshVersion::V2_2),
+                    "4.1" => Some(MshVersion::V4_1),
+                    _ => return Err(lines.error("the version of the .msh file must be 2.2 or 4.1")),
+                };
+                expect_end(&mut lines, "$EndMeshFormat")?;
+            }
+            "$Entities" => {
+                let fields = lines.expect_fields(4, "cannot read the entities header")?;
+                for dim in 0..4 {
+                    let count: usize = lines.parse(fields[dim], "cannot parse the number of entities")?;
+                    // points have the coordinates only; other entities have the bounding box
+                    let position = if dim == 0 { 4 } else { 7 };
+                    for _ in 0..count {
+                        let fields = lines.expect_fields(position + 1, "cannot read entity")?;
+                        let tag: usize = lines.parse(fields[0], "cannot parse the tag of entity")?;
+                        let nphysical: usize =
+                            lines.parse(fields[position], "cannot parse the number of physical tags of entity")?;
+                        let physical: i32 = match fields.get(position + 1) {
+                            Some(field) if nphysical > 0 => {
+                                lines.parse(field, "cannot parse the physical tag of entity")?
+                            }
+                            _ => 0,
+                        };
+                        entities.insert((dim, tag), physical);
+                    }
+                }
+                expect_end(&mut lines, "$EndEntities")?;
+            }
+            "$Nodes" => match version {
+                Some(MshVersion::V2_2) => {
+                    let fields = lines.expect_fields(1, "cannot read the nodes header")?;
+                    let nnode: usize = lines.parse(fields[0], "cannot parse the number of nodes")?;
+                    for _ in 0..nnode {
+                        let fields = lines.expect_fields(4, "cannot read node")?;
+                        let tag: usize = lines.parse(fields[0], "cannot parse the tag of node")?;
+                        nodes.insert(tag, parse_coordinates(&lines, &fields[1..4])?);
+                    }
+                    expect_end(&mut lines, "$EndNodes")?;
+                }
+                Some(MshVersion::V4_1) => {
+                    let fields = lines.expect_fields(4, "cannot read the nodes header")?;
+                    let nblock: usize = lines.parse(fields[0], "cannot parse the number of node blocks")?;
+                    for _ in 0..nblock {
+                        let fields = lines.expect_fields(4, "cannot read the header of node block")?;
+                        let count: usize = lines.parse(fields[3], "cannot parse the number of nodes in block")?;
+                        let mut tags = Vec::new(); // count is not trusted to preallocate
+                        while tags.len() < count {
+                            for field in lines.expect_fields(1, "cannot read the tags of node block")? {
+                                tags.push(lines.parse::<usize>(field, "cannot parse the tag of node")?);
+                            }
+                        }
+                        for tag in tags {
+                            let fields = lines.expect_fields(3, "cannot read the coordinates of node")?;
+                            nodes.insert(tag, parse_coordinates(&lines, &fields[..3])?);
+                        }
+                    }
+                    expect_end(&mut lines, "$EndNodes")?;
+                }
+                None => return Err(lines.error("the .msh file must start with $MeshFormat")),
+            },
+            "$Elements" => match version {
+                Some(MshVersion::V2_2) => {
+                    let fields = lines.expect_fields(1, "cannot read the elements header")?;
+                    let nelement: usize = lines.parse(fields[0], "cannot parse the number of elements")?;
+                    for _ in 0..nelement {
+                        let fields = lines.expect_fields(3, "cannot read element")?;
+                        let element_type: usize = lines.parse(fields[1], "cannot parse the type of element")?;
+                        let ntag: usize = lines.parse(fields[2], "cannot parse the number of tags of element")?;
+                        if ntag >= fields.len() - 3 {
+                            return Err(lines.error("cannot read element"));
+                        }
+                        let physical: i32 = if ntag > 0 {
+                            lines.parse(fields[3], "cannot parse the physical tag of element")?
+                        } else {
+                            0
+                        };
+                        let nodes = parse_tags(&lines, element_type, &fields[(3 + ntag)..])?;
+                        elements.push(MshElement {
+                            element_type,
+                            physical,
+                            nodes,
+                        });
+                    }
+                    expect_end(&mut lines, "$EndElements")?;
+                }
+                Some(MshVersion::V4_1) => {
+                    let fields = lines.expect_fields(4, "cannot read the elements header")?;
+                    let nblock: usize = lines.parse(fields[0], "cannot parse the number of element blocks")?;
+                    for _ in 0..nblock {
+                        let fields = lines.expect_fields(4, "cannot read the header of element block")?;
+                        let dim: usize = lines.parse(fields[0], "cannot parse the dimension of element block")?;
+                        let tag: usize = lines.parse(fields[1], "cannot parse the entity of element block")?;
+                        let element_type: usize = lines.parse(fields[2], "cannot parse the type of element block")?;
+                        let count: usize = lines.parse(fields[3], "cannot parse the number of elements in block")?;
+                        let physical = entities.get(&(dim, tag)).copied().unwrap_or(0);
+                        for _ in 0..count {
+                            let fields = lines.expect_fields(2, "cannot read element")?;
+                            let nodes = parse_tags(&lines, element_type, &fields[1..])?;
+                            elements.push(MshElement {
+                                element_type,
+                                physical,
+                                nodes,
+                            });
+                        }
+                    }
+                    expect_end(&mut lines, "$EndElements")?;
+                }
+                None => return Err(lines.error("the .msh file must start with $MeshFormat")),
+            },
+            section => {
+                // skip other sections (e.g., $PhysicalNames)
+                if !section.starts_with('$') {
+                    return Err(lines.error("cannot find the beginning of a section"));
+                }
+                let end = format!("$End{}", &section[1..]);
+                loop {
+                    match lines.next_fields() {
+                        Some(fields) if fields[0] == end => break,
+                        Some(_) => (),
+                        None => return Err(lines.error("cannot find the end of a section")),
+                    }
+                }
+            }
+        }
+    }
+    if version.is_none() {
+        return Err(lines.error("the .msh file must start with $MeshFormat"));
+    }
+    Ok((nodes, elements))
+}
+
+/// Reads the next line and checks that it is the end of a section
+fn expect_end(lines: &mut DataLines, end: &str) -> Result<(), Error> {
+    let fields = lines.expect_fields(1, "cannot find the end of a section")?;
+    if fields[0] != end {
+        return Err(lines.error("cannot find the end of a section"));
+    }
+    Ok(())
+}
+
+/// Parses the x-y-z coordinates of a node
+fn parse_coordinates(lines: &DataLines, fields: &[&str]) -> Result<[f64; 3], Error> {
+    let mut x = [0.0; 3];
+    for i in 0..3 {
+        x[i] = lines.parse(fields[i], "cannot parse the coordinates of node")?;
+    }
+    Ok(x)
+}
+
+/// Parses the node tags of an element, checking their number if the element type is supported
+fn parse_tags(lines: &DataLines, element_type: usize, fields: &[&str]) -> Result<Vec<usize>, Error> {
+    if let Some(nnode) = element_nnode(element_type) {
+        if fields.len() != nnode {
+            return Err(lines.error("cannot read element"));
+        }
+    }
+    fields
+        .iter()
+        .map(|field| lines.parse(field, "cannot parse the node tag of element"))
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{read_msh_tetgen, read_msh_triangle, write_tet_msh, write_tri_msh, MshVersion};
+    use crate::file_io::{read_file, write_file};
+    use crate::test_fixtures::set_cube;
+    use crate::{Error, Tetgen, Triangle};
+
+    // Returns a square with two regions and marked boundaries
+    fn square_with_two_regions() -> Result<Triangle, Error> {
+        let mut triangle = Triangle::new(6, Some(7), Some(2), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 2.0, 0.0)?
+            .set_point(3, 2.0, 1.0)?
+            .set_point(4, 1.0, 1.0)?
+            .set_point(5, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 4)?
+            .set_segment(4, 4, 5)?
+            .set_segment(5, 5, 0)?
+            .set_segment(6, 1, 4)?;
+        triangle
+            .set_segment_marker(0, -10)?
+            .set_segment_marker(1, -10)?
+            .set_segment_marker(2, -20)?
+            .set_segment_marker(5, -30)?;
+        triangle
+            .set_region(0, 0.5, 0.5, 1, None)?
+            .set_region(1, 1.5, 0.5, 2, None)?;
+        Ok(triangle)
+    }
+
+    #[test]
+    fn write_tri_msh_captures_some_errors() -> Result<(), Error> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            write_tri_msh(&triangle, MshVersion::V4_1, "/tmp/tritet/test_write_tri_msh_error.msh").err(),
+            Some(Error::InvalidInput("there are no triangles to write"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_msh_works() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, Some(3), Some(1), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.set_segment_marker(0, 5)?;
+        triangle.set_region(0, 0.1, 0.1, 7, None)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.triangle_node(0, 0), 0);
+
+        let file_path = "/tmp/tritet/test_write_tri_msh_v2.msh";
+        write_tri_msh(&triangle, MshVersion::V2_2, file_path)?;
+        let contents = read_file(file_path)?;
+        assert_eq!(
+            contents,
+            "$MeshFormat\n\
+             2.2 0 8\n\
+             $EndMeshFormat\n\
+             $Nodes\n\
+             3\n\
+             1 0 0 0\n\
+             2 1 0 0\n\
+             3 0 1 0\n\
+             $EndNodes\n\
+             $Elements\n\
+             4\n\
+             1 1 2 1 1 3 2\n\
+             2 1 2 1 1 1 3\n\
+             3 1 2 5 2 2 1\n\
+             4 2 2 7 1 1 2 3\n\
+             $EndElements\n"
+        );
+
+        let file_path = "/tmp/tritet/test_write_tri_msh_v4.msh";
+        write_tri_msh(&triangle, MshVersion::V4_1, file_path)?;
+        let contents = read_file(file_path)?;
+        assert_eq!(
+            contents,
+            "$MeshFormat\n\
+             4.1 0 8\n\
+             $EndMeshFormat\n\
+             $Entities\n\
+             0 2 1 0\n\
+             1 0 0 0 1 1 0 1 1 0\n\
+             2 0 0 0 1 0 0 1 5 0\n\
+             1 0 0 0 1 1 0 1 7 0\n\
+             $EndEntities\n\
+             $Nodes\n\
+             1 3 1 3\n\
+             2 1 0 3\n\
+             1\n\
+             2\n\
+             3\n\
+             0 0 0\n\
+             1 0 0\n\
+             0 1 0\n\
+             $EndNodes\n\
+             $Elements\n\
+             3 4 1 4\n\
+             1 1 1 2\n\
+             1 3 2\n\
+             2 1 3\n\
+             1 2 1 1\n\
+             3 2 1\n\
+             2 1 2 1\n\
+             4 1 2 3\n\
+             $EndElements\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_msh_works_o2() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, Some(3), Some(1), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.set_segment_marker(0, 5)?;
+        triangle.set_region(0, 0.1, 0.1, 7, None)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        let file_path = "/tmp/tritet/test_write_tri_msh_o2.msh";
+        write_tri_msh(&triangle, MshVersion::V2_2, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        // the middle nodes 4, 5, 6 are at (0.5,0), (0.5,0.5), (0,0.5) as required by Gmsh
+        assert_eq!(lines[8], "4 0.5 0 0");
+        assert_eq!(lines[9], "5 0.5 0.5 0");
+        assert_eq!(lines[10], "6 0 0.5 0");
+        assert_eq!(lines[14], "1 8 2 1 1 3 2 5");
+        assert_eq!(lines[16], "3 8 2 5 2 2 1 4");
+        assert_eq!(lines[17], "4 9 2 7 1 1 2 3 4 5 6");
+        Ok(())
+    }
+
+    #[test]
+    fn write_tet_msh_works() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen
+            .set_facet_point(0, 0, 0)?
+            .set_facet_point(0, 1, 2)?
+            .set_facet_point(0, 2, 1)?
+            .set_facet_point(1, 0, 0)?
+            .set_facet_point(1, 1, 1)?
+            .set_facet_point(1, 2, 3)?
+            .set_facet_point(2, 0, 0)?
+            .set_facet_point(2, 1, 3)?
+            .set_facet_point(2, 2, 2)?
+            .set_facet_point(3, 0, 1)?
+            .set_facet_point(3, 1, 2)?
+            .set_facet_point(3, 2, 3)?;
+        tetgen.set_facet_marker(0, 10)?.set_facet_marker(3, 20)?;
+        tetgen.set_region(0, 0.1, 0.1, 0.1, 3, None)?;
+        tetgen.generate_mesh(false, true, None, None)?;
+        assert_eq!(tetgen.ntet(), 1);
+        let file_path = "/tmp/tritet/test_write_tet_msh_o2.msh";
+        write_tet_msh(&tetgen, MshVersion::V4_1, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        // one entity for each face marker (0, 10, 20) and one for the region
+        assert_eq!(lines[4], "0 0 3 1");
+        assert_eq!(lines[8], "1 0 0 0 1 1 1 1 3 0");
+        let i = lines.iter().position(|l| *l == "$Elements").unwrap();
+        assert_eq!(lines[i + 1], "4 5 1 5");
+        assert_eq!(lines[i + 2], "2 1 9 2");
+        assert_eq!(lines[i + 5], "2 2 9 1");
+        assert_eq!(lines[i + 7], "2 3 9 1");
+        assert_eq!(lines[i + 9], "3 1 11 1");
+
+        // check the ordering of the 10-node tetrahedron
+        let fields: Vec<usize> = lines[i + 10].split(' ').map(|f| f.parse().unwrap()).collect();
+        let x = |tag: usize| -> Vec<f64> {
+            let j = lines.iter().position(|l| *l == "$Nodes").unwrap() + 2 + 10 + tag;
+            lines[j].split(' ').map(|f| f.parse().unwrap()).collect()
+        };
+        let nodes = &fields[1..];
+        for (m, (a, b)) in [(0, 1), (1, 2), (2, 0), (3, 0), (3, 2), (3, 1)].iter().enumerate() {
+            let (xa, xb, xm) = (x(nodes[*a]), x(nodes[*b]), x(nodes[4 + m]));
+            for i in 0..3 {
+                assert_eq!(xm[i], (xa[i] + xb[i]) / 2.0);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn read_msh_captures_some_errors() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_read_msh_error.msh";
+        write_file(file_path, "$MeshFormat\n4.1 1 8\n$EndMeshFormat\n")?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 2,
+                message: "binary .msh files are not supported"
+            })
+        );
+        write_file(file_path, "$MeshFormat\n4.0 0 8\n$EndMeshFormat\n")?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 2,
+                message: "the version of the .msh file must be 2.2 or 4.1"
+            })
+        );
+        write_file(file_path, "$Nodes\n1\n1 0 0 0\n$EndNodes\n")?;
+        assert_eq!(
+            read_msh_tetgen(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 1,
+                message: "the .msh file must start with $MeshFormat"
+            })
+        );
+        write_file(
+            file_path,
+            "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n\
+             $Nodes\n2\n1 0 0 0\n2 1 0 0\n$EndNodes\n\
+             $Elements\n1\n1 1 2 0 1 1 3\n$EndElements\n",
+        )?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidInput(
+                "the node tag of element is not defined in the .msh file"
+            ))
+        );
+        write_file(file_path, "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n$Nodes\n1\n1 0 0 0\n")?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 7,
+                message: "cannot find the end of a section"
+            })
+        );
+        // the numbers read from the file are not used to preallocate memory
+        write_file(
+            file_path,
+            "$MeshFormat\n4.1 0 8\n$EndMeshFormat\n$Nodes\n1 2 1 2\n2 1 0 99999999999999999\n1\n",
+        )?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 8,
+                message: "cannot read the tags of node block"
+            })
+        );
+        write_file(
+            file_path,
+            "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n\
+             $Nodes\n2\n1 0 0 0\n2 1 0 0\n$EndNodes\n\
+             $Elements\n1\n1 1 18446744073709551615 0 1 2\n$EndElements\n",
+        )?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 11,
+                message: "cannot read element"
+            })
+        );
+        // the number of nodes must match the element type (2 for lines)
+        write_file(
+            file_path,
+            "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n\
+             $Nodes\n2\n1 0 0 0\n2 1 0 0\n$EndNodes\n\
+             $Elements\n1\n1 1 0 3\n$EndElements\n",
+        )?;
+        assert_eq!(
+            read_msh_triangle(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 11,
+                message: "cannot read element"
+            })
+        );
+        write_file(
+            file_path,
+            "$MeshFormat\n4.1 0 8\n$EndMeshFormat\n\
+             $Nodes\n1 2 1 2\n0 1 0 2\n1\n2\n0 0 0\n1 0 0\n$EndNodes\n\
+             $Elements\n1 1 1 1\n1 1 1 1\n1 5\n$EndElements\n",
+        )?;
+        assert_eq!(
+            read_msh_tetgen(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 15,
+                message: "cannot read element"
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_msh_triangle_works() -> Result<(), Error> {
+        // a square made of lines (version 2.2) with a physical name section
+        let file_path = "/tmp/tritet/test_read_msh_triangle.msh";
+        write_file(
+            file_path,
+            "$MeshFormat\n2.2 0 8\n$EndMeshFormat\n\
+             $PhysicalNames\n1\n1 10 \"bottom\"\n$EndPhysicalNames\n\
+             $Nodes\n5\n1 0 0 0\n2 1 0 0\n3 1 1 0\n4 0 1 0\n9 0.5 0.5 0\n$EndNodes\n\
+             $Elements\n5\n\
+             1 1 2 10 1 1 2\n\
+             2 1 2 0 2 2 3\n\
+             3 1 0 3 4\n\
+             4 1 2 0 4 4 1\n\
+             5 15 2 0 5 9\n\
+             $EndElements\n",
+        )?;
+        let mut triangle = read_msh_triangle(file_path)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.npoint(), 5);
+        assert_eq!(triangle.ntriangle(), 4);
+        assert_eq!(triangle.nsegment(), 4);
+        assert_eq!(triangle.segment_marker(0), 10);
+        assert_eq!(triangle.segment_marker(1), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_read_tri_msh_work() -> Result<(), Error> {
+        let mut triangle = square_with_two_regions()?;
+        triangle.generate_mesh(false, true, None, None)?;
+        for (version, file_path) in [
+            (MshVersion::V2_2, "/tmp/tritet/test_write_and_read_tri_msh_v2.msh"),
+            (MshVersion::V4_1, "/tmp/tritet/test_write_and_read_tri_msh_v4.msh"),
+        ] {
+            write_tri_msh(&triangle, version, file_path)?;
+            let mut copy = read_msh_triangle(file_path)?;
+            copy.generate_mesh(false, false, None, None)?;
+            assert_eq!(copy.npoint(), 6);
+            assert_eq!(copy.ntriangle(), triangle.ntriangle());
+            let attributes = |t: &Triangle| {
+                let mut values: Vec<usize> = (0..t.ntriangle()).map(|i| t.triangle_attribute(i)).collect();
+                values.sort();
+                values
+            };
+            assert_eq!(attributes(&copy), attributes(&triangle));
+            assert_eq!(attributes(&copy)[0], 1);
+            assert_eq!(attributes(&copy)[copy.ntriangle() - 1], 2);
+            let markers = |t: &Triangle| {
+                let mut values: Vec<i32> = (0..t.nsegment()).map(|i| t.segment_marker(i)).collect();
+                values.sort();
+                values
+            };
+            assert_eq!(markers(&copy), markers(&triangle));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_read_tet_msh_work() -> Result<(), Error> {
+        // cube with marked faces
+        let mut tetgen = Tetgen::new(8, Some(vec![4; 6]), Some(1), None)?;
+        set_cube(&mut tetgen)?;
+        tetgen.set_region(0, 0.5, 0.5, 0.5, 4, None)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        for (version, file_path) in [
+            (MshVersion::V2_2, "/tmp/tritet/test_write_and_read_tet_msh_v2.msh"),
+            (MshVersion::V4_1, "/tmp/tritet/test_write_and_read_tet_msh_v4.msh"),
+        ] {
+            write_tet_msh(&tetgen, version, file_path)?;
+            let mut copy = read_msh_tetgen(file_path)?;
+            copy.generate_mesh(false, false, None, None)?;
+            assert_eq!(copy.npoint(), 8);
+            assert_eq!(copy.nface(), tetgen.nface());
+            assert!((0..copy.ntet()).all(|i| copy.tet_attribute(i) == 4));
+            let mut markers: Vec<i32> = (0..copy.nface()).map(|i| copy.face_marker(i)).collect();
+            markers.sort();
+            markers.dedup();
+            assert_eq!(markers, &[-6, -5, -4, -3, -2, -1]);
+        }
+        Ok(())
+    }
+}
@@ -1,16 +1,37 @@
 //! Triangle and tetrahedron mesh generators
 
-/// Defines a type alias for the error type as a static string
-pub type StrError = &'static str;
-
+mod bitset;
 mod constants;
 mod conversion;
+mod error;
+mod file_io;
+mod gmsh_files;
+mod mesh;
 mod paraview;
+mod surface_files;
+#[cfg(test)]
+mod test_fixtures;
 mod tetgen;
+mod tetgen_files;
+mod tetgen_quality;
+mod tetgen_validation;
 mod triangle;
+mod triangle_files;
+mod triangle_quality;
+mod triangle_validation;
+pub use crate::error::*;
+pub use crate::gmsh_files::*;
+pub use crate::mesh::*;
 pub use crate::paraview::*;
+pub use crate::surface_files::*;
 pub use crate::tetgen::*;
+pub use crate::tetgen_files::*;
+pub use crate::tetgen_quality::*;
+pub use crate::tetgen_validation::*;
 pub use crate::triangle::*;
+pub use crate::triangle_files::*;
+pub use crate::triangle_quality::*;
+pub use crate::triangle_validation::*;
 
 // run code from README file
 #[cfg(doctest)]
@@ -0,0 +1,511 @@
+use crate::{Tetgen, Triangle};
+
+#[cfg(feature = "plotpy")]
+use crate::constants;
+
+#[cfg(feature = "plotpy")]
+use std::collections::HashMap;
+
+#[cfg(feature = "plotpy")]
+use plotpy::{Canvas, Plot, PolyCode, Text};
+
+/// Holds a mesh of triangles detached from the c-code
+///
+/// The mesh may be created from a [Triangle] after the generation; e.g., with `Mesh2D::from(triangle)`.
+/// In this case, the data allocated by the c-code is released if the `triangle` is moved into the mesh.
+///
+/// **Note:** All indices are are zero-based.
+///
+/// # Example
+///
+/// ```
+/// use tritet::{Error, Mesh2D, Triangle};
+///
+/// fn main() -> Result<(), Error> {
+///     let mut triangle = Triangle::new(3, None, None, None)?;
+///     triangle
+///         .set_point(0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0)?
+///         .set_point(2, 0.0, 1.0)?;
+///     triangle.generate_delaunay(false)?;
+///     let mesh = Mesh2D::from(triangle);
+///     let handle = std::thread::spawn(move || mesh.triangles.len());
+///     assert_eq!(handle.join().unwrap(), 1);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mesh2D {
+    /// Holds the x-y coordinates of the points (npoint)
+    pub points: Vec<[f64; 2]>,
+
+    /// Holds the markers of the points (npoint); see [Triangle::point_marker]
+    pub point_markers: Vec<i32>,
+
+    /// Holds the IDs of the nodes of the triangles (ntriangle × nnode); see [Triangle::triangle_node]
+    pub triangles: Vec<Vec<usize>>,
+
+    /// Holds the attribute IDs of the triangles (ntriangle)
+    pub attributes: Vec<usize>,
+
+    /// Holds the neighbors across the sides of the triangles (ntriangle); see [Triangle::triangle_neighbor]
+    pub neighbors: Vec<[Option<usize>; 3]>,
+
+    /// Holds the IDs of the endpoints of the segments (nsegment)
+    pub segments: Vec<[usize; 2]>,
+
+    /// Holds the markers of the segments (nsegment)
+    pub segment_markers: Vec<i32>,
+}
+
+/// Holds a mesh of tetrahedra detached from the c-code
+///
+/// The mesh may be created from a [Tetgen] after the generation; e.g., with `Mesh3D::from(tetgen)`.
+/// In this case, the data allocated by the c-code is released if the `tetgen` is moved into the mesh.
+///
+/// **Note:** All indices are are zero-based.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mesh3D {
+    /// Holds the x-y-z coordinates of the points (npoint)
+    pub points: Vec<[f64; 3]>,
+
+    /// Holds the IDs of the nodes of the tetrahedra (ntet × nnode); see [Tetgen::tet_node]
+    pub tets: Vec<Vec<usize>>,
+
+    /// Holds the attribute IDs of the tetrahedra (ntet)
+    pub attributes: Vec<usize>,
+
+    /// Holds the neighbors across the faces of the tetrahedra (ntet); see [Tetgen::tet_neighbor]
+    pub neighbors: Vec<[Option<usize>; 4]>,
+
+    /// Holds the IDs of the nodes of the triangular faces on the boundary (nface × 3 or 6); see [Tetgen::face_node]
+    pub faces: Vec<Vec<usize>>,
+
+    /// Holds the markers of the triangular faces on the boundary (nface)
+    pub face_markers: Vec<i32>,
+}
+
+impl From<&Triangle> for Mesh2D {
+    /// Copies the output of Triangle
+    fn from(triangle: &Triangle) -> Self {
+        let nnode = usize::max(triangle.nnode(), 1); // nnode is zero before the generation
+        let mut point_markers = triangle.point_markers().to_vec();
+        if point_markers.is_empty() {
+            point_markers = vec![0; triangle.npoint()];
+        }
+        let mut segment_markers = triangle.segment_markers().to_vec();
+        if segment_markers.is_empty() {
+            segment_markers = vec![0; triangle.nsegment()];
+        }
+        Mesh2D {
+            points: triangle.points().chunks_exact(2).map(|x| [x[0], x[1]]).collect(),
+            point_markers,
+            triangles: triangle.triangles().chunks_exact(nnode).map(|c| c.to_vec()).collect(),
+            attributes: triangle.triangle_attributes(),
+            neighbors: triangle.triangle_neighbors(),
+            segments: triangle.segments(),
+            segment_markers,
+        }
+    }
+}
+
+impl From<Triangle> for Mesh2D {
+    /// Moves the output of Triangle into the mesh and releases the data allocated by the c-code
+    fn from(triangle: Triangle) -> Self {
+        Mesh2D::from(&triangle)
+    }
+}
+
+impl From<&Tetgen> for Mesh3D {
+    /// Copies the output of Tetgen
+    fn from(tetgen: &Tetgen) -> Self {
+        let nnode = usize::max(tetgen.nnode(), 1); // nnode is zero before the generation
+        let nface = tetgen.nface();
+        let faces = tetgen.faces();
+        let face_nnode = faces.len().checked_div(nface).unwrap_or(3);
+        let mut face_markers = tetgen.face_markers().to_vec();
+        if face_markers.is_empty() {
+            face_markers = vec![0; nface];
+        }
+        Mesh3D {
+            points: tetgen.points().chunks_exact(3).map(|x| [x[0], x[1], x[2]]).collect(),
+            tets: tetgen.tets().chunks_exact(nnode).map(|c| c.to_vec()).collect(),
+            attributes: tetgen.tet_attributes(),
+            neighbors: tetgen.tet_neighbors(),
+            faces: faces.chunks_exact(face_nnode).map(|c| c.to_vec()).collect(),
+            face_markers,
+        }
+    }
+}
+
+impl From<Tetgen> for Mesh3D {
+    /// Moves the output of Tetgen into the mesh and releases the data allocated by the c-code
+    fn from(tetgen: Tetgen) -> Self {
+        Mesh3D::from(&tetgen)
+    }
+}
+
+impl Mesh2D {
+    /// Returns the number of nodes on a triangle (e.g., 3 or 6)
+    pub fn nnode(&self) -> usize {
+        match self.triangles.first() {
+            Some(nodes) => nodes.len(),
+            None => 0,
+        }
+    }
+
+    #[cfg(feature = "plotpy")]
+    #[allow(clippy::too_many_arguments)]
+    /// Draw triangles
+    pub fn draw_triangles(
+        &self,
+        plot: &mut Plot,
+        set_range: bool,
+        with_point_ids: bool,
+        with_triangle_ids: bool,
+        with_attribute_ids: bool,
+        fontsize_point_ids: Option<f64>,
+        fontsize_triangle_ids: Option<f64>,
+        fontsize_attribute_ids: Option<f64>,
+    ) {
+        draw_triangles(
+            plot,
+            self.points.as_flattened(),
+            self.triangles.iter().map(|nodes| nodes.as_slice()),
+            &self.attributes,
+            set_range,
+            [with_point_ids, with_triangle_ids, with_attribute_ids],
+            [fontsize_point_ids, fontsize_triangle_ids, fontsize_attribute_ids],
+        );
+    }
+}
+
+impl Mesh3D {
+    /// Returns the number of nodes on a tetrahedron (e.g., 4 or 10)
+    pub fn nnode(&self) -> usize {
+        match self.tets.first() {
+            Some(nodes) => nodes.len(),
+            None => 0,
+        }
+    }
+
+    #[cfg(feature = "plotpy")]
+    #[allow(clippy::too_many_arguments)]
+    /// Draws wireframe representing the edges of tetrahedra
+    pub fn draw_wireframe(
+        &self,
+        plot: &mut Plot,
+        set_range: bool,
+        with_point_ids: bool,
+        with_triangle_ids: bool,
+        with_attribute_ids: bool,
+        fontsize_point_ids: Option<f64>,
+        fontsize_triangle_ids: Option<f64>,
+        fontsize_attribute_ids: Option<f64>,
+    ) {
+        draw_wireframe(
+            plot,
+            self.points.as_flattened(),
+            self.tets.iter().map(|nodes| nodes.as_slice()),
+            &self.attributes,
+            set_range,
+            [with_point_ids, with_triangle_ids, with_attribute_ids],
+            [fontsize_point_ids, fontsize_triangle_ids, fontsize_attribute_ids],
+        );
+    }
+}
+
+/// Allocates the texts with the point IDs, cell IDs, and attribute IDs
+#[cfg(feature = "plotpy")]
+fn new_id_texts(fontsizes: [Option<f64>; 3]) -> [Text; 3] {
+    let mut texts = [Text::new(), Text::new(), Text::new()];
+    texts[0]
+        .set_color("red")
+        .set_align_horizontal("center")
+        .set_align_vertical("center")
+        .set_bbox(true)
+        .set_bbox_facecolor("white")
+        .set_bbox_alpha(0.8)
+        .set_bbox_style("circle");
+    texts[1]
+        .set_color("blue")
+        .set_align_horizontal("center")
+        .set_align_vertical("center");
+    texts[2]
+        .set_color("black")
+        .set_align_horizontal("center")
+        .set_align_vertical("center");
+    for (text, fontsize) in texts.iter_mut().zip(fontsizes) {
+        if let Some(fsz) = fontsize {
+            text.set_fontsize(fsz);
+        }
+    }
+    texts
+}
+
+/// Returns the color of an attribute, assigning the next color of the palette to new attributes
+#[cfg(feature = "plotpy")]
+fn attribute_color(colors: &mut HashMap<usize, usize>, palette: &[&'static str], attribute: usize) -> &'static str {
+    let n = colors.len();
+    let index = *colors.entry(attribute).or_insert(n);
+    palette[index % palette.len()]
+}
+
+/// Draws triangles (used by Triangle and Mesh2D)
+///
+/// # Input
+///
+/// * `points` -- the x-y coordinates of the points (npoint × 2)
+/// * `triangles` -- the nodes of each triangle (only the first three are drawn)
+/// * `attributes` -- the attribute ID of each triangle
+/// * `with_ids` -- whether to draw the point IDs, triangle IDs, and attribute IDs, respectively
+/// * `fontsizes` -- the font sizes of the point IDs, triangle IDs, and attribute IDs, respectively
+#[cfg(feature = "plotpy")]
+pub(crate) fn draw_triangles<'a>(
+    plot: &mut Plot,
+    points: &[f64],
+    triangles: impl Iterator<Item = &'a [usize]>,
+    attributes: &[usize],
+    set_range: bool,
+    with_ids: [bool; 3],
+    fontsizes: [Option<f64>; 3],
+) {
+    let [with_point_ids, with_triangle_ids, with_attribute_ids] = with_ids;
+    let [mut point_ids, mut triangle_ids, mut attribute_ids] = new_id_texts(fontsizes);
+    let mut canvas = Canvas::new();
+    canvas.set_edge_color("black");
+    let mut min = [f64::MAX; 2];
+    let mut max = [f64::MIN; 2];
+    let mut colors: HashMap<usize, usize> = HashMap::new(); // attribute => index of color
+    let mut ntriangle = 0;
+    for (tri, nodes) in triangles.enumerate() {
+        let attribute = attributes[tri];
+        canvas.set_face_color(attribute_color(&mut colors, &constants::LIGHT_COLORS, attribute));
+        canvas.polycurve_begin();
+        let mut xmid = [0.0; 2];
+        for (m, p) in nodes.iter().take(3).enumerate() {
+            let x = &points[(2 * p)..(2 * p + 2)];
+            for (dim, v) in x.iter().enumerate() {
+                min[dim] = f64::min(min[dim], *v);
+                max[dim] = f64::max(max[dim], *v);
+                xmid[dim] += v / 3.0;
+            }
+            let code = if m == 0 { PolyCode::MoveTo } else { PolyCode::LineTo };
+            canvas.polycurve_add(x[0], x[1], code);
+        }
+        canvas.polycurve_end(true);
+        if with_triangle_ids {
+            triangle_ids.draw(xmid[0], xmid[1], format!("{}", tri).as_str());
+        }
+        if with_attribute_ids {
+            let p = nodes[0];
+            let xatt = [(points[2 * p] + xmid[0]) / 2.0, (points[2 * p + 1] + xmid[1]) / 2.0];
+            attribute_ids.draw(xatt[0], xatt[1], format!("[{}]", attribute).as_str());
+        }
+        ntriangle += 1;
+    }
+    if ntriangle == 0 {
+        return;
+    }
+    if with_point_ids {
+        for (p, x) in points.chunks_exact(2).enumerate() {
+            point_ids.draw(x[0], x[1], format!("{}", p).as_str());
+        }
+    }
+    plot.add(&canvas);
+    if with_triangle_ids {
+        plot.add(&triangle_ids);
+    }
+    if with_point_ids {
+        plot.add(&point_ids);
+    }
+    if with_attribute_ids {
+        plot.add(&attribute_ids);
+    }
+    if set_range {
+        plot.set_range(min[0], max[0], min[1], max[1]);
+    }
+}
+
+/// Draws wireframe representing the edges of tetrahedra (used by Tetgen and Mesh3D)
+///
+/// # Input
+///
+/// * `points` -- the x-y-z coordinates of the points (npoint × 3)
+/// * `tets` -- the nodes of each tetrahedron (only the edges between the first four are drawn)
+/// * `attributes` -- the attribute ID of each tetrahedron
+/// * `with_ids` -- whether to draw the point IDs, tetrahedron IDs, and attribute IDs, respectively
+/// * `fontsizes` -- the font sizes of the point IDs, tetrahedron IDs, and attribute IDs, respectively
+#[cfg(feature = "plotpy")]
+pub(crate) fn draw_wireframe<'a>(
+    plot: &mut Plot,
+    points: &[f64],
+    tets: impl Iterator<Item = &'a [usize]>,
+    attributes: &[usize],
+    set_range: bool,
+    with_ids: [bool; 3],
+    fontsizes: [Option<f64>; 3],
+) {
+    const EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+    let [with_point_ids, with_tetrahedron_ids, with_attribute_ids] = with_ids;
+    let [mut point_ids, mut tetrahedron_ids, mut attribute_ids] = new_id_texts(fontsizes);
+    let mut canvas = Canvas::new();
+    let mut min = [f64::MAX; 3];
+    let mut max = [f64::MIN; 3];
+    let mut colors: HashMap<usize, usize> = HashMap::new(); // attribute => index of color
+    let mut ntet = 0;
+    for (tet, nodes) in tets.enumerate() {
+        let attribute = attributes[tet];
+        canvas.set_edge_color(attribute_color(&mut colors, &constants::DARK_COLORS, attribute));
+        let mut xcen = [0.0; 3];
+        for p in nodes.iter().take(4) {
+            for (dim, v) in points[(3 * p)..(3 * p + 3)].iter().enumerate() {
+                min[dim] = f64::min(min[dim], *v);
+                max[dim] = f64::max(max[dim], *v);
+                xcen[dim] += v / 4.0;
+            }
+        }
+        for (ma, mb) in &EDGES {
+            let xa = &points[(3 * nodes[*ma])..];
+            let xb = &points[(3 * nodes[*mb])..];
+            canvas.polyline_3d_begin();
+            canvas.polyline_3d_add(xa[0], xa[1], xa[2]);
+            canvas.polyline_3d_add(xb[0], xb[1], xb[2]);
+            canvas.polyline_3d_end();
+        }
+        if with_tetrahedron_ids {
+            tetrahedron_ids.draw_3d(xcen[0], xcen[1], xcen[2], format!("{}", tet).as_str());
+        }
+        if with_attribute_ids {
+            let x = &points[(3 * nodes[0])..];
+            let xatt = [(x[0] + xcen[0]) / 2.0, (x[1] + xcen[1]) / 2.0, (x[2] + xcen[2]) / 2.0];
+            attribute_ids.draw_3d(xatt[0], xatt[1], xatt[2], format!("[{}]", attribute).as_str());
+        }
+        ntet += 1;
+    }
+    if ntet == 0 {
+        return;
+    }
+    if with_point_ids {
+        for (p, x) in points.chunks_exact(3).enumerate() {
+            point_ids.draw_3d(x[0], x[1], x[2], format!("{}", p).as_str());
+        }
+    }
+    plot.add(&canvas);
+    if with_tetrahedron_ids {
+        plot.add(&tetrahedron_ids);
+    }
+    if with_point_ids {
+        plot.add(&point_ids);
+    }
+    if with_attribute_ids {
+        plot.add(&attribute_ids);
+    }
+    if set_range {
+        plot.set_range_3d(min[0], max[0], min[1], max[1], min[2], max[2]);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{Mesh2D, Mesh3D};
+    use crate::test_fixtures::cube;
+    use crate::{Error, Tetgen, Triangle};
+
+    fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+
+    #[test]
+    fn derive_works() {
+        assert_send_sync_clone::<Mesh2D>();
+        assert_send_sync_clone::<Mesh3D>();
+    }
+
+    #[test]
+    fn mesh_2d_from_triangle_works() -> Result<(), Error> {
+        let mut triangle = Triangle::new(4, Some(4), Some(1), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?;
+        triangle.set_segment_marker(0, -10)?;
+        triangle.set_region(0, 0.5, 0.5, 7, None)?;
+        triangle.generate_mesh(false, true, Some(0.2), None)?;
+        let mesh = Mesh2D::from(&triangle);
+        assert_eq!(mesh.nnode(), 6);
+        assert_eq!(mesh.points.len(), triangle.npoint());
+        assert_eq!(mesh.point_markers.len(), triangle.npoint());
+        assert_eq!(mesh.triangles.len(), triangle.ntriangle());
+        assert_eq!(mesh.attributes, vec![7; triangle.ntriangle()]);
+        assert_eq!(mesh.neighbors.len(), triangle.ntriangle());
+        assert_eq!(mesh.segments.len(), triangle.nsegment());
+        assert_eq!(mesh.segment_markers.len(), triangle.nsegment());
+        for (i, nodes) in mesh.triangles.iter().enumerate() {
+            for (m, p) in nodes.iter().enumerate() {
+                assert_eq!(*p, triangle.triangle_node(i, m));
+                assert_eq!(mesh.points[*p], [triangle.point(*p, 0), triangle.point(*p, 1)]);
+            }
+            for side in 0..3 {
+                assert_eq!(mesh.neighbors[i][side], triangle.triangle_neighbor(i, side));
+            }
+        }
+        for (i, marker) in mesh.segment_markers.iter().enumerate() {
+            assert_eq!(*marker, triangle.segment_marker(i));
+        }
+        let copy = mesh.clone();
+        let moved = Mesh2D::from(triangle);
+        assert_eq!(moved, copy);
+        Ok(())
+    }
+
+    #[test]
+    fn mesh_3d_from_tetgen_works() -> Result<(), Error> {
+        let mut tetgen = cube()?;
+        tetgen.generate_mesh(false, true, None, None)?;
+        let mesh = Mesh3D::from(&tetgen);
+        assert_eq!(mesh.nnode(), 10);
+        assert_eq!(mesh.points.len(), tetgen.npoint());
+        assert_eq!(mesh.tets.len(), tetgen.ntet());
+        assert_eq!(mesh.attributes, vec![0; tetgen.ntet()]);
+        assert_eq!(mesh.faces.len(), 12);
+        for (i, nodes) in mesh.tets.iter().enumerate() {
+            for (m, p) in nodes.iter().enumerate() {
+                assert_eq!(*p, tetgen.tet_node(i, m));
+            }
+            for face in 0..4 {
+                assert_eq!(mesh.neighbors[i][face], tetgen.tet_neighbor(i, face));
+            }
+        }
+        for (i, nodes) in mesh.faces.iter().enumerate() {
+            assert_eq!(nodes.len(), 6);
+            for (m, p) in nodes.iter().enumerate() {
+                assert_eq!(*p, tetgen.face_node(i, m));
+            }
+            assert_eq!(mesh.face_markers[i], tetgen.face_marker(i));
+        }
+        let moved = Mesh3D::from(tetgen);
+        let handle = std::thread::spawn(move || moved.tets.len());
+        assert_eq!(handle.join().unwrap(), mesh.tets.len());
+        Ok(())
+    }
+
+    #[test]
+    fn from_empty_generators_works() -> Result<(), Error> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        let mesh = Mesh2D::from(&triangle);
+        assert_eq!(mesh.nnode(), 0);
+        assert_eq!(mesh.triangles.len(), 0);
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        let mesh = Mesh3D::from(&tetgen);
+        assert_eq!(mesh.nnode(), 0);
+        assert_eq!(mesh.tets.len(), 0);
+        Ok(())
+    }
+}
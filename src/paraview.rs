@@ -1,143 +1,1049 @@
 use crate::constants;
-use crate::StrError;
-use crate::Tetgen;
+use crate::file_io::{close_file, create_file};
+use crate::Error;
+use crate::{Mesh2D, Mesh3D, Tetgen, Triangle};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
 use std::ffi::OsStr;
-use std::fmt::Write;
-use std::fs::{self, File};
-use std::io::Write as IoWrite;
-use std::path::Path;
+use std::io::{self, Write};
+
+/// Holds named data fields defined at the points or cells of a mesh to be written to a VTU file
+///
+/// # Example
+///
+/// ```
+/// use tritet::{write_tri_vtu_with_fields, Error, Triangle, VtuFields};
+///
+/// fn main() -> Result<(), Error> {
+///     let mut triangle = Triangle::new(3, None, None, None)?;
+///     triangle
+///         .set_point(0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0)?
+///         .set_point(2, 0.0, 1.0)?;
+///     triangle.generate_delaunay(false)?;
+///     let mut fields = VtuFields::new();
+///     fields
+///         .add_point_scalar("temperature", &[10.0, 20.0, 30.0])
+///         .add_point_vector("velocity", &[[1.0, 0.0], [1.0, 1.0], [0.0, 1.0]])
+///         .add_cell_scalar("error", &[0.01]);
+///     write_tri_vtu_with_fields(&triangle, &fields, "/tmp/tritet/doc_write_tri_vtu_with_fields.vtu")?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct VtuFields {
+    point_data: Vec<VtuField>, // fields defined at the points
+    cell_data: Vec<VtuField>,  // fields defined at the cells
+}
+
+/// Holds a named data field with one or three components
+#[derive(Clone, Debug)]
+struct VtuField {
+    name: String,      // name of the field
+    ncomponent: usize, // number of components (1 or 3)
+    values: Vec<f64>,  // (npoint or ncell) × ncomponent values
+}
+
+impl VtuFields {
+    /// Allocates a new (empty) instance
+    pub fn new() -> Self {
+        VtuFields {
+            point_data: Vec::new(),
+            cell_data: Vec::new(),
+        }
+    }
+
+    /// Adds a scalar field defined at the points
+    ///
+    /// # Input
+    ///
+    /// * `name` -- is the name of the field
+    /// * `values` -- are the values at each point (the length must be equal to `npoint`)
+    pub fn add_point_scalar(&mut self, name: &str, values: &[f64]) -> &mut Self {
+        self.point_data.push(VtuField::scalar(name, values));
+        self
+    }
+
+    /// Adds a vector field defined at the points
+    ///
+    /// # Input
+    ///
+    /// * `name` -- is the name of the field
+    /// * `values` -- are the vectors at each point (the length must be equal to `npoint`).
+    ///   Vectors with less than three components are padded with zeros (e.g., 2D vectors).
+    pub fn add_point_vector<V: AsRef<[f64]>>(&mut self, name: &str, values: &[V]) -> &mut Self {
+        self.point_data.push(VtuField::vector(name, values));
+        self
+    }
+
+    /// Adds a scalar field defined at the cells
+    ///
+    /// # Input
+    ///
+    /// * `name` -- is the name of the field
+    /// * `values` -- are the values at each cell (the length must be equal to the number of cells)
+    pub fn add_cell_scalar(&mut self, name: &str, values: &[f64]) -> &mut Self {
+        self.cell_data.push(VtuField::scalar(name, values));
+        self
+    }
+
+    /// Adds a vector field defined at the cells
+    ///
+    /// # Input
+    ///
+    /// * `name` -- is the name of the field
+    /// * `values` -- are the vectors at each cell (the length must be equal to the number of cells).
+    ///   Vectors with less than three components are padded with zeros (e.g., 2D vectors).
+    pub fn add_cell_vector<V: AsRef<[f64]>>(&mut self, name: &str, values: &[V]) -> &mut Self {
+        self.cell_data.push(VtuField::vector(name, values));
+        self
+    }
+}
+
+impl VtuField {
+    /// Allocates a new scalar field
+    fn scalar(name: &str, values: &[f64]) -> Self {
+        VtuField {
+            name: name.to_string(),
+            ncomponent: 1,
+            values: values.to_vec(),
+        }
+    }
+
+    /// Allocates a new vector field (with three components)
+    fn vector<V: AsRef<[f64]>>(name: &str, values: &[V]) -> Self {
+        let mut data = vec![0.0; 3 * values.len()];
+        for (i, vector) in values.iter().enumerate() {
+            for (j, v) in vector.as_ref().iter().take(3).enumerate() {
+                data[3 * i + j] = *v;
+            }
+        }
+        VtuField {
+            name: name.to_string(),
+            ncomponent: 3,
+            values: data,
+        }
+    }
+
+    /// Returns the number of entries (points or cells)
+    fn len(&self) -> usize {
+        self.values.len() / self.ncomponent
+    }
+}
+
+/// Defines how the data arrays are stored in a VTU file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VtuFormat {
+    /// Writes the values as text (human-readable)
+    Ascii,
+
+    /// Writes the values as base64-encoded binary data within each DataArray
+    Binary,
+
+    /// Writes the values as raw binary data in an AppendedData section at the end of the file
+    Appended,
+}
+
+/// Holds options to write VTU files
+///
+/// The default is the ASCII format without compression.
+///
+/// # Example
+///
+/// ```
+/// use tritet::{write_tet_vtu_with_options, Error, Tetgen, VtuFields, VtuFormat, VtuOptions};
+///
+/// fn main() -> Result<(), Error> {
+///     let mut tetgen = Tetgen::new(4, None, None, None)?;
+///     tetgen
+///         .set_point(0, 0.0, 0.0, 0.0)?
+///         .set_point(1, 1.0, 0.0, 0.0)?
+///         .set_point(2, 0.0, 1.0, 0.0)?
+///         .set_point(3, 0.0, 0.0, 1.0)?;
+///     tetgen.generate_delaunay(false)?;
+///     let mut options = VtuOptions::new();
+///     options.set_format(VtuFormat::Appended).set_compress(true);
+///     let path = "/tmp/tritet/doc_write_tet_vtu_with_options.vtu";
+///     write_tet_vtu_with_options(&tetgen, &VtuFields::new(), &options, path)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct VtuOptions {
+    format: VtuFormat, // how the data arrays are stored
+    compress: bool,    // compress the binary data with zlib
+}
+
+impl VtuOptions {
+    /// Allocates a new instance with the default options (ASCII; no compression)
+    pub fn new() -> Self {
+        VtuOptions {
+            format: VtuFormat::Ascii,
+            compress: false,
+        }
+    }
+
+    /// Sets how the data arrays are stored
+    pub fn set_format(&mut self, format: VtuFormat) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    /// Enables the zlib compression of the binary data
+    ///
+    /// **Note:** Compression is ignored by the ASCII format. The binary format compresses and writes one
+    /// array at a time, whereas the appended format compresses all arrays in memory before writing the
+    /// file because the header needs their (compressed) sizes.
+    pub fn set_compress(&mut self, compress: bool) -> &mut Self {
+        self.compress = compress;
+        self
+    }
+}
+
+impl Default for VtuOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Writes triangles as a Paraview's VTU file
+///
+/// The attribute of each triangle (see [Triangle::triangle_attribute]) is written as cell data.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tri_vtu<P>(triangle: &Triangle, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    write_tri_vtu_with_options(triangle, &VtuFields::new(), &VtuOptions::new(), full_path)
+}
+
+/// Writes triangles and data fields as a Paraview's VTU file
+///
+/// The attribute of each triangle (see [Triangle::triangle_attribute]) is written as cell data,
+/// followed by the fields defined at the points and cells.
+///
+/// # Input
+///
+/// * `fields` -- are the fields defined at the points and cells
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tri_vtu_with_fields<P>(triangle: &Triangle, fields: &VtuFields, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    write_tri_vtu_with_options(triangle, fields, &VtuOptions::new(), full_path)
+}
+
+/// Writes triangles and data fields as a Paraview's VTU file using the given format
+///
+/// # Input
+///
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tri_vtu_with_options<P>(
+    triangle: &Triangle,
+    fields: &VtuFields,
+    options: &VtuOptions,
+    full_path: &P,
+) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let cells = tri_cells(triangle)?;
+    check_fields(&cells, fields)?;
+    let mut writer = create_file(full_path)?;
+    write_vtu(&mut writer, &cells, fields, options)?;
+    close_file(writer, full_path)
+}
+
+/// Writes triangles and data fields in the VTU format to a writer (e.g., a file or a buffer)
+///
+/// The data is streamed to the writer; thus, a [std::io::BufWriter] is recommended for files.
+///
+/// # Input
+///
+/// * `triangle` -- is the mesh
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `writer` -- is the destination of the data
+pub fn write_tri_vtu_to<W: io::Write>(
+    triangle: &Triangle,
+    fields: &VtuFields,
+    options: &VtuOptions,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let cells = tri_cells(triangle)?;
+    check_fields(&cells, fields)?;
+    write_vtu(writer, &cells, fields, options)
+}
 
 /// Writes tetrahedra as a Paraview's VTU file
 ///
+/// The attribute of each tetrahedron (see [Tetgen::tet_attribute]) is written as cell data.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tet_vtu<P>(tetgen: &Tetgen, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    write_tet_vtu_with_options(tetgen, &VtuFields::new(), &VtuOptions::new(), full_path)
+}
+
+/// Writes tetrahedra and data fields as a Paraview's VTU file
+///
+/// The attribute of each tetrahedron (see [Tetgen::tet_attribute]) is written as cell data,
+/// followed by the fields defined at the points and cells.
+///
+/// # Input
+///
+/// * `fields` -- are the fields defined at the points and cells
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tet_vtu_with_fields<P>(tetgen: &Tetgen, fields: &VtuFields, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    write_tet_vtu_with_options(tetgen, fields, &VtuOptions::new(), full_path)
+}
+
+/// Writes tetrahedra and data fields as a Paraview's VTU file using the given format
+///
 /// # Input
 ///
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
 /// * `full_path` -- may be a String, &str, or Path
-pub fn write_tet_vtu<P>(tetgen: &Tetgen, full_path: &P) -> Result<(), StrError>
+pub fn write_tet_vtu_with_options<P>(
+    tetgen: &Tetgen,
+    fields: &VtuFields,
+    options: &VtuOptions,
+    full_path: &P,
+) -> Result<(), Error>
 where
     P: AsRef<OsStr> + ?Sized,
 {
+    let cells = tet_cells(tetgen)?;
+    check_fields(&cells, fields)?;
+    let mut writer = create_file(full_path)?;
+    write_vtu(&mut writer, &cells, fields, options)?;
+    close_file(writer, full_path)
+}
+
+/// Writes tetrahedra and data fields in the VTU format to a writer (e.g., a file or a buffer)
+///
+/// The data is streamed to the writer; thus, a [std::io::BufWriter] is recommended for files.
+///
+/// # Input
+///
+/// * `tetgen` -- is the mesh
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `writer` -- is the destination of the data
+pub fn write_tet_vtu_to<W: io::Write>(
+    tetgen: &Tetgen,
+    fields: &VtuFields,
+    options: &VtuOptions,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let cells = tet_cells(tetgen)?;
+    check_fields(&cells, fields)?;
+    write_vtu(writer, &cells, fields, options)
+}
+
+/// Writes a mesh of triangles and data fields as a Paraview's VTU file
+///
+/// The attribute of each triangle is written as cell data, followed by the fields
+/// defined at the points and cells.
+///
+/// # Input
+///
+/// * `mesh` -- is the mesh detached from Triangle
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_mesh2d_vtu<P>(mesh: &Mesh2D, fields: &VtuFields, options: &VtuOptions, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let cells = mesh2d_cells(mesh)?;
+    check_fields(&cells, fields)?;
+    let mut writer = create_file(full_path)?;
+    write_vtu(&mut writer, &cells, fields, options)?;
+    close_file(writer, full_path)
+}
+
+/// Writes a mesh of triangles and data fields in the VTU format to a writer (e.g., a file or a buffer)
+///
+/// # Input
+///
+/// * `mesh` -- is the mesh detached from Triangle
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `writer` -- is the destination of the data
+pub fn write_mesh2d_vtu_to<W: io::Write>(
+    mesh: &Mesh2D,
+    fields: &VtuFields,
+    options: &VtuOptions,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let cells = mesh2d_cells(mesh)?;
+    check_fields(&cells, fields)?;
+    write_vtu(writer, &cells, fields, options)
+}
+
+/// Writes a mesh of tetrahedra and data fields as a Paraview's VTU file
+///
+/// The attribute of each tetrahedron is written as cell data, followed by the fields
+/// defined at the points and cells.
+///
+/// # Input
+///
+/// * `mesh` -- is the mesh detached from Tetgen
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_mesh3d_vtu<P>(mesh: &Mesh3D, fields: &VtuFields, options: &VtuOptions, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let cells = mesh3d_cells(mesh)?;
+    check_fields(&cells, fields)?;
+    let mut writer = create_file(full_path)?;
+    write_vtu(&mut writer, &cells, fields, options)?;
+    close_file(writer, full_path)
+}
+
+/// Writes a mesh of tetrahedra and data fields in the VTU format to a writer (e.g., a file or a buffer)
+///
+/// # Input
+///
+/// * `mesh` -- is the mesh detached from Tetgen
+/// * `fields` -- are the fields defined at the points and cells
+/// * `options` -- defines the format of the data arrays and the compression
+/// * `writer` -- is the destination of the data
+pub fn write_mesh3d_vtu_to<W: io::Write>(
+    mesh: &Mesh3D,
+    fields: &VtuFields,
+    options: &VtuOptions,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let cells = mesh3d_cells(mesh)?;
+    check_fields(&cells, fields)?;
+    write_vtu(writer, &cells, fields, options)
+}
+
+/// Holds the accessors to the points and cells to be written to a VTU file
+struct VtuCells<'a> {
+    npoint: usize,                                 // number of points
+    ncell: usize,                                  // number of cells
+    nnode: usize,                                  // number of nodes of each cell
+    vtk_type: i32,                                 // VTK cell type
+    point: Box<dyn Fn(usize, usize) -> f64 + 'a>,  // (index, dim) → x-y-z coordinate of point
+    node: Box<dyn Fn(usize, usize) -> usize + 'a>, // (index, m) → ID of the m-th node of cell (VTK ordering)
+    attribute: Box<dyn Fn(usize) -> usize + 'a>,   // index → attribute of cell
+}
+
+/// Returns the accessors to the triangles
+fn tri_cells(triangle: &Triangle) -> Result<VtuCells<'_>, Error> {
+    let ntriangle = triangle.ntriangle();
+    if ntriangle < 1 {
+        return Err(Error::InvalidInput("there are no triangles to write"));
+    }
+    let nnode = triangle.nnode();
+    Ok(VtuCells {
+        npoint: triangle.npoint(),
+        ncell: ntriangle,
+        nnode,
+        vtk_type: if nnode == 3 {
+            constants::VTK_TRIANGLE
+        } else {
+            constants::VTK_QUADRATIC_TRIANGLE
+        },
+        point: Box::new(move |index, dim| if dim < 2 { triangle.point(index, dim) } else { 0.0 }),
+        node: Box::new(move |index, m| triangle.triangle_node(index, m)),
+        attribute: Box::new(move |index| triangle.triangle_attribute(index)),
+    })
+}
+
+/// Returns the accessors to the tetrahedra
+fn tet_cells(tetgen: &Tetgen) -> Result<VtuCells<'_>, Error> {
     let ntet = tetgen.ntet();
     if ntet < 1 {
-        return Err("there are no tetrahedra to write");
+        return Err(Error::InvalidInput("there are no tetrahedra to write"));
     }
-
-    let npoint = tetgen.npoint();
     let nnode = tetgen.nnode();
-    let vtk_type = if nnode == 4 {
-        constants::VTK_TETRA
+    Ok(VtuCells {
+        npoint: tetgen.npoint(),
+        ncell: ntet,
+        nnode,
+        vtk_type: if nnode == 4 {
+            constants::VTK_TETRA
+        } else {
+            constants::VTK_QUADRATIC_TETRA
+        },
+        point: Box::new(move |index, dim| tetgen.point(index, dim)),
+        node: Box::new(move |index, m| tetgen.tet_node(index, m)),
+        attribute: Box::new(move |index| tetgen.tet_attribute(index)),
+    })
+}
+
+/// Returns the accessors to the triangles of a detached mesh
+fn mesh2d_cells(mesh: &Mesh2D) -> Result<VtuCells<'_>, Error> {
+    if mesh.triangles.is_empty() {
+        return Err(Error::InvalidInput("there are no triangles to write"));
+    }
+    let nnode = mesh.nnode();
+    Ok(VtuCells {
+        npoint: mesh.points.len(),
+        ncell: mesh.triangles.len(),
+        nnode,
+        vtk_type: if nnode == 3 {
+            constants::VTK_TRIANGLE
+        } else {
+            constants::VTK_QUADRATIC_TRIANGLE
+        },
+        point: Box::new(move |index, dim| if dim < 2 { mesh.points[index][dim] } else { 0.0 }),
+        node: Box::new(move |index, m| mesh.triangles[index][m]),
+        attribute: Box::new(move |index| mesh.attributes[index]),
+    })
+}
+
+/// Returns the accessors to the tetrahedra of a detached mesh
+fn mesh3d_cells(mesh: &Mesh3D) -> Result<VtuCells<'_>, Error> {
+    if mesh.tets.is_empty() {
+        return Err(Error::InvalidInput("there are no tetrahedra to write"));
+    }
+    let nnode = mesh.nnode();
+    Ok(VtuCells {
+        npoint: mesh.points.len(),
+        ncell: mesh.tets.len(),
+        nnode,
+        vtk_type: if nnode == 4 {
+            constants::VTK_TETRA
+        } else {
+            constants::VTK_QUADRATIC_TETRA
+        },
+        point: Box::new(move |index, dim| mesh.points[index][dim]),
+        node: Box::new(move |index, m| mesh.tets[index][m]),
+        attribute: Box::new(move |index| mesh.attributes[index]),
+    })
+}
+
+/// Checks the number of values of the fields
+fn check_fields(cells: &VtuCells, fields: &VtuFields) -> Result<(), Error> {
+    if fields.point_data.iter().any(|field| field.len() != cells.npoint) {
+        return Err(Error::InvalidInput(
+            "the number of values of point data must be equal to the number of points",
+        ));
+    }
+    if fields.cell_data.iter().any(|field| field.len() != cells.ncell) {
+        return Err(Error::InvalidInput(
+            "the number of values of cell data must be equal to the number of cells",
+        ));
+    }
+    Ok(())
+}
+
+/// Holds a value of a DataArray
+#[derive(Clone, Copy)]
+enum Value {
+    Float64(f64),
+    Int32(i32),
+    UInt8(u8),
+}
+
+impl Value {
+    /// Writes the value as text
+    fn write_ascii(&self, writer: &mut dyn io::Write) -> io::Result<()> {
+        match self {
+            Value::Float64(v) => write!(writer, "{} ", v),
+            Value::Int32(v) => write!(writer, "{} ", v),
+            Value::UInt8(v) => write!(writer, "{} ", v),
+        }
+    }
+
+    /// Writes the value as little-endian bytes
+    fn write_bytes(&self, writer: &mut dyn io::Write) -> io::Result<()> {
+        match self {
+            Value::Float64(v) => writer.write_all(&v.to_le_bytes()),
+            Value::Int32(v) => writer.write_all(&v.to_le_bytes()),
+            Value::UInt8(v) => writer.write_all(&v.to_le_bytes()),
+        }
+    }
+}
+
+/// Holds the definition of a DataArray whose values are computed on demand
+struct DataArray<'a> {
+    vtk_type: &'static str,                  // Float64, Int32, or UInt8
+    nbyte: usize,                            // number of bytes of each value
+    name: Option<&'a str>,                   // name of the array
+    ncomponent: Option<usize>,               // number of components
+    size: usize,                             // total number of values
+    value: Box<dyn Fn(usize) -> Value + 'a>, // k → k-th value
+}
+
+impl<'a> DataArray<'a> {
+    /// Allocates a new array of Float64 values
+    fn float64(name: Option<&'a str>, ncomponent: usize, size: usize, value: Box<dyn Fn(usize) -> Value + 'a>) -> Self {
+        DataArray {
+            vtk_type: "Float64",
+            nbyte: 8,
+            name,
+            ncomponent: Some(ncomponent),
+            size,
+            value,
+        }
+    }
+
+    /// Allocates a new array of Int32 values
+    fn int32(name: &'a str, size: usize, value: Box<dyn Fn(usize) -> Value + 'a>) -> Self {
+        DataArray {
+            vtk_type: "Int32",
+            nbyte: 4,
+            name: Some(name),
+            ncomponent: None,
+            size,
+            value,
+        }
+    }
+
+    /// Allocates a new array of UInt8 values
+    fn uint8(name: &'a str, size: usize, value: Box<dyn Fn(usize) -> Value + 'a>) -> Self {
+        DataArray {
+            vtk_type: "UInt8",
+            nbyte: 1,
+            name: Some(name),
+            ncomponent: None,
+            size,
+            value,
+        }
+    }
+
+    /// Writes the opening tag of the DataArray (without the closing '>')
+    fn write_tag(&self, writer: &mut dyn io::Write, format: &str) -> io::Result<()> {
+        write!(writer, "<DataArray type=\"{}\"", self.vtk_type)?;
+        if let Some(name) = self.name {
+            write!(writer, " Name=\"{}\"", name)?;
+        }
+        if let Some(ncomponent) = self.ncomponent {
+            write!(writer, " NumberOfComponents=\"{}\"", ncomponent)?;
+        }
+        write!(writer, " format=\"{}\"", format)
+    }
+
+    /// Writes all values as little-endian bytes
+    fn write_bytes(&self, writer: &mut dyn io::Write) -> io::Result<()> {
+        for k in 0..self.size {
+            (self.value)(k).write_bytes(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the header (number of bytes) and the values as raw binary data
+    fn write_raw(&self, writer: &mut dyn io::Write) -> io::Result<()> {
+        writer.write_all(&((self.size * self.nbyte) as u64).to_le_bytes())?;
+        self.write_bytes(writer)
+    }
+
+    /// Returns the number of bytes written by write_raw
+    fn raw_len(&self) -> usize {
+        8 + self.size * self.nbyte
+    }
+
+    /// Compresses the values with zlib (in blocks) and returns the compression header and the compressed data
+    fn compress(&self) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        let mut compressor = BlockCompressor::new();
+        self.write_bytes(&mut compressor)?;
+        compressor.finish()
+    }
+}
+
+/// Generates the DataArrays of a VTU file in the order they appear in the file
+///
+/// Returns the arrays and the number of arrays in the Points, Cells, PointData, and CellData sections.
+fn data_arrays<'a>(cells: &'a VtuCells, fields: &'a VtuFields) -> (Vec<DataArray<'a>>, [usize; 4]) {
+    let mut arrays = vec![
+        DataArray::float64(
+            None,
+            3,
+            3 * cells.npoint,
+            Box::new(|k| Value::Float64((cells.point)(k / 3, k % 3))),
+        ),
+        DataArray::int32(
+            "connectivity",
+            cells.ncell * cells.nnode,
+            Box::new(|k| Value::Int32((cells.node)(k / cells.nnode, k % cells.nnode) as i32)),
+        ),
+        DataArray::int32(
+            "offsets",
+            cells.ncell,
+            Box::new(|k| Value::Int32(((k + 1) * cells.nnode) as i32)),
+        ),
+        DataArray::uint8("types", cells.ncell, Box::new(|_| Value::UInt8(cells.vtk_type as u8))),
+    ];
+    for field in &fields.point_data {
+        arrays.push(field_array(field));
+    }
+    arrays.push(DataArray::int32(
+        "attribute",
+        cells.ncell,
+        Box::new(|k| Value::Int32((cells.attribute)(k) as i32)),
+    ));
+    for field in &fields.cell_data {
+        arrays.push(field_array(field));
+    }
+    (arrays, [1, 3, fields.point_data.len(), 1 + fields.cell_data.len()])
+}
+
+/// Returns the DataArray of a data field
+fn field_array(field: &VtuField) -> DataArray<'_> {
+    DataArray::float64(
+        Some(&field.name),
+        field.ncomponent,
+        field.values.len(),
+        Box::new(|k| Value::Float64(field.values[k])),
+    )
+}
+
+/// Streams the contents of a VTU file to a writer
+fn write_vtu(
+    writer: &mut dyn io::Write,
+    cells: &VtuCells,
+    fields: &VtuFields,
+    options: &VtuOptions,
+) -> Result<(), Error> {
+    write_vtu_sections(writer, cells, fields, options).map_err(|e| Error::Io {
+        message: "cannot write VTU data",
+        path: None,
+        source: e.into(),
+    })
+}
+
+/// Streams the contents of a VTU file to a writer (returning IO errors)
+fn write_vtu_sections(
+    writer: &mut dyn io::Write,
+    cells: &VtuCells,
+    fields: &VtuFields,
+    options: &VtuOptions,
+) -> io::Result<()> {
+    let (arrays, counts) = data_arrays(cells, fields);
+    let binary = options.format != VtuFormat::Ascii;
+    let compress = binary && options.compress;
+
+    // the appended format needs the offsets in the header; thus, all arrays are compressed beforehand
+    // (the binary format compresses each array when writing it)
+    let compressed = if compress && options.format == VtuFormat::Appended {
+        arrays
+            .iter()
+            .map(|array| array.compress())
+            .collect::<io::Result<Vec<_>>>()?
     } else {
-        constants::VTK_QUADRATIC_TETRA
+        Vec::new()
     };
 
-    let mut buffer = String::new();
-
     // header
+    writeln!(writer, "<?xml version=\"1.0\"?>")?;
+    if binary {
+        write!(
+            writer,
+            "<VTKFile type=\"UnstructuredGrid\" version=\"1.0\" byte_order=\"LittleEndian\" header_type=\"UInt64\""
+        )?;
+        if compress {
+            write!(writer, " compressor=\"vtkZLibDataCompressor\"")?;
+        }
+        writeln!(writer, ">")?;
+    } else {
+        writeln!(
+            writer,
+            "<VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">"
+        )?;
+    }
     write!(
-        &mut buffer,
-        "<?xml version=\"1.0\"?>\n\
-         <VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">\n\
-         <UnstructuredGrid>\n\
+        writer,
+        "<UnstructuredGrid>\n\
          <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">\n",
-        npoint, ntet
-    )
-    .unwrap();
+        cells.npoint, cells.ncell
+    )?;
 
-    // nodes: coordinates
-    write!(
-        &mut buffer,
-        "<Points>\n\
-         <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">\n"
-    )
-    .unwrap();
-    for index in 0..npoint {
-        for dim in 0..3 {
-            write!(&mut buffer, "{} ", tetgen.point(index, dim)).unwrap();
+    // sections
+    let mut index = 0;
+    let mut offset = 0;
+    for (section, count) in counts.iter().enumerate() {
+        match section {
+            0 => writeln!(writer, "<Points>")?,
+            1 => writeln!(writer, "<Cells>")?,
+            2 if *count > 0 => writeln!(writer, "<PointData>")?,
+            3 => writeln!(writer, "<CellData Scalars=\"attribute\">")?,
+            _ => (),
+        }
+        for _ in 0..*count {
+            let array = &arrays[index];
+            match options.format {
+                VtuFormat::Ascii => {
+                    array.write_tag(writer, "ascii")?;
+                    writeln!(writer, ">")?;
+                    for k in 0..array.size {
+                        (array.value)(k).write_ascii(writer)?;
+                    }
+                    write!(writer, "\n</DataArray>\n")?;
+                }
+                VtuFormat::Binary => {
+                    array.write_tag(writer, "binary")?;
+                    writeln!(writer, ">")?;
+                    // the header and the data are encoded separately, as VTK does
+                    if compress {
+                        let (header, data) = array.compress()?;
+                        write_base64(writer, &header)?;
+                        write_base64(writer, &data)?;
+                    } else {
+                        write_base64(writer, &((array.size * array.nbyte) as u64).to_le_bytes())?;
+                        let mut encoder = Base64Writer::new(writer);
+                        array.write_bytes(&mut encoder)?;
+                        encoder.finish()?;
+                    }
+                    write!(writer, "\n</DataArray>\n")?;
+                }
+                VtuFormat::Appended => {
+                    array.write_tag(writer, "appended")?;
+                    writeln!(writer, " offset=\"{}\"/>", offset)?;
+                    offset += if compress {
+                        compressed[index].0.len() + compressed[index].1.len()
+                    } else {
+                        array.raw_len()
+                    };
+                }
+            }
+            index += 1;
+        }
+        match section {
+            0 => writeln!(writer, "</Points>")?,
+            1 => writeln!(writer, "</Cells>")?,
+            2 if *count > 0 => writeln!(writer, "</PointData>")?,
+            3 => writeln!(writer, "</CellData>")?,
+            _ => (),
         }
     }
     write!(
-        &mut buffer,
-        "\n</DataArray>\n\
-         </Points>\n"
-    )
-    .unwrap();
+        writer,
+        "</Piece>\n\
+         </UnstructuredGrid>\n"
+    )?;
 
-    // elements: connectivity
-    write!(
-        &mut buffer,
-        "<Cells>\n\
-         <DataArray type=\"Int32\" Name=\"connectivity\" format=\"ascii\">\n"
-    )
-    .unwrap();
-    for index in 0..ntet {
-        for m in 0..nnode {
-            write!(&mut buffer, "{} ", tetgen.tet_node(index, m)).unwrap();
+    // appended data
+    if options.format == VtuFormat::Appended {
+        write!(writer, "<AppendedData encoding=\"raw\">\n_")?;
+        for (index, array) in arrays.iter().enumerate() {
+            if compress {
+                let (header, data) = &compressed[index];
+                writer.write_all(header)?;
+                writer.write_all(data)?;
+            } else {
+                array.write_raw(writer)?;
+            }
         }
+        write!(writer, "\n</AppendedData>\n")?;
     }
+    writeln!(writer, "</VTKFile>")?;
+    writer.flush()
+}
 
-    // elements: offsets
-    write!(
-        &mut buffer,
-        "\n</DataArray>\n\
-         <DataArray type=\"Int32\" Name=\"offsets\" format=\"ascii\">\n"
-    )
-    .unwrap();
-    let mut offset = 0;
-    for _ in 0..ntet {
-        offset += nnode;
-        write!(&mut buffer, "{} ", offset).unwrap();
+/// Size of the uncompressed blocks (the same as VTK's)
+const COMPRESSION_BLOCK_SIZE: usize = 32768;
+
+/// Compresses a stream of bytes with zlib in blocks, as required by vtkZLibDataCompressor
+struct BlockCompressor {
+    block: Vec<u8>,       // current (uncompressed) block
+    sizes: Vec<u64>,      // sizes of the compressed blocks
+    data: Vec<u8>,        // compressed blocks
+    last_block_size: u64, // size of the last (partial) block; zero if all blocks are full
+}
+
+impl BlockCompressor {
+    /// Allocates a new instance
+    fn new() -> Self {
+        BlockCompressor {
+            block: Vec::with_capacity(COMPRESSION_BLOCK_SIZE),
+            sizes: Vec::new(),
+            data: Vec::new(),
+            last_block_size: 0,
+        }
     }
 
-    // elements: types
-    write!(
-        &mut buffer,
-        "\n</DataArray>\n\
-         <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">\n"
-    )
-    .unwrap();
-    for _ in 0..ntet {
-        write!(&mut buffer, "{} ", vtk_type).unwrap();
+    /// Compresses the current block
+    fn compress_block(&mut self) -> io::Result<()> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&self.block)?;
+        let compressed = encoder.finish()?;
+        self.sizes.push(compressed.len() as u64);
+        self.data.extend_from_slice(&compressed);
+        self.block.clear();
+        Ok(())
     }
-    write!(
-        &mut buffer,
-        "\n</DataArray>\n\
-         </Cells>\n"
-    )
-    .unwrap();
 
-    write!(
-        &mut buffer,
-        "</Piece>\n\
-         </UnstructuredGrid>\n\
-         </VTKFile>\n"
-    )
-    .unwrap();
+    /// Compresses the remaining bytes and returns the header and the compressed data
+    ///
+    /// The header is `[nblock, block_size, last_block_size, compressed_size_0, compressed_size_1, ...]`.
+    fn finish(mut self) -> io::Result<(Vec<u8>, Vec<u8>)> {
+        if !self.block.is_empty() {
+            self.last_block_size = self.block.len() as u64;
+            self.compress_block()?;
+        }
+        let mut header = Vec::with_capacity(8 * (3 + self.sizes.len()));
+        header.extend_from_slice(&(self.sizes.len() as u64).to_le_bytes());
+        header.extend_from_slice(&(COMPRESSION_BLOCK_SIZE as u64).to_le_bytes());
+        header.extend_from_slice(&self.last_block_size.to_le_bytes());
+        for size in &self.sizes {
+            header.extend_from_slice(&size.to_le_bytes());
+        }
+        Ok((header, self.data))
+    }
+}
+
+impl io::Write for BlockCompressor {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = usize::min(buf.len(), COMPRESSION_BLOCK_SIZE - self.block.len());
+        self.block.extend_from_slice(&buf[..n]);
+        if self.block.len() == COMPRESSION_BLOCK_SIZE {
+            self.compress_block()?;
+        }
+        Ok(n)
+    }
 
-    // create directory
-    let path = Path::new(full_path);
-    if let Some(p) = path.parent() {
-        fs::create_dir_all(p).map_err(|_| "cannot create directory")?;
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
     }
+}
 
-    // write file
-    let mut file = File::create(path).map_err(|_| "cannot create file")?;
-    file.write_all(buffer.as_bytes()).map_err(|_| "cannot write file")?;
+/// Base64 alphabet (RFC 4648)
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 
-    // force sync
-    file.sync_all().map_err(|_| "cannot sync file")?;
-    Ok(())
+/// Encodes up to three bytes into four base64 characters (with padding)
+fn encode_base64_chunk(chunk: &[u8]) -> [u8; 4] {
+    let b0 = chunk[0] as usize;
+    let b1 = if chunk.len() > 1 { chunk[1] as usize } else { 0 };
+    let b2 = if chunk.len() > 2 { chunk[2] as usize } else { 0 };
+    let mut out = [b'='; 4];
+    out[0] = BASE64_ALPHABET[b0 >> 2];
+    out[1] = BASE64_ALPHABET[((b0 & 0x03) << 4) | (b1 >> 4)];
+    if chunk.len() > 1 {
+        out[2] = BASE64_ALPHABET[((b1 & 0x0f) << 2) | (b2 >> 6)];
+    }
+    if chunk.len() > 2 {
+        out[3] = BASE64_ALPHABET[b2 & 0x3f];
+    }
+    out
+}
+
+/// Writes bytes encoded in base64
+fn write_base64(writer: &mut dyn io::Write, bytes: &[u8]) -> io::Result<()> {
+    let mut encoder = Base64Writer::new(writer);
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+/// Encodes a stream of bytes in base64
+struct Base64Writer<'a> {
+    writer: &'a mut dyn io::Write, // destination of the encoded characters
+    pending: [u8; 3],              // bytes not encoded yet
+    npending: usize,               // number of bytes not encoded yet
+    buffer: Vec<u8>,               // encoded characters not written yet
+}
+
+impl<'a> Base64Writer<'a> {
+    /// Allocates a new instance
+    fn new(writer: &'a mut dyn io::Write) -> Self {
+        Base64Writer {
+            writer,
+            pending: [0; 3],
+            npending: 0,
+            buffer: Vec::with_capacity(4096),
+        }
+    }
+
+    /// Encodes the pending bytes (with padding) and writes all characters
+    fn finish(mut self) -> io::Result<()> {
+        if self.npending > 0 {
+            let out = encode_base64_chunk(&self.pending[..self.npending]);
+            self.buffer.extend_from_slice(&out);
+        }
+        self.writer.write_all(&self.buffer)
+    }
+}
+
+impl<'a> io::Write for Base64Writer<'a> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for byte in buf {
+            self.pending[self.npending] = *byte;
+            self.npending += 1;
+            if self.npending == 3 {
+                let out = encode_base64_chunk(&self.pending);
+                self.buffer.extend_from_slice(&out);
+                self.npending = 0;
+            }
+        }
+        if self.buffer.len() >= 4096 {
+            self.writer.write_all(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
-    use super::write_tet_vtu;
-    use crate::StrError;
-    use crate::Tetgen;
-    use std::fs;
+    use super::{
+        write_base64, write_mesh2d_vtu, write_mesh2d_vtu_to, write_mesh3d_vtu_to, write_tet_vtu, write_tet_vtu_to,
+        write_tet_vtu_with_fields, write_tet_vtu_with_options, write_tri_vtu, write_tri_vtu_to,
+        write_tri_vtu_with_fields, BlockCompressor, VtuFields, VtuFormat, VtuOptions,
+    };
+    use crate::file_io::{read_file, read_file_bytes};
+    use crate::Error;
+    use crate::{Mesh2D, Mesh3D, Tetgen, Triangle};
+    use flate2::read::ZlibDecoder;
+    use std::io::{Read, Write};
+
+    fn unit_tet() -> Result<Tetgen, Error> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        Ok(tetgen)
+    }
+
+    // Returns the raw bytes of the AppendedData section and the offsets of the arrays
+    fn appended_data(contents: &[u8]) -> (Vec<u8>, Vec<usize>) {
+        let marker = "<AppendedData encoding=\"raw\">\n_";
+        let text = String::from_utf8_lossy(contents);
+        let start = text.find(marker).unwrap();
+        let offsets = text[..start]
+            .match_indices("offset=\"")
+            .map(|(i, _)| {
+                let rest = &text[(i + 8)..];
+                rest[..rest.find('"').unwrap()].parse().unwrap()
+            })
+            .collect();
+        (contents[(start + marker.len())..].to_vec(), offsets)
+    }
+
+    fn read_u64(bytes: &[u8], position: usize) -> usize {
+        u64::from_le_bytes(bytes[position..(position + 8)].try_into().unwrap()) as usize
+    }
 
     #[test]
-    fn write_tet_vtu_works() -> Result<(), StrError> {
+    fn write_tet_vtu_works() -> Result<(), Error> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         tetgen
             .set_point(0, 0.0, 0.0, 0.0)?
@@ -147,7 +1053,7 @@ mod tests {
         tetgen.generate_delaunay(false)?;
         let file_path = "/tmp/tritet/test_write_tet_vtu.vtu";
         write_tet_vtu(&tetgen, file_path)?;
-        let contents = fs::read_to_string(file_path).map_err(|_| "cannot open file")?;
+        let contents = read_file(file_path)?;
         assert_eq!(
             contents,
             r#"<?xml version="1.0"?>
@@ -170,6 +1076,169 @@ mod tests {
 10 
 </DataArray>
 </Cells>
+<CellData Scalars="attribute">
+<DataArray type="Int32" Name="attribute" format="ascii">
+0 
+</DataArray>
+</CellData>
+</Piece>
+</UnstructuredGrid>
+</VTKFile>
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_vtu_captures_some_errors() -> Result<(), Error> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            write_tri_vtu(&triangle, "/tmp/tritet/test_write_tri_vtu_error.vtu").err(),
+            Some(Error::InvalidInput("there are no triangles to write"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_vtu_works() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        let file_path = "/tmp/tritet/test_write_tri_vtu.vtu";
+        write_tri_vtu(&triangle, file_path)?;
+        let contents = read_file(file_path)?;
+        assert_eq!(
+            contents,
+            r#"<?xml version="1.0"?>
+<VTKFile type="UnstructuredGrid" version="0.1" byte_order="LittleEndian">
+<UnstructuredGrid>
+<Piece NumberOfPoints="3" NumberOfCells="1">
+<Points>
+<DataArray type="Float64" NumberOfComponents="3" format="ascii">
+0 0 0 1 0 0 0 1 0 
+</DataArray>
+</Points>
+<Cells>
+<DataArray type="Int32" Name="connectivity" format="ascii">
+0 1 2 
+</DataArray>
+<DataArray type="Int32" Name="offsets" format="ascii">
+3 
+</DataArray>
+<DataArray type="UInt8" Name="types" format="ascii">
+5 
+</DataArray>
+</Cells>
+<CellData Scalars="attribute">
+<DataArray type="Int32" Name="attribute" format="ascii">
+0 
+</DataArray>
+</CellData>
+</Piece>
+</UnstructuredGrid>
+</VTKFile>
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_vtu_works_o2() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, Some(3), Some(1), None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.set_region(0, 0.1, 0.1, 7, None)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        let file_path = "/tmp/tritet/test_write_tri_vtu_o2.vtu";
+        write_tri_vtu(&triangle, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines[3], r#"<Piece NumberOfPoints="6" NumberOfCells="1">"#);
+        assert_eq!(lines[6], "0 0 0 1 0 0 0 1 0 0.5 0 0 0.5 0.5 0 0 0.5 0 ");
+        // the middle nodes 3, 4, 5 are at (0.5,0), (0.5,0.5), (0,0.5) as required by VTK
+        assert_eq!(lines[11], "0 1 2 3 4 5 ");
+        assert_eq!(lines[14], "6 ");
+        assert_eq!(lines[17], "22 ");
+        assert_eq!(lines[22], "7 ");
+        Ok(())
+    }
+
+    #[test]
+    fn write_vtu_with_fields_captures_some_errors() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        let file_path = "/tmp/tritet/test_write_vtu_with_fields_error.vtu";
+        let mut fields = VtuFields::new();
+        fields.add_point_scalar("temperature", &[1.0, 2.0]);
+        assert_eq!(
+            write_tri_vtu_with_fields(&triangle, &fields, file_path).err(),
+            Some(Error::InvalidInput(
+                "the number of values of point data must be equal to the number of points"
+            ))
+        );
+        let mut fields = VtuFields::new();
+        fields.add_cell_vector("flux", &[[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(
+            write_tri_vtu_with_fields(&triangle, &fields, file_path).err(),
+            Some(Error::InvalidInput(
+                "the number of values of cell data must be equal to the number of cells"
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tri_vtu_with_fields_works() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        let mut fields = VtuFields::new();
+        fields
+            .add_point_scalar("temperature", &[10.0, 20.0, 30.0])
+            .add_point_vector("velocity", &[[1.0, 0.0], [1.0, 1.0], [0.0, 1.0]])
+            .add_cell_scalar("error", &[0.5])
+            .add_cell_vector("flux", &[vec![1.0, 2.0, 3.0]]);
+        let file_path = "/tmp/tritet/test_write_tri_vtu_with_fields.vtu";
+        write_tri_vtu_with_fields(&triangle, &fields, file_path)?;
+        let contents = read_file(file_path)?;
+        let i = contents.find("</Cells>").unwrap() + 9;
+        assert_eq!(
+            &contents[i..],
+            r#"<PointData>
+<DataArray type="Float64" Name="temperature" NumberOfComponents="1" format="ascii">
+10 20 30 
+</DataArray>
+<DataArray type="Float64" Name="velocity" NumberOfComponents="3" format="ascii">
+1 0 0 1 1 0 0 1 0 
+</DataArray>
+</PointData>
+<CellData Scalars="attribute">
+<DataArray type="Int32" Name="attribute" format="ascii">
+0 
+</DataArray>
+<DataArray type="Float64" Name="error" NumberOfComponents="1" format="ascii">
+0.5 
+</DataArray>
+<DataArray type="Float64" Name="flux" NumberOfComponents="3" format="ascii">
+1 2 3 
+</DataArray>
+</CellData>
 </Piece>
 </UnstructuredGrid>
 </VTKFile>
@@ -177,4 +1246,221 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn write_tet_vtu_with_fields_works() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        let mut fields = VtuFields::new();
+        fields.add_point_vector(
+            "displacement",
+            &[[0.0, 0.0, 0.0], [0.1, 0.0, 0.0], [0.0, 0.2, 0.0], [0.0, 0.0, 0.3]],
+        );
+        let file_path = "/tmp/tritet/test_write_tet_vtu_with_fields.vtu";
+        write_tet_vtu_with_fields(&tetgen, &fields, file_path)?;
+        let contents = read_file(file_path)?;
+        assert!(contents.contains(
+            "<PointData>\n\
+             <DataArray type=\"Float64\" Name=\"displacement\" NumberOfComponents=\"3\" format=\"ascii\">\n\
+             0 0 0 0.1 0 0 0 0.2 0 0 0 0.3 \n\
+             </DataArray>\n\
+             </PointData>\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn write_base64_works() {
+        // RFC 4648
+        for (input, correct) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            let mut buffer = Vec::new();
+            write_base64(&mut buffer, input.as_bytes()).unwrap();
+            assert_eq!(String::from_utf8(buffer).unwrap(), correct);
+        }
+    }
+
+    #[test]
+    fn block_compressor_works() {
+        let bytes: Vec<u8> = (0..40000).map(|i| (i % 251) as u8).collect();
+        let mut compressor = BlockCompressor::new();
+        compressor.write_all(&bytes).unwrap();
+        let (header, data) = compressor.finish().unwrap();
+        assert_eq!(header.len(), 8 * 5);
+        assert_eq!(read_u64(&header, 0), 2);
+        assert_eq!(read_u64(&header, 8), 32768);
+        assert_eq!(read_u64(&header, 16), 40000 - 32768);
+        let size_0 = read_u64(&header, 24);
+        let size_1 = read_u64(&header, 32);
+        assert_eq!(size_0 + size_1, data.len());
+        let mut decompressed = Vec::new();
+        ZlibDecoder::new(&data[..size_0])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        ZlibDecoder::new(&data[size_0..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, bytes);
+    }
+
+    #[test]
+    fn write_tri_vtu_to_works() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle.generate_delaunay(false)?;
+        let file_path = "/tmp/tritet/test_write_tri_vtu_to.vtu";
+        write_tri_vtu(&triangle, file_path)?;
+        let contents = read_file_bytes(file_path)?;
+        let mut buffer = Vec::new();
+        write_tri_vtu_to(&triangle, &VtuFields::new(), &VtuOptions::new(), &mut buffer)?;
+        assert_eq!(buffer, contents);
+        Ok(())
+    }
+
+    #[test]
+    fn write_mesh_vtu_works() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 0)?;
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        let mut fields = VtuFields::new();
+        fields.add_cell_scalar("error", &vec![0.5; triangle.ntriangle()]);
+        let mut options = VtuOptions::new();
+        options.set_format(VtuFormat::Binary).set_compress(true);
+        let mut expected = Vec::new();
+        write_tri_vtu_to(&triangle, &fields, &options, &mut expected)?;
+        let mesh = Mesh2D::from(triangle);
+        let mut buffer = Vec::new();
+        write_mesh2d_vtu_to(&mesh, &fields, &options, &mut buffer)?;
+        assert_eq!(buffer, expected);
+        let file_path = "/tmp/tritet/test_write_mesh2d_vtu.vtu";
+        write_mesh2d_vtu(&mesh, &fields, &options, file_path)?;
+        let contents = read_file_bytes(file_path)?;
+        assert_eq!(contents, expected);
+
+        let tetgen = unit_tet()?;
+        let mut expected = Vec::new();
+        write_tet_vtu_to(&tetgen, &VtuFields::new(), &VtuOptions::new(), &mut expected)?;
+        let mesh = Mesh3D::from(tetgen);
+        let mut buffer = Vec::new();
+        write_mesh3d_vtu_to(&mesh, &VtuFields::new(), &VtuOptions::new(), &mut buffer)?;
+        assert_eq!(buffer, expected);
+        assert_eq!(
+            write_mesh3d_vtu_to(
+                &Mesh3D::from(&Tetgen::new(4, None, None, None)?),
+                &fields,
+                &options,
+                &mut buffer
+            )
+            .err(),
+            Some(Error::InvalidInput("there are no tetrahedra to write"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_tet_vtu_binary_works() -> Result<(), Error> {
+        let tetgen = unit_tet()?;
+        let mut options = VtuOptions::new();
+        options.set_format(VtuFormat::Binary);
+        let file_path = "/tmp/tritet/test_write_tet_vtu_binary.vtu";
+        write_tet_vtu_with_options(&tetgen, &VtuFields::new(), &options, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(
+            lines[1],
+            r#"<VTKFile type="UnstructuredGrid" version="1.0" byte_order="LittleEndian" header_type="UInt64">"#
+        );
+        assert_eq!(
+            lines[5],
+            r#"<DataArray type="Float64" NumberOfComponents="3" format="binary">"#
+        );
+        assert_eq!(
+            lines[10],
+            r#"<DataArray type="Int32" Name="connectivity" format="binary">"#
+        );
+        // header: 16 bytes; data: 1 0 3 2
+        assert_eq!(lines[11], "EAAAAAAAAAA=AQAAAAAAAAADAAAAAgAAAA==");
+        // header: 1 byte; data: 10
+        assert_eq!(lines[17], "AQAAAAAAAAA=Cg==");
+        assert_eq!(lines[lines.len() - 1], "</VTKFile>");
+        Ok(())
+    }
+
+    #[test]
+    fn write_tet_vtu_appended_works() -> Result<(), Error> {
+        let tetgen = unit_tet()?;
+        let mut fields = VtuFields::new();
+        fields.add_cell_scalar("error", &[0.5]);
+        let mut options = VtuOptions::new();
+        options.set_format(VtuFormat::Appended);
+        let file_path = "/tmp/tritet/test_write_tet_vtu_appended.vtu";
+        write_tet_vtu_with_options(&tetgen, &fields, &options, file_path)?;
+        let contents = read_file_bytes(file_path)?;
+        let (data, offsets) = appended_data(&contents);
+        // points, connectivity, offsets, types, attribute, error
+        assert_eq!(offsets, &[0, 104, 128, 140, 149, 161]);
+        assert_eq!(read_u64(&data, 0), 96);
+        let x: Vec<f64> = (0..12)
+            .map(|k| f64::from_le_bytes(data[(8 + 8 * k)..(16 + 8 * k)].try_into().unwrap()))
+            .collect();
+        assert_eq!(x, &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(read_u64(&data, 140), 1);
+        assert_eq!(data[148], 10);
+        assert_eq!(read_u64(&data, 161), 8);
+        assert_eq!(f64::from_le_bytes(data[169..177].try_into().unwrap()), 0.5);
+        assert_eq!(&data[177..], b"\n</AppendedData>\n</VTKFile>\n");
+        Ok(())
+    }
+
+    #[test]
+    fn write_tet_vtu_compressed_works() -> Result<(), Error> {
+        let tetgen = unit_tet()?;
+        let mut options = VtuOptions::new();
+        options.set_format(VtuFormat::Appended).set_compress(true);
+        let file_path = "/tmp/tritet/test_write_tet_vtu_compressed.vtu";
+        write_tet_vtu_with_options(&tetgen, &VtuFields::new(), &options, file_path)?;
+        let contents = read_file_bytes(file_path)?;
+        let text = String::from_utf8_lossy(&contents);
+        assert!(text.contains(r#"header_type="UInt64" compressor="vtkZLibDataCompressor">"#));
+        let (data, offsets) = appended_data(&contents);
+        assert_eq!(offsets.len(), 5);
+        // connectivity
+        let start = offsets[1];
+        assert_eq!(read_u64(&data, start), 1); // number of blocks
+        assert_eq!(read_u64(&data, start + 16), 16); // size of the last block
+        let size = read_u64(&data, start + 24);
+        assert_eq!(start + 32 + size, offsets[2]);
+        let mut decompressed = Vec::new();
+        ZlibDecoder::new(&data[(start + 32)..offsets[2]])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        let connectivity: Vec<i32> = decompressed
+            .chunks(4)
+            .map(|c| i32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        assert_eq!(connectivity, &[1, 0, 3, 2]);
+        Ok(())
+    }
 }
@@ -0,0 +1,919 @@
+use crate::file_io::{read_file_bytes, DataLines};
+use crate::{Error, Tetgen};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+
+/// Reads an STL file (ASCII or binary) and allocates a Tetgen with one facet per triangle
+///
+/// The binary format is detected by the size of the file (84 bytes plus 50 bytes per triangle);
+/// otherwise, the file is read as ASCII. The vertices closer than `tolerance` are merged (see below).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+/// * `tolerance` -- is the maximum distance between vertices to be merged (zero means exactly equal vertices)
+///
+/// # Warning
+///
+/// The surface must be closed. Triangles that collapse after merging the vertices are discarded,
+/// and vertices not used by any triangle are ignored. The normals are ignored.
+pub fn read_stl_tetgen<P>(full_path: &P, tolerance: f64) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let bytes = read_file_bytes(full_path)?;
+    let surface = if is_binary_stl(&bytes) {
+        read_binary_stl(&bytes)?
+    } else {
+        let contents = std::str::from_utf8(&bytes).map_err(|_| Error::InvalidInput("the STL file is invalid"))?;
+        read_ascii_stl(contents)?
+    };
+    surface.to_tetgen(tolerance)
+}
+
+/// Reads an OFF file and allocates a Tetgen with one facet per triangle
+///
+/// Polygons with more than three vertices are split into triangles (as a fan around the first vertex).
+/// The vertices closer than `tolerance` are merged (see below).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+/// * `tolerance` -- is the maximum distance between vertices to be merged (zero means exactly equal vertices)
+///
+/// # Warning
+///
+/// The surface must be closed. Triangles that collapse after merging the vertices are discarded,
+/// and vertices not used by any triangle are ignored. Colors and normals are ignored.
+pub fn read_off_tetgen<P>(full_path: &P, tolerance: f64) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let bytes = read_file_bytes(full_path)?;
+    let contents = std::str::from_utf8(&bytes).map_err(|_| Error::InvalidInput("the OFF file is invalid"))?;
+    read_off(contents)?.to_tetgen(tolerance)
+}
+
+/// Reads a PLY file (ASCII or binary) and allocates a Tetgen with one facet per triangle
+///
+/// The vertices are given by the `x`, `y`, and `z` properties of the `vertex` element, and the
+/// polygons by the `vertex_indices` (or `vertex_index`) list of the `face` element. Polygons with more
+/// than three vertices are split into triangles (as a fan around the first vertex). The vertices
+/// closer than `tolerance` are merged (see below).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+/// * `tolerance` -- is the maximum distance between vertices to be merged (zero means exactly equal vertices)
+///
+/// # Warning
+///
+/// The surface must be closed. Triangles that collapse after merging the vertices are discarded,
+/// and vertices not used by any triangle are ignored. Other elements and properties are ignored.
+pub fn read_ply_tetgen<P>(full_path: &P, tolerance: f64) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let bytes = read_file_bytes(full_path)?;
+    read_ply(&bytes)?.to_tetgen(tolerance)
+}
+
+/// Holds the vertices and polygons of a surface read from a file
+struct Surface {
+    vertices: Vec<[f64; 3]>,   // coordinates of the vertices
+    polygons: Vec<Vec<usize>>, // vertex indices of each polygon
+}
+
+impl Surface {
+    /// Merges the vertices, splits the polygons into triangles, and allocates a Tetgen
+    fn to_tetgen(&self, tolerance: f64) -> Result<Tetgen, Error> {
+        if tolerance < 0.0 {
+            return Err(Error::InvalidInput("the tolerance must be non-negative"));
+        }
+
+        // merge the vertices used by the polygons
+        let mut merger = PointMerger::new(tolerance);
+        let mut ids: Vec<Option<usize>> = vec![None; self.vertices.len()];
+        let mut triangles = Vec::new();
+        for polygon in &self.polygons {
+            let mut corners = Vec::with_capacity(polygon.len());
+            for v in polygon {
+                let id = match ids[*v] {
+                    Some(id) => id,
+                    None => {
+                        let id = merger.insert(&self.vertices[*v]);
+                        ids[*v] = Some(id);
+                        id
+                    }
+                };
+                corners.push(id);
+            }
+            for k in 1..(corners.len() - 1) {
+                let (a, b, c) = (corners[0], corners[k], corners[k + 1]);
+                if a != b && b != c && c != a {
+                    triangles.push([a, b, c]);
+                }
+            }
+        }
+
+        // allocate and set the data
+        let mut tetgen = Tetgen::new(merger.points.len(), Some(vec![3; triangles.len()]), None, None)?;
+        for (index, x) in merger.points.iter().enumerate() {
+            tetgen.set_point(index, x[0], x[1], x[2])?;
+        }
+        for (index, triangle) in triangles.iter().enumerate() {
+            for (m, p) in triangle.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        Ok(tetgen)
+    }
+}
+
+/// Merges points closer than a tolerance using a uniform grid
+struct PointMerger {
+    tolerance: f64,                      // maximum distance between merged points
+    points: Vec<[f64; 3]>,               // unique points
+    grid: HashMap<[i64; 3], Vec<usize>>, // grid cell → indices of points (if tolerance > 0)
+    exact: HashMap<[u64; 3], usize>,     // bits of coordinates → index of point (if tolerance = 0)
+}
+
+impl PointMerger {
+    /// Allocates a new instance
+    fn new(tolerance: f64) -> Self {
+        PointMerger {
+            tolerance,
+            points: Vec::new(),
+            grid: HashMap::new(),
+            exact: HashMap::new(),
+        }
+    }
+
+    /// Returns the index of the (possibly new) unique point at x
+    fn insert(&mut self, x: &[f64; 3]) -> usize {
+        if self.tolerance == 0.0 {
+            // adding 0.0 turns -0.0 into 0.0
+            let key = [(x[0] + 0.0).to_bits(), (x[1] + 0.0).to_bits(), (x[2] + 0.0).to_bits()];
+            let n = self.points.len();
+            let index = *self.exact.entry(key).or_insert(n);
+            if index == n {
+                self.points.push(*x);
+            }
+            return index;
+        }
+        let cell = x.map(|v| f64::floor(v / self.tolerance) as i64);
+        for i in -1..=1 {
+            for j in -1..=1 {
+                for k in -1..=1 {
+                    if let Some(indices) = self.grid.get(&[cell[0] + i, cell[1] + j, cell[2] + k]) {
+                        for index in indices {
+                            let y = &self.points[*index];
+                            let distance =
+                                f64::sqrt((x[0] - y[0]).powi(2) + (x[1] - y[1]).powi(2) + (x[2] - y[2]).powi(2));
+                            if distance <= self.tolerance {
+                                return *index;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let index = self.points.len();
+        self.points.push(*x);
+        self.grid.entry(cell).or_default().push(index);
+        index
+    }
+}
+
+/// Reads binary data sequentially
+struct BinaryReader<'a> {
+    bytes: &'a [u8],  // data
+    position: usize,  // position of the next byte
+    big_endian: bool, // byte order
+}
+
+impl<'a> BinaryReader<'a> {
+    /// Allocates a new instance
+    fn new(bytes: &'a [u8], position: usize, big_endian: bool) -> Self {
+        BinaryReader {
+            bytes,
+            position,
+            big_endian,
+        }
+    }
+
+    /// Returns the number of bytes not read yet
+    fn remaining(&self) -> usize {
+        self.bytes.len().saturating_sub(self.position)
+    }
+
+    /// Returns the next N bytes (in little-endian order)
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        if self.position + N > self.bytes.len() {
+            return Err(Error::InvalidInput("the binary data is truncated"));
+        }
+        let mut data = [0; N];
+        data.copy_from_slice(&self.bytes[self.position..(self.position + N)]);
+        if self.big_endian {
+            data.reverse();
+        }
+        self.position += N;
+        Ok(data)
+    }
+
+    /// Reads the next value
+    fn read(&mut self, kind: PlyType) -> Result<f64, Error> {
+        Ok(match kind {
+            PlyType::Int8 => i8::from_le_bytes(self.take()?) as f64,
+            PlyType::UInt8 => u8::from_le_bytes(self.take()?) as f64,
+            PlyType::Int16 => i16::from_le_bytes(self.take()?) as f64,
+            PlyType::UInt16 => u16::from_le_bytes(self.take()?) as f64,
+            PlyType::Int32 => i32::from_le_bytes(self.take()?) as f64,
+            PlyType::UInt32 => u32::from_le_bytes(self.take()?) as f64,
+            PlyType::Float32 => f32::from_le_bytes(self.take()?) as f64,
+            PlyType::Float64 => f64::from_le_bytes(self.take()?),
+        })
+    }
+}
+
+/// Checks whether the size of the file matches the number of triangles of a binary STL file
+fn is_binary_stl(bytes: &[u8]) -> bool {
+    if bytes.len() < 84 {
+        return false;
+    }
+    let ntriangle = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    bytes.len() == 84 + 50 * ntriangle
+}
+
+/// Reads a binary STL file (80-byte header, number of triangles, and 50 bytes per triangle)
+fn read_binary_stl(bytes: &[u8]) -> Result<Surface, Error> {
+    let mut reader = BinaryReader::new(bytes, 80, false);
+    let ntriangle = reader.read(PlyType::UInt32)? as usize;
+    let mut vertices = Vec::with_capacity(3 * ntriangle);
+    let mut polygons = Vec::with_capacity(ntriangle);
+    for _ in 0..ntriangle {
+        for _ in 0..3 {
+            reader.read(PlyType::Float32)?; // normal
+        }
+        for _ in 0..3 {
+            let mut x = [0.0; 3];
+            for v in x.iter_mut() {
+                *v = reader.read(PlyType::Float32)?;
+            }
+            vertices.push(x);
+        }
+        reader.read(PlyType::UInt16)?; // attribute byte count
+        let n = vertices.len();
+        polygons.push(vec![n - 3, n - 2, n - 1]);
+    }
+    Ok(Surface { vertices, polygons })
+}
+
+/// Reads an ASCII STL file
+fn read_ascii_stl(contents: &str) -> Result<Surface, Error> {
+    let mut lines = DataLines::new(contents);
+    let mut vertices = Vec::new();
+    let mut polygons = Vec::new();
+    let mut polygon = Vec::new();
+    while let Some(fields) = lines.next_fields() {
+        match fields[0] {
+            "vertex" => {
+                if fields.len() < 4 {
+                    return Err(lines.error("cannot read vertex"));
+                }
+                polygon.push(vertices.len());
+                vertices.push(parse_coordinates(&lines, &fields[1..4])?);
+            }
+            "endloop" => {
+                if polygon.len() < 3 {
+                    return Err(lines.error("the facet must have at least three vertices"));
+                }
+                polygons.push(std::mem::take(&mut polygon));
+            }
+            _ => (), // solid, facet normal, outer loop, endfacet, endsolid
+        }
+    }
+    Ok(Surface { vertices, polygons })
+}
+
+/// Reads an OFF file
+fn read_off(contents: &str) -> Result<Surface, Error> {
+    let mut lines = DataLines::new(contents);
+
+    // header (the counts may be in the same line as the keyword)
+    let fields = lines.expect_fields(1, "cannot read the OFF header")?;
+    if !fields[0].ends_with("OFF") {
+        return Err(lines.error("the OFF file must start with OFF"));
+    }
+    let fields = if fields.len() > 1 {
+        fields[1..].to_vec()
+    } else {
+        lines.expect_fields(2, "cannot read the number of vertices and faces")?
+    };
+    if fields.len() < 2 {
+        return Err(lines.error("cannot read the number of vertices and faces"));
+    }
+    let nvertex: usize = lines.parse(fields[0], "cannot parse the number of vertices")?;
+    let nface: usize = lines.parse(fields[1], "cannot parse the number of faces")?;
+
+    // vertices (the counts come from the file; thus, they are not used to preallocate memory)
+    let mut vertices = Vec::new();
+    for _ in 0..nvertex {
+        let fields = lines.expect_fields(3, "cannot read vertex")?;
+        vertices.push(parse_coordinates(&lines, &fields[..3])?);
+    }
+
+    // faces
+    let mut polygons = Vec::new();
+    for _ in 0..nface {
+        let fields = lines.expect_fields(1, "cannot read face")?;
+        let n: usize = lines.parse(fields[0], "cannot parse the number of vertices of face")?;
+        if n < 3 {
+            return Err(lines.error("the face must have at least three vertices"));
+        }
+        if n > fields.len() - 1 {
+            return Err(lines.error("cannot read face"));
+        }
+        let mut polygon = Vec::with_capacity(n); // n ≤ number of fields
+        for field in &fields[1..(1 + n)] {
+            let v: usize = lines.parse(field, "cannot parse the vertex index of face")?;
+            if v >= nvertex {
+                return Err(lines.error("the vertex index of face is out of bounds"));
+            }
+            polygon.push(v);
+        }
+        polygons.push(polygon);
+    }
+    Ok(Surface { vertices, polygons })
+}
+
+/// Defines the scalar types of PLY files
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PlyType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl PlyType {
+    /// Returns the number of bytes of a value in binary files
+    fn size(&self) -> usize {
+        match self {
+            PlyType::Int8 | PlyType::UInt8 => 1,
+            PlyType::Int16 | PlyType::UInt16 => 2,
+            PlyType::Int32 | PlyType::UInt32 | PlyType::Float32 => 4,
+            PlyType::Float64 => 8,
+        }
+    }
+}
+
+/// Holds a property of an element of a PLY file
+struct PlyProperty {
+    name: String,           // name of the property
+    count: Option<PlyType>, // type of the number of items (if the property is a list)
+    kind: PlyType,          // type of the value (or of each item of the list)
+}
+
+/// Holds an element of a PLY file
+struct PlyElement {
+    name: String,                 // name of the element
+    count: usize,                 // number of instances
+    properties: Vec<PlyProperty>, // properties of each instance
+}
+
+/// Parses a PLY scalar type
+fn parse_ply_type(lines: &DataLines, field: &str) -> Result<PlyType, Error> {
+    match field {
+        "char" | "int8" => Ok(PlyType::Int8),
+        "uchar" | "uint8" => Ok(PlyType::UInt8),
+        "short" | "int16" => Ok(PlyType::Int16),
+        "ushort" | "uint16" => Ok(PlyType::UInt16),
+        "int" | "int32" => Ok(PlyType::Int32),
+        "uint" | "uint32" => Ok(PlyType::UInt32),
+        "float" | "float32" => Ok(PlyType::Float32),
+        "double" | "float64" => Ok(PlyType::Float64),
+        _ => Err(lines.error("cannot parse the type of property")),
+    }
+}
+
+/// Reads the header of a PLY file, returning the format and the elements
+///
+/// The format is None for ASCII files or Some(big_endian) for binary files.
+fn read_ply_header(lines: &mut DataLines) -> Result<(Option<bool>, Vec<PlyElement>), Error> {
+    let fields = lines.expect_fields(1, "cannot read the PLY header")?;
+    if fields[0] != "ply" {
+        return Err(lines.error("the PLY file must start with ply"));
+    }
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    loop {
+        let fields = lines.expect_fields(1, "cannot find the end of the PLY header")?;
+        match fields[0] {
+            "format" => {
+                format = match fields.get(1) {
+                    Some(&"ascii") => Some(None),
+                    Some(&"binary_little_endian") => Some(Some(false)),
+                    Some(&"binary_big_endian") => Some(Some(true)),
+                    _ => return Err(lines.error("cannot parse the format of the PLY file")),
+                };
+            }
+            "element" => {
+                if fields.len() < 3 {
+                    return Err(lines.error("cannot read element"));
+                }
+                elements.push(PlyElement {
+                    name: fields[1].to_string(),
+                    count: lines.parse(fields[2], "cannot parse the number of instances of element")?,
+                    properties: Vec::new(),
+                });
+            }
+            "property" => {
+                let element = match elements.last_mut() {
+                    Some(element) => element,
+                    None => return Err(lines.error("the property must follow an element")),
+                };
+                let property = if fields.get(1) == Some(&"list") {
+                    if fields.len() < 5 {
+                        return Err(lines.error("cannot read property"));
+                    }
+                    PlyProperty {
+                        name: fields[4].to_string(),
+                        count: Some(parse_ply_type(lines, fields[2])?),
+                        kind: parse_ply_type(lines, fields[3])?,
+                    }
+                } else {
+                    if fields.len() < 3 {
+                        return Err(lines.error("cannot read property"));
+                    }
+                    PlyProperty {
+                        name: fields[2].to_string(),
+                        count: None,
+                        kind: parse_ply_type(lines, fields[1])?,
+                    }
+                };
+                element.properties.push(property);
+            }
+            "end_header" => break,
+            _ => (), // comment, obj_info
+        }
+    }
+    match format {
+        Some(format) => Ok((format, elements)),
+        None => Err(lines.error("the PLY file must define the format")),
+    }
+}
+
+/// Reads a PLY file
+fn read_ply(bytes: &[u8]) -> Result<Surface, Error> {
+    // the header is ASCII and ends with the end_header line
+    let marker = b"end_header";
+    let position = bytes
+        .windows(marker.len())
+        .position(|w| w == marker)
+        .ok_or(Error::InvalidInput("cannot find the end of the PLY header"))?;
+    let header_end = match bytes[position..].iter().position(|b| *b == b'\n') {
+        Some(i) => position + i + 1,
+        None => bytes.len(),
+    };
+    let header =
+        std::str::from_utf8(&bytes[..header_end]).map_err(|_| Error::InvalidInput("the PLY file is invalid"))?;
+    let mut lines = DataLines::new(header);
+    let (format, elements) = read_ply_header(&mut lines)?;
+
+    // data of each instance of each element: scalars and lists
+    let mut values: Vec<Vec<Vec<Vec<f64>>>> = Vec::with_capacity(elements.len());
+    match format {
+        None => {
+            let contents = std::str::from_utf8(bytes).map_err(|_| Error::InvalidInput("the PLY file is invalid"))?;
+            let mut lines = DataLines::new(contents);
+            read_ply_header(&mut lines)?;
+            for element in &elements {
+                // the number of instances comes from the file; thus, it is not used to preallocate memory
+                let mut instances = Vec::new();
+                for _ in 0..element.count {
+                    let fields = lines.expect_fields(1, "cannot read element")?;
+                    let mut next = fields.iter();
+                    let mut instance = Vec::with_capacity(element.properties.len());
+                    for property in &element.properties {
+                        let n = match property.count {
+                            Some(_) => {
+                                let field = next.next().ok_or_else(|| lines.error("cannot read element"))?;
+                                let n: usize = lines.parse(field, "cannot parse the number of items of property")?;
+                                if n > next.len() {
+                                    return Err(lines.error("cannot read element"));
+                                }
+                                n
+                            }
+                            None => 1,
+                        };
+                        let mut items = Vec::with_capacity(n); // n ≤ number of remaining fields
+                        for _ in 0..n {
+                            let field = next.next().ok_or_else(|| lines.error("cannot read element"))?;
+                            items.push(lines.parse(field, "cannot parse the value of property")?);
+                        }
+                        instance.push(items);
+                    }
+                    instances.push(instance);
+                }
+                values.push(instances);
+            }
+        }
+        Some(big_endian) => {
+            let mut reader = BinaryReader::new(bytes, header_end, big_endian);
+            for element in &elements {
+                // each instance has at least one value (or the number of items) per property
+                let size: usize = element
+                    .properties
+                    .iter()
+                    .map(|p| p.count.unwrap_or(p.kind).size())
+                    .sum();
+                if size == 0 && element.count > 0 {
+                    return Err(Error::InvalidInput("the element must have at least one property"));
+                }
+                if size > 0 && element.count > reader.remaining() / size {
+                    return Err(Error::InvalidInput("the binary data is truncated"));
+                }
+                let mut instances = Vec::with_capacity(element.count);
+                for _ in 0..element.count {
+                    let mut instance = Vec::with_capacity(element.properties.len());
+                    for property in &element.properties {
+                        let n = match property.count {
+                            Some(kind) => reader.read(kind)? as usize,
+                            None => 1,
+                        };
+                        if n > reader.remaining() / property.kind.size() {
+                            return Err(Error::InvalidInput("the binary data is truncated"));
+                        }
+                        let mut items = Vec::with_capacity(n);
+                        for _ in 0..n {
+                            items.push(reader.read(property.kind)?);
+                        }
+                        instance.push(items);
+                    }
+                    instances.push(instance);
+                }
+                values.push(instances);
+            }
+        }
+    }
+
+    // vertices
+    let property_index =
+        |element: &PlyElement, names: &[&str]| element.properties.iter().position(|p| names.contains(&p.name.as_str()));
+    let mut vertices = Vec::new();
+    let mut polygons = Vec::new();
+    for (element, instances) in elements.iter().zip(values.iter()) {
+        if element.name == "vertex" {
+            let ids = [
+                property_index(element, &["x"]),
+                property_index(element, &["y"]),
+                property_index(element, &["z"]),
+            ];
+            if ids.iter().any(|id| id.is_none()) {
+                return Err(Error::InvalidInput(
+                    "the vertex element must have the x, y, and z properties",
+                ));
+            }
+            if ids.iter().any(|id| element.properties[id.unwrap()].count.is_some()) {
+                return Err(Error::InvalidInput(
+                    "the x, y, and z properties of vertex must not be lists",
+                ));
+            }
+            for instance in instances {
+                vertices.push(ids.map(|id| instance[id.unwrap()][0]));
+            }
+        }
+    }
+    for (element, instances) in elements.iter().zip(values.iter()) {
+        if element.name == "face" {
+            let id = property_index(element, &["vertex_indices", "vertex_index"]).ok_or(Error::InvalidInput(
+                "the face element must have the vertex_indices property",
+            ))?;
+            for instance in instances {
+                let items = &instance[id];
+                if items.len() < 3 {
+                    return Err(Error::InvalidInput("the face must have at least three vertices"));
+                }
+                let mut polygon = Vec::with_capacity(items.len());
+                for v in items {
+                    if *v < 0.0 || *v as usize >= vertices.len() {
+                        return Err(Error::InvalidInput("the vertex index of face is out of bounds"));
+                    }
+                    polygon.push(*v as usize);
+                }
+                polygons.push(polygon);
+            }
+        }
+    }
+    Ok(Surface { vertices, polygons })
+}
+
+/// Parses the x-y-z coordinates of a vertex
+fn parse_coordinates(lines: &DataLines, fields: &[&str]) -> Result<[f64; 3], Error> {
+    let mut x = [0.0; 3];
+    for i in 0..3 {
+        x[i] = lines.parse(fields[i], "cannot parse the coordinates of vertex")?;
+    }
+    Ok(x)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{read_off_tetgen, read_ply_tetgen, read_stl_tetgen, PointMerger};
+    use crate::file_io::write_file;
+    use crate::test_fixtures::{total_volume, CUBE_FACETS, CUBE_POINTS};
+    use crate::{Error, Item, Tetgen};
+
+    // returns the triangles of the cube (two per facet)
+    fn cube_triangles() -> Vec<[usize; 3]> {
+        let mut triangles = Vec::new();
+        for q in &CUBE_FACETS {
+            triangles.push([q[0], q[1], q[2]]);
+            triangles.push([q[0], q[2], q[3]]);
+        }
+        triangles
+    }
+
+    // generates the mesh and checks the number of points and the volume
+    fn check_cube(tetgen: &mut Tetgen) -> Result<(), Error> {
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.npoint(), 8);
+        assert_eq!(tetgen.nface(), 12);
+        let volume = total_volume(tetgen)?;
+        assert!(f64::abs(volume - 1.0) < 1e-15);
+        Ok(())
+    }
+
+    #[test]
+    fn point_merger_works() {
+        let mut merger = PointMerger::new(0.0);
+        assert_eq!(merger.insert(&[0.0, 0.0, 0.0]), 0);
+        assert_eq!(merger.insert(&[1.0, 0.0, 0.0]), 1);
+        assert_eq!(merger.insert(&[-0.0, 0.0, 0.0]), 0);
+        assert_eq!(merger.insert(&[1e-12, 0.0, 0.0]), 2);
+        let mut merger = PointMerger::new(1e-6);
+        assert_eq!(merger.insert(&[0.0, 0.0, 0.0]), 0);
+        assert_eq!(merger.insert(&[1.0, 0.0, 0.0]), 1);
+        assert_eq!(merger.insert(&[-1e-7, 1e-7, 0.0]), 0);
+        assert_eq!(merger.insert(&[1.0, 0.0, 9e-7]), 1);
+        assert_eq!(merger.insert(&[1.0, 0.0, 2e-6]), 2);
+        assert_eq!(merger.points.len(), 3);
+    }
+
+    #[test]
+    fn read_surface_captures_some_errors() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_read_surface_error.off";
+        write_file(file_path, "OFF\n4 1 0\n0 0 0\n1 0 0\n0 1 0\n0 0 1\n3 0 1 4\n")?;
+        assert_eq!(
+            read_off_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 7,
+                message: "the vertex index of face is out of bounds"
+            })
+        );
+        write_file(file_path, "4 1 0\n")?;
+        assert_eq!(
+            read_off_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 1,
+                message: "the OFF file must start with OFF"
+            })
+        );
+        write_file(file_path, "OFF\n4 1 0\n0 0 0\n1 0 0\n0 1 0\n0 0 1\n3 0 1 2\n")?;
+        assert_eq!(
+            read_off_tetgen(file_path, -1.0).err(),
+            Some(Error::InvalidInput("the tolerance must be non-negative"))
+        );
+        // only the three points of the face are used
+        assert_eq!(
+            read_off_tetgen(file_path, 0.0).err(),
+            Some(Error::TooFew {
+                item: Item::Point,
+                count: 3,
+                min: 4
+            })
+        );
+        let file_path = "/tmp/tritet/test_read_surface_error.ply";
+        write_file(file_path, "ply\nelement vertex 0\nend_header\n")?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 3,
+                message: "the PLY file must define the format"
+            })
+        );
+        write_file(
+            file_path,
+            "ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty float x\nend_header\n",
+        )?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidInput("the binary data is truncated"))
+        );
+        // the counts read from the file are not used to preallocate memory
+        let file_path = "/tmp/tritet/test_read_surface_error.off";
+        write_file(file_path, "OFF\n99999999999999999 1 0\n0 0 0\n")?;
+        assert_eq!(
+            read_off_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 4,
+                message: "cannot read vertex"
+            })
+        );
+        write_file(
+            file_path,
+            "OFF\n4 1 0\n0 0 0\n1 0 0\n0 1 0\n0 0 1\n18446744073709551615 0 1 2\n",
+        )?;
+        assert_eq!(
+            read_off_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 7,
+                message: "cannot read face"
+            })
+        );
+        let file_path = "/tmp/tritet/test_read_surface_error.ply";
+        let header = "ply\nformat ascii 1.0\n\
+                      element vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+                      element face 1\nproperty list uchar int vertex_indices\nend_header\n\
+                      0 0 0\n1 0 0\n0 1 0\n";
+        write_file(file_path, &format!("{}1e30 0 1 2\n", header))?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot parse the number of items of property"
+            })
+        );
+        write_file(file_path, &format!("{}9 0 1 2\n", header))?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot read element"
+            })
+        );
+        write_file(
+            file_path,
+            "ply\nformat ascii 1.0\nelement vertex 99999999999999999\nproperty float x\nend_header\n0\n",
+        )?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidFile {
+                line: 7,
+                message: "cannot read element"
+            })
+        );
+        let mut bytes = "ply\nformat binary_little_endian 1.0\n\
+                         element face 1\nproperty list uint uint vertex_indices\nend_header\n"
+            .to_string()
+            .into_bytes();
+        bytes.extend_from_slice(&4_000_000_000_u32.to_le_bytes());
+        bytes.extend_from_slice(&[0; 12]);
+        write_file(file_path, &bytes)?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidInput("the binary data is truncated"))
+        );
+        write_file(
+            file_path,
+            "ply\nformat binary_little_endian 1.0\nelement vertex 99999999999999999\nproperty float x\nend_header\n",
+        )?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidInput("the binary data is truncated"))
+        );
+        write_file(
+            file_path,
+            "ply\nformat binary_little_endian 1.0\nelement vertex 99999999999999999\nend_header\n",
+        )?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidInput("the element must have at least one property"))
+        );
+        // the coordinates must not be lists (which may be empty)
+        write_file(
+            file_path,
+            "ply\nformat ascii 1.0\n\
+             element vertex 1\nproperty list uchar float x\nproperty float y\nproperty float z\nend_header\n\
+             0 0 0\n",
+        )?;
+        assert_eq!(
+            read_ply_tetgen(file_path, 0.0).err(),
+            Some(Error::InvalidInput(
+                "the x, y, and z properties of vertex must not be lists"
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_stl_tetgen_works() -> Result<(), Error> {
+        // ASCII (each triangle has its own vertices; one vertex is slightly off)
+        let mut contents = String::from("solid cube\n");
+        for (t, triangle) in cube_triangles().iter().enumerate() {
+            contents.push_str("  facet normal 0 0 0\n    outer loop\n");
+            for p in triangle {
+                let x = CUBE_POINTS[*p];
+                let delta = if t == 5 && *p == 6 { 1e-9 } else { 0.0 };
+                contents.push_str(&format!("      vertex {} {} {}\n", x[0] + delta, x[1], x[2]));
+            }
+            contents.push_str("    endloop\n  endfacet\n");
+        }
+        contents.push_str("endsolid cube\n");
+        let file_path = "/tmp/tritet/test_read_stl_tetgen_ascii.stl";
+        write_file(file_path, &contents)?;
+        check_cube(&mut read_stl_tetgen(file_path, 1e-6)?)?;
+
+        // binary
+        let mut bytes = vec![0_u8; 80];
+        bytes.extend_from_slice(&12_u32.to_le_bytes());
+        for triangle in cube_triangles() {
+            bytes.extend_from_slice(&[0; 12]);
+            for p in triangle {
+                for v in CUBE_POINTS[p] {
+                    bytes.extend_from_slice(&(v as f32).to_le_bytes());
+                }
+            }
+            bytes.extend_from_slice(&[0; 2]);
+        }
+        let file_path = "/tmp/tritet/test_read_stl_tetgen_binary.stl";
+        write_file(file_path, &bytes)?;
+        check_cube(&mut read_stl_tetgen(file_path, 0.0)?)
+    }
+
+    #[test]
+    fn read_off_tetgen_works() -> Result<(), Error> {
+        let mut contents = String::from("OFF # cube\n8 6 12\n");
+        for x in &CUBE_POINTS {
+            contents.push_str(&format!("{} {} {}\n", x[0], x[1], x[2]));
+        }
+        for q in &CUBE_FACETS {
+            contents.push_str(&format!("4 {} {} {} {} 255 0 0\n", q[0], q[1], q[2], q[3]));
+        }
+        let file_path = "/tmp/tritet/test_read_off_tetgen.off";
+        write_file(file_path, &contents)?;
+        check_cube(&mut read_off_tetgen(file_path, 0.0)?)
+    }
+
+    #[test]
+    fn read_ply_tetgen_works() -> Result<(), Error> {
+        // ASCII with quadrilaterals and an extra element
+        let mut contents = String::from(
+            "ply\nformat ascii 1.0\ncomment cube\n\
+             element vertex 8\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\n\
+             element face 6\nproperty list uchar int vertex_index\n\
+             element edge 1\nproperty int vertex1\nproperty int vertex2\n\
+             end_header\n",
+        );
+        for x in &CUBE_POINTS {
+            contents.push_str(&format!("{} {} {} 255\n", x[0], x[1], x[2]));
+        }
+        for q in &CUBE_FACETS {
+            contents.push_str(&format!("4 {} {} {} {}\n", q[0], q[1], q[2], q[3]));
+        }
+        contents.push_str("0 1\n");
+        let file_path = "/tmp/tritet/test_read_ply_tetgen_ascii.ply";
+        write_file(file_path, &contents)?;
+        check_cube(&mut read_ply_tetgen(file_path, 0.0)?)?;
+
+        // binary with triangles (both byte orders)
+        for big_endian in [false, true] {
+            let format = if big_endian {
+                "binary_big_endian"
+            } else {
+                "binary_little_endian"
+            };
+            let mut bytes = format!(
+                "ply\nformat {} 1.0\n\
+                 element vertex 8\nproperty double x\nproperty double y\nproperty double z\n\
+                 element face 12\nproperty list uchar uint vertex_indices\n\
+                 end_header\n",
+                format
+            )
+            .into_bytes();
+            for x in &CUBE_POINTS {
+                for v in x {
+                    bytes.extend_from_slice(&if big_endian { v.to_be_bytes() } else { v.to_le_bytes() });
+                }
+            }
+            for triangle in cube_triangles() {
+                bytes.push(3);
+                for p in triangle {
+                    let p = p as u32;
+                    bytes.extend_from_slice(&if big_endian { p.to_be_bytes() } else { p.to_le_bytes() });
+                }
+            }
+            let file_path = "/tmp/tritet/test_read_ply_tetgen_binary.ply";
+            write_file(file_path, &bytes)?;
+            check_cube(&mut read_ply_tetgen(file_path, 0.0)?)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{Error, Tetgen};
+
+/// Holds the points of the unit cube
+pub(crate) const CUBE_POINTS: [[f64; 3]; 8] = [
+    [0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0],
+    [1.0, 1.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [1.0, 0.0, 1.0],
+    [1.0, 1.0, 1.0],
+    [0.0, 1.0, 1.0],
+];
+
+/// Holds the facets of the unit cube (x=0, x=1, y=0, y=1, z=0, z=1) with outward normals
+pub(crate) const CUBE_FACETS: [[usize; 4]; 6] = [
+    [0, 4, 7, 3],
+    [1, 2, 6, 5],
+    [0, 1, 5, 4],
+    [2, 3, 7, 6],
+    [0, 3, 2, 1],
+    [4, 5, 6, 7],
+];
+
+/// Returns the facets of the unit cube as required by [Tetgen::from_input]
+pub(crate) fn cube_facets() -> Vec<Vec<usize>> {
+    CUBE_FACETS.iter().map(|facet| facet.to_vec()).collect()
+}
+
+/// Sets the points and facets of the unit cube with the markers -1 (x=0), -2 (x=1), ..., -6 (z=1)
+///
+/// The instance must have been allocated with (at least) 8 points and 6 facets with 4 points each.
+pub(crate) fn set_cube(tetgen: &mut Tetgen) -> Result<(), Error> {
+    for (p, x) in CUBE_POINTS.iter().enumerate() {
+        tetgen.set_point(p, x[0], x[1], x[2])?;
+    }
+    for (index, points) in CUBE_FACETS.iter().enumerate() {
+        for (m, p) in points.iter().enumerate() {
+            tetgen.set_facet_point(index, m, *p)?;
+        }
+        tetgen.set_facet_marker(index, -(index as i32) - 1)?;
+    }
+    Ok(())
+}
+
+/// Allocates the unit cube (see [set_cube])
+pub(crate) fn cube() -> Result<Tetgen, Error> {
+    let mut tetgen = Tetgen::new(8, Some(vec![4; 6]), None, None)?;
+    set_cube(&mut tetgen)?;
+    Ok(tetgen)
+}
+
+/// Returns the total volume of the tetrahedra
+pub(crate) fn total_volume(tetgen: &Tetgen) -> Result<f64, Error> {
+    let quality = tetgen.quality(None)?;
+    Ok(quality.tets.iter().map(|t| t.volume).sum())
+}
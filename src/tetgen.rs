@@ -1,12 +1,13 @@
+use crate::bitset::{check_each, BitSet};
 use crate::constants;
-use crate::conversion::to_i32;
-use crate::StrError;
+use crate::conversion::{to_i32, to_slice};
+use crate::{Error, Item, Stage};
 
 #[cfg(feature = "plotpy")]
-use std::collections::HashMap;
+use crate::mesh;
 
 #[cfg(feature = "plotpy")]
-use plotpy::{Canvas, Plot, Text};
+use plotpy::Plot;
 
 #[repr(C)]
 pub(crate) struct ExtTetgen {
@@ -15,10 +16,20 @@ pub(crate) struct ExtTetgen {
 }
 
 extern "C" {
-    fn new_tetgen(npoint: i32, nfacet: i32, facet_npoint: *const i32, nregion: i32, nhole: i32) -> *mut ExtTetgen;
+    fn new_tetgen(
+        npoint: i32,
+        nfacet: i32,
+        facet_npolygon: *const i32,
+        facet_polygon_npoint: *const i32,
+        facet_nhole: *const i32,
+        nregion: i32,
+        nhole: i32,
+    ) -> *mut ExtTetgen;
     fn drop_tetgen(tetgen: *mut ExtTetgen);
     fn tet_set_point(tetgen: *mut ExtTetgen, index: i32, x: f64, y: f64, z: f64) -> i32;
-    fn tet_set_facet_point(tetgen: *mut ExtTetgen, index: i32, m: i32, p: i32) -> i32;
+    fn tet_set_facet_point(tetgen: *mut ExtTetgen, index: i32, polygon: i32, m: i32, p: i32) -> i32;
+    fn tet_set_facet_hole(tetgen: *mut ExtTetgen, index: i32, hole: i32, x: f64, y: f64, z: f64) -> i32;
+    fn tet_set_facet_marker(tetgen: *mut ExtTetgen, index: i32, marker: i32) -> i32;
     fn tet_set_region(
         tetgen: *mut ExtTetgen,
         index: i32,
@@ -37,12 +48,36 @@ extern "C" {
         global_max_volume: f64,
         global_min_angle: f64,
     ) -> i32;
+    fn tet_run_refine(
+        tetgen: *mut ExtTetgen,
+        verbose: i32,
+        o2: i32,
+        max_volumes: *const f64,
+        global_min_angle: f64,
+    ) -> i32;
+    fn tet_run_intersection_check(tetgen: *mut ExtTetgen, verbose: i32) -> i32;
     fn tet_get_npoint(tetgen: *mut ExtTetgen) -> i32;
     fn tet_get_ntetrahedron(tetgen: *mut ExtTetgen) -> i32;
     fn tet_get_ncorner(tetgen: *mut ExtTetgen) -> i32;
     fn tet_get_point(tetgen: *mut ExtTetgen, index: i32, dim: i32) -> f64;
     fn tet_get_tetrahedron_corner(tetgen: *mut ExtTetgen, index: i32, corner: i32) -> i32;
     fn tet_get_tetrahedron_attribute(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_tetrahedron_neighbor(tetgen: *mut ExtTetgen, index: i32, face: i32) -> i32;
+    fn tet_get_nface(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_face_corner(tetgen: *mut ExtTetgen, index: i32, corner: i32) -> i32;
+    fn tet_get_face_marker(tetgen: *mut ExtTetgen, index: i32) -> i32;
+    fn tet_get_point_list(tetgen: *mut ExtTetgen) -> *const f64;
+    fn tet_get_tetrahedron_list(tetgen: *mut ExtTetgen) -> *const i32;
+    fn tet_get_ntetrahedron_attribute(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_tetrahedron_attribute_list(tetgen: *mut ExtTetgen) -> *const f64;
+    fn tet_get_neighbor_list(tetgen: *mut ExtTetgen) -> *const i32;
+    fn tet_get_face_list(tetgen: *mut ExtTetgen) -> *const i32;
+    fn tet_get_face_middle_list(tetgen: *mut ExtTetgen) -> *const i32;
+    fn tet_get_face_marker_list(tetgen: *mut ExtTetgen) -> *const i32;
+    fn tet_get_nintersection(tetgen: *mut ExtTetgen) -> i32;
+    fn tet_get_intersection_list(tetgen: *mut ExtTetgen) -> *const i32;
+    fn tet_get_input_point_list(tetgen: *mut ExtTetgen) -> *const f64;
+    fn tet_get_input_facet_point(tetgen: *mut ExtTetgen, index: i32, polygon: i32, m: i32) -> i32;
 }
 
 /// Implements high-level functions to call Si's Tetgen Cpp-Code
@@ -55,9 +90,9 @@ extern "C" {
 ///
 /// ```
 /// use plotpy::Plot;
-/// use tritet::{StrError, Tetgen};
+/// use tritet::{Error, Tetgen};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // allocate data for 4 points
 ///     let mut tetgen = Tetgen::new(5, None, None, None)?;
 ///
@@ -90,9 +125,9 @@ extern "C" {
 ///
 /// ```
 /// use plotpy::Plot;
-/// use tritet::{StrError, Tetgen};
+/// use tritet::{Error, Tetgen};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // allocate data for 4 points
 ///     let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), None)?;
 ///
@@ -141,17 +176,18 @@ extern "C" {
 ///
 /// ![doc_tetgen_mesh_1.svg](https://raw.githubusercontent.com/cpmech/tritet/main/data/figures/doc_tetgen_mesh_1.svg)
 pub struct Tetgen {
-    ext_tetgen: *mut ExtTetgen,       // data allocate by the c-code
-    npoint: usize,                    // number of points
-    facet_npoint: Option<Vec<usize>>, // number of points on each facet
-    total_facet_npoint: usize,        // total number of facet points
-    facet_point_set_count: usize,     // counts the number of facet point already set
-    nregion: Option<usize>,           // number of regions
-    nhole: Option<usize>,             // number of holes
-    all_points_set: bool,             // indicates that all points have been set
-    all_facets_set: bool,             // indicates that all facets have been set
-    all_regions_set: bool,            // indicates that all regions have been set
-    all_holes_set: bool,              // indicates that all holes have been set
+    ext_tetgen: *mut ExtTetgen,                    // data allocate by the c-code
+    npoint: usize,                                 // number of points
+    facet_polygon_npoint: Option<Vec<Vec<usize>>>, // number of points on each polygon of each facet
+    facet_nhole: Option<Vec<usize>>,               // number of holes on each facet
+    nregion: Option<usize>,                        // number of regions
+    nhole: Option<usize>,                          // number of holes
+    points_set: BitSet,                            // indicates which points have been set
+    facet_points_set: Vec<BitSet>,                 // indicates which points of each facet have been set
+    facet_holes_set: Vec<BitSet>,                  // indicates which holes of each facet have been set
+    regions_set: BitSet,                           // indicates which regions have been set
+    holes_set: BitSet,                             // indicates which holes have been set
+    plc_validation: bool,                          // validates the PLC before generating the mesh
 }
 
 impl Drop for Tetgen {
@@ -165,31 +201,103 @@ impl Drop for Tetgen {
 
 impl Tetgen {
     /// Allocates a new instance
+    ///
+    /// # Input
+    ///
+    /// * `npoint` -- is the number of points
+    /// * `facet_npoint` -- is the number of points on each facet (each facet is a single polygon)
+    /// * `nregion` -- is the number of regions
+    /// * `nhole` -- is the number of holes
     pub fn new(
         npoint: usize,
         facet_npoint: Option<Vec<usize>>,
         nregion: Option<usize>,
         nhole: Option<usize>,
-    ) -> Result<Self, StrError> {
+    ) -> Result<Self, Error> {
+        let facet_polygon_npoint = facet_npoint.map(|facets| facets.iter().map(|n| vec![*n]).collect());
+        Tetgen::new_with_facet_polygons(npoint, facet_polygon_npoint, None, nregion, nhole)
+    }
+
+    /// Allocates a new instance with facets made of several polygons and with holes
+    ///
+    /// Each facet is a planar region defined by one or more polygons. For example, a wall with
+    /// a window is defined by two polygons (the outer boundary and the window) and one hole
+    /// (a point inside the window).
+    ///
+    /// # Input
+    ///
+    /// * `npoint` -- is the number of points
+    /// * `facet_polygon_npoint` -- is the number of points on each polygon of each facet
+    /// * `facet_nhole` -- is the number of holes on each facet (the number of facets must match)
+    /// * `nregion` -- is the number of regions
+    /// * `nhole` -- is the number of holes
+    pub fn new_with_facet_polygons(
+        npoint: usize,
+        facet_polygon_npoint: Option<Vec<Vec<usize>>>,
+        facet_nhole: Option<Vec<usize>>,
+        nregion: Option<usize>,
+        nhole: Option<usize>,
+    ) -> Result<Self, Error> {
         if npoint < 4 {
-            return Err("npoint must be ≥ 4");
+            return Err(Error::TooFew {
+                item: Item::Point,
+                count: npoint,
+                min: 4,
+            });
         }
         let npoint_i32: i32 = to_i32(npoint);
         let mut nfacet_i32: i32 = 0;
-        let mut total_facet_npoint = 0;
-        let mut facet_npoint_i32: Vec<i32> = Vec::new();
-        if let Some(facets) = &facet_npoint {
+        let mut facet_points_set: Vec<BitSet> = Vec::new();
+        let mut facet_holes_set: Vec<BitSet> = Vec::new();
+        let mut facet_npolygon_i32: Vec<i32> = Vec::new();
+        let mut facet_polygon_npoint_i32: Vec<i32> = Vec::new();
+        let mut facet_nhole_i32: Vec<i32> = Vec::new();
+        if let Some(facets) = &facet_polygon_npoint {
             nfacet_i32 = to_i32(facets.len());
             if nfacet_i32 < 4 {
-                return Err("nfacet must be ≥ 4");
+                return Err(Error::TooFew {
+                    item: Item::Facet,
+                    count: facets.len(),
+                    min: 4,
+                });
+            }
+            for polygons in facets {
+                if polygons.is_empty() {
+                    return Err(Error::TooFew {
+                        item: Item::FacetPolygon,
+                        count: 0,
+                        min: 1,
+                    });
+                }
+                for npoint in polygons {
+                    if *npoint < 3 {
+                        return Err(Error::TooFew {
+                            item: Item::FacetPoint,
+                            count: *npoint,
+                            min: 3,
+                        });
+                    }
+                    facet_polygon_npoint_i32.push(to_i32(*npoint));
+                }
+                facet_npolygon_i32.push(to_i32(polygons.len()));
+                facet_points_set.push(BitSet::new(polygons.iter().sum()));
             }
-            for npoint in facets {
-                if *npoint < 3 {
-                    return Err("facet npoint must be ≥ 3");
+            match &facet_nhole {
+                Some(holes) => {
+                    if holes.len() != facets.len() {
+                        return Err(Error::InvalidInput(
+                            "facet_nhole must have the same length as facet_polygon_npoint",
+                        ));
+                    }
+                    for nhole in holes {
+                        facet_nhole_i32.push(to_i32(*nhole));
+                        facet_holes_set.push(BitSet::new(*nhole));
+                    }
                 }
-                total_facet_npoint += npoint;
-                facet_npoint_i32.push(to_i32(*npoint));
+                None => facet_nhole_i32.resize(facets.len(), 0),
             }
+        } else if facet_nhole.is_some() {
+            return Err(Error::InvalidInput("facet_nhole requires facet_polygon_npoint"));
         }
         let nregion_i32: i32 = match nregion {
             Some(v) => to_i32(v),
@@ -203,99 +311,286 @@ impl Tetgen {
             let ext_tetgen = new_tetgen(
                 npoint_i32,
                 nfacet_i32,
-                facet_npoint_i32.as_ptr(),
+                facet_npolygon_i32.as_ptr(),
+                facet_polygon_npoint_i32.as_ptr(),
+                facet_nhole_i32.as_ptr(),
                 nregion_i32,
                 nhole_i32,
             );
             if ext_tetgen.is_null() {
-                return Err("INTERNAL ERROR: cannot allocate ExtTetgen");
+                return Err(Error::AllocationFailed);
             }
             Ok(Tetgen {
                 ext_tetgen,
                 npoint,
-                facet_npoint,
-                total_facet_npoint,
-                facet_point_set_count: 0,
+                facet_polygon_npoint,
+                facet_nhole,
                 nregion,
                 nhole,
-                all_points_set: false,
-                all_facets_set: false,
-                all_regions_set: false,
-                all_holes_set: false,
+                points_set: BitSet::new(npoint),
+                facet_points_set,
+                facet_holes_set,
+                regions_set: BitSet::new(nregion.unwrap_or(0)),
+                holes_set: BitSet::new(nhole.unwrap_or(0)),
+                plc_validation: false,
             })
         }
     }
 
+    /// Allocates a new instance and sets all points, facets, regions and holes at once
+    ///
+    /// All input is validated before the c-code is called; thus, the returned instance is
+    /// ready for `generate_delaunay` or `generate_mesh`. Facet markers may still be set
+    /// afterwards with [Tetgen::set_facet_marker].
+    ///
+    /// # Input
+    ///
+    /// * `points` -- are the point coordinates (npoint ≥ 4)
+    /// * `facets` -- are the IDs of the points on each facet; each facet is a single polygon (empty or nfacet ≥ 4)
+    /// * `regions` -- are the `(x, y, z, attribute, max_volume)` data of each region (see [Tetgen::set_region])
+    /// * `holes` -- are the coordinates of a point inside each hole
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Tetgen};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut tetgen = Tetgen::from_input(
+    ///         &[[0.0, 1.0, 0.0], [0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 1.0]],
+    ///         &vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]],
+    ///         &[(0.1, 0.9, 0.1, 1, None)],
+    ///         &[],
+    ///     )?;
+    ///     tetgen.generate_mesh(false, false, Some(0.01), None)?;
+    ///     assert_eq!(tetgen.tet_attribute(0), 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_input(
+        points: &[[f64; 3]],
+        facets: &[Vec<usize>],
+        regions: &[(f64, f64, f64, usize, Option<f64>)],
+        holes: &[[f64; 3]],
+    ) -> Result<Self, Error> {
+        let npoint = points.len();
+        if points.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(Error::InvalidInput("the coordinates of all points must be finite"));
+        }
+        for facet in facets {
+            if let Some(id) = facet.iter().find(|id| **id >= npoint) {
+                return Err(Error::PointIdOutOfBounds {
+                    item: Item::Facet,
+                    id: *id,
+                    npoint,
+                });
+            }
+        }
+        if regions
+            .iter()
+            .any(|(x, y, z, _, _)| !x.is_finite() || !y.is_finite() || !z.is_finite())
+        {
+            return Err(Error::InvalidInput("the coordinates of all regions must be finite"));
+        }
+        if regions
+            .iter()
+            .any(|(_, _, _, _, max_volume)| matches!(max_volume, Some(v) if v.is_nan() || *v <= 0.0))
+        {
+            return Err(Error::InvalidInput("the max_volume of all regions must be positive"));
+        }
+        if holes.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(Error::InvalidInput("the coordinates of all holes must be finite"));
+        }
+        let facet_npoint = if facets.is_empty() {
+            None
+        } else {
+            Some(facets.iter().map(|facet| facet.len()).collect())
+        };
+        let count = |n: usize| if n > 0 { Some(n) } else { None };
+        let mut tetgen = Tetgen::new(npoint, facet_npoint, count(regions.len()), count(holes.len()))?;
+        for (index, x) in points.iter().enumerate() {
+            tetgen.set_point(index, x[0], x[1], x[2])?;
+        }
+        for (index, facet) in facets.iter().enumerate() {
+            for (m, p) in facet.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        for (index, (x, y, z, attribute, max_volume)) in regions.iter().enumerate() {
+            tetgen.set_region(index, *x, *y, *z, *attribute, *max_volume)?;
+        }
+        for (index, x) in holes.iter().enumerate() {
+            tetgen.set_hole(index, x[0], x[1], x[2])?;
+        }
+        Ok(tetgen)
+    }
+
     /// Sets the point coordinates
-    pub fn set_point(&mut self, index: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, StrError> {
+    pub fn set_point(&mut self, index: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, Error> {
         unsafe {
             let status = tet_set_point(self.ext_tetgen, to_i32(index), x, y, z);
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_POINT_INDEX {
-                    return Err("index of point is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Point,
+                        index,
+                        limit: self.npoint,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == self.npoint - 1 {
-            self.all_points_set = true;
-        } else {
-            self.all_points_set = false;
-        }
+        self.points_set.insert(index);
         Ok(self)
     }
 
     /// Sets the facet's point IDs
     ///
+    /// This function sets the points of the first (or single) polygon of the facet.
+    ///
     /// # Input
     ///
     /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new`)
     /// * `m` -- is the local index of the point on the facet and goes from 0 to `facet_npoint`
     /// * `p` -- is the ID (index) of the point on the facet
-    pub fn set_facet_point(&mut self, index: usize, m: usize, p: usize) -> Result<&mut Self, StrError> {
-        match &self.facet_npoint {
-            Some(n) => n,
-            None => return Err("cannot set facet point because facet_npoint is None"),
+    pub fn set_facet_point(&mut self, index: usize, m: usize, p: usize) -> Result<&mut Self, Error> {
+        self.set_facet_polygon_point(index, 0, m, p)
+    }
+
+    /// Sets the point IDs of a polygon of a facet
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new_with_facet_polygons`)
+    /// * `polygon` -- is the index of the polygon on the facet and goes from 0 to the facet's `npolygon`
+    /// * `m` -- is the local index of the point on the polygon and goes from 0 to the polygon's `npoint`
+    /// * `p` -- is the ID (index) of the point on the polygon
+    pub fn set_facet_polygon_point(
+        &mut self,
+        index: usize,
+        polygon: usize,
+        m: usize,
+        p: usize,
+    ) -> Result<&mut Self, Error> {
+        let facets = match &self.facet_polygon_npoint {
+            Some(facets) => facets,
+            None => return Err(Error::NotAllocated { item: Item::Facet }),
         };
+        let nfacet = facets.len();
         unsafe {
-            let status = tet_set_facet_point(self.ext_tetgen, to_i32(index), to_i32(m), to_i32(p));
+            let status = tet_set_facet_point(self.ext_tetgen, to_i32(index), to_i32(polygon), to_i32(m), to_i32(p));
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_FACET_LIST {
-                    return Err("INTERNAL ERROR: found NULL facet list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_FACET_INDEX {
-                    return Err("index of facet is out of bounds");
-                }
-                if status == constants::TRITET_ERROR_NULL_FACET_POLYGON_LIST {
-                    return Err("INTERNAL ERROR: found NULL facet polygon list");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Facet,
+                        index,
+                        limit: nfacet,
+                    });
                 }
-                if status == constants::TRITET_ERROR_INVALID_FACET_NUM_POLYGON {
-                    return Err("INTERNAL ERROR: found invalid facet number of polygon");
+                if status == constants::TRITET_ERROR_INVALID_FACET_POLYGON_INDEX {
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::FacetPolygon,
+                        index: polygon,
+                        limit: facets[index].len(),
+                    });
                 }
                 if status == constants::TRITET_ERROR_INVALID_FACET_POINT_INDEX {
-                    return Err("index of facet point is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::FacetPoint,
+                        index: m,
+                        limit: facets[index][polygon],
+                    });
                 }
                 if status == constants::TRITET_ERROR_INVALID_FACET_POINT_ID {
-                    return Err("id of facet point is out of bounds");
+                    return Err(Error::PointIdOutOfBounds {
+                        item: Item::Facet,
+                        id: p,
+                        npoint: self.npoint,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == 0 && m == 0 {
-            self.facet_point_set_count = 0;
+        let offset: usize = facets[index][..polygon].iter().sum();
+        self.facet_points_set[index].insert(offset + m);
+        Ok(self)
+    }
+
+    /// Sets a hole within a facet
+    ///
+    /// The hole point must be on the facet's plane and inside the region to be removed
+    /// from the facet (e.g., inside the polygon representing a window on a wall).
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new_with_facet_polygons`)
+    /// * `hole` -- is the index of the hole on the facet and goes from 0 to the facet's `nhole`
+    /// * `x` -- is the x-coordinate of the hole
+    /// * `y` -- is the y-coordinate of the hole
+    /// * `z` -- is the z-coordinate of the hole
+    pub fn set_facet_hole(&mut self, index: usize, hole: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, Error> {
+        let holes = match &self.facet_nhole {
+            Some(holes) => holes,
+            None => return Err(Error::NotAllocated { item: Item::FacetHole }),
+        };
+        let nfacet = holes.len();
+        unsafe {
+            let status = tet_set_facet_hole(self.ext_tetgen, to_i32(index), to_i32(hole), x, y, z);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_INVALID_FACET_INDEX {
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Facet,
+                        index,
+                        limit: nfacet,
+                    });
+                }
+                if status == constants::TRITET_ERROR_NULL_FACET_HOLE_LIST {
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::FacetHole,
+                        index: hole,
+                        limit: 0,
+                    });
+                }
+                if status == constants::TRITET_ERROR_INVALID_FACET_HOLE_INDEX {
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::FacetHole,
+                        index: hole,
+                        limit: holes[index],
+                    });
+                }
+                return Err(Error::Internal { status });
+            }
         }
-        self.facet_point_set_count += 1;
-        if self.facet_point_set_count == self.total_facet_npoint {
-            self.all_facets_set = true;
+        self.facet_holes_set[index].insert(hole);
+        Ok(self)
+    }
+
+    /// Sets the boundary marker of a facet
+    ///
+    /// The markers are propagated to the output faces on the boundary (see [Tetgen::face_marker]).
+    /// Facets without a marker have a zero marker.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the facet and goes from 0 to `nfacet` (passed down to `new`)
+    /// * `marker` -- is the marker (tag) of the facet
+    pub fn set_facet_marker(&mut self, index: usize, marker: i32) -> Result<&mut Self, Error> {
+        let nfacet = match &self.facet_polygon_npoint {
+            Some(facets) => facets.len(),
+            None => return Err(Error::NotAllocated { item: Item::Facet }),
+        };
+        unsafe {
+            let status = tet_set_facet_marker(self.ext_tetgen, to_i32(index), marker);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_INVALID_FACET_INDEX {
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Facet,
+                        index,
+                        limit: nfacet,
+                    });
+                }
+                return Err(Error::Internal { status });
+            }
         }
         Ok(self)
     }
@@ -318,10 +613,10 @@ impl Tetgen {
         z: f64,
         attribute: usize,
         max_volume: Option<f64>,
-    ) -> Result<&mut Self, StrError> {
+    ) -> Result<&mut Self, Error> {
         let nregion = match self.nregion {
             Some(n) => n,
-            None => return Err("cannot set region because the number of regions is None"),
+            None => return Err(Error::NotAllocated { item: Item::Region }),
         };
         let volume_constraint = match max_volume {
             Some(v) => v,
@@ -338,23 +633,17 @@ impl Tetgen {
                 volume_constraint,
             );
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_REGION_LIST {
-                    return Err("INTERNAL ERROR: found NULL region list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_REGION_INDEX {
-                    return Err("index of region is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Region,
+                        index,
+                        limit: nregion,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == nregion - 1 {
-            self.all_regions_set = true;
-        } else {
-            self.all_regions_set = false;
-        }
+        self.regions_set.insert(index);
         Ok(self)
     }
 
@@ -366,54 +655,77 @@ impl Tetgen {
     /// * `x` -- is the x-coordinate of the hole
     /// * `y` -- is the y-coordinate of the hole
     /// * `z` -- is the z-coordinate of the hole
-    pub fn set_hole(&mut self, index: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, StrError> {
+    pub fn set_hole(&mut self, index: usize, x: f64, y: f64, z: f64) -> Result<&mut Self, Error> {
         let nhole = match self.nhole {
             Some(n) => n,
-            None => return Err("cannot set hole because the number of holes is None"),
+            None => return Err(Error::NotAllocated { item: Item::Hole }),
         };
         unsafe {
             let status = tet_set_hole(self.ext_tetgen, to_i32(index), x, y, z);
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_HOLE_LIST {
-                    return Err("INTERNAL ERROR: found NULL hole list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_HOLE_INDEX {
-                    return Err("index of hole is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Hole,
+                        index,
+                        limit: nhole,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == nhole - 1 {
-            self.all_holes_set = true;
-        } else {
-            self.all_holes_set = false;
-        }
+        self.holes_set.insert(index);
         Ok(self)
     }
 
-    /// Generates a Delaunay triangulation
+    /// Enables or disables the validation of the PLC by `generate_mesh`
+    ///
+    /// When enabled, `generate_mesh` calls [Tetgen::validate_plc] before calling Tetgen
+    /// and returns [Error::InvalidPlc] if any problem is found. The validation is disabled by default.
+    pub fn set_plc_validation(&mut self, enabled: bool) -> &mut Self {
+        self.plc_validation = enabled;
+        self
+    }
+
+    /// Finds the pairs of facets that intersect each other (Tetgen's `-d` switch)
+    ///
+    /// Returns the sorted pairs of facet indices `[i, j]` with `i ≤ j`; `i == j` indicates a
+    /// self-intersecting facet. Facets that share a vertex or an edge do not intersect, but
+    /// overlapping (duplicated) facets do.
+    ///
+    /// **Note:** No mesh is generated and the output of a previous generation is kept.
     ///
     /// # Input
     ///
     /// * `verbose` -- Prints Tetgen's messages to the console
-    pub fn generate_delaunay(&self, verbose: bool) -> Result<(), StrError> {
-        if !self.all_points_set {
-            return Err("cannot generate Delaunay tetrahedralization because not all points are set");
+    pub fn check_intersections(&self, verbose: bool) -> Result<Vec<[usize; 2]>, Error> {
+        if self.facet_polygon_npoint.is_none() {
+            return Err(Error::NotAllocated { item: Item::Facet });
         }
+        self.check_facets_set(Stage::TetgenMesh)?;
+        let mut pairs: Vec<[usize; 2]> = unsafe {
+            let status = tet_run_intersection_check(self.ext_tetgen, if verbose { 1 } else { 0 });
+            check_status(status, Stage::TetgenMesh)?;
+            let n = tet_get_nintersection(self.ext_tetgen) as usize;
+            to_slice(tet_get_intersection_list(self.ext_tetgen), 2 * n)
+                .chunks_exact(2)
+                .map(|ij| [ij[0] as usize, ij[1] as usize])
+                .collect()
+        };
+        pairs.sort();
+        pairs.dedup();
+        Ok(pairs)
+    }
+
+    /// Generates a Delaunay triangulation
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Tetgen's messages to the console
+    pub fn generate_delaunay(&mut self, verbose: bool) -> Result<(), Error> {
+        self.points_set.check(Item::Point, Stage::TetgenDelaunay)?;
         unsafe {
             let status = tet_run_delaunay(self.ext_tetgen, if verbose { 1 } else { 0 });
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
-                return Err("INTERNAL ERROR: some error occurred");
-            }
+            check_status(status, Stage::TetgenDelaunay)?;
         }
         Ok(())
     }
@@ -427,17 +739,22 @@ impl Tetgen {
     /// * `global_max_volume` -- The maximum volume constraint for all generated tetrahedra
     /// * `global_min_angle` -- The minimum angle constraint is given in degrees (the default minimum angle is TODO degrees)
     pub fn generate_mesh(
-        &self,
+        &mut self,
         verbose: bool,
         o2: bool,
         global_volume_area: Option<f64>,
         global_min_angle: Option<f64>,
-    ) -> Result<(), StrError> {
-        if !self.all_points_set {
-            return Err("cannot generate mesh of tetrahedra because not all points are set");
-        }
-        if !self.all_facets_set {
-            return Err("cannot generate mesh of tetrahedra because not all facets are set");
+    ) -> Result<(), Error> {
+        self.points_set.check(Item::Point, Stage::TetgenMesh)?;
+        check_each(&self.facet_points_set, Item::Facet, Stage::TetgenMesh)?;
+        check_each(&self.facet_holes_set, Item::FacetHole, Stage::TetgenMesh)?;
+        self.regions_set.check(Item::Region, Stage::TetgenMesh)?;
+        self.holes_set.check(Item::Hole, Stage::TetgenMesh)?;
+        if self.plc_validation {
+            let report = self.validate_plc()?;
+            if !report.is_valid() {
+                return Err(Error::InvalidPlc(Box::new(report)));
+            }
         }
         let max_volume = match global_volume_area {
             Some(v) => v,
@@ -455,21 +772,96 @@ impl Tetgen {
                 max_volume,
                 min_angle,
             );
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
-                if status == constants::TRITET_ERROR_NULL_FACET_LIST {
-                    return Err("INTERNAL ERROR: list of facets must be defined first");
-                }
-                if status == constants::TRITET_ERROR_STRING_CONCAT {
-                    return Err("INTERNAL ERROR: cannot write string with commands for Tetgen");
-                }
-                return Err("INTERNAL ERROR: some error occurred");
-            }
+            check_status(status, Stage::TetgenMesh)?;
+        }
+        Ok(())
+    }
+
+    /// Refines the current mesh with a maximum volume constraint for each tetrahedron
+    ///
+    /// The current mesh (e.g., generated by [Tetgen::generate_mesh]) is replaced by the refined
+    /// mesh. The tetrahedra are subdivided until their volumes satisfy the constraints; the other
+    /// tetrahedra are only modified to keep the quality of the mesh. The boundary faces, the face
+    /// markers, and the attributes (regions) of the tetrahedra are preserved.
+    ///
+    /// This function may be called repeatedly, e.g., in an error-estimate and refinement loop.
+    /// The input (PLC) is not modified; thus, [Tetgen::generate_mesh] will start from scratch.
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Tetgen's messages to the console
+    /// * `o2` -- Generates the middle nodes of the refined mesh; e.g., nnode = 10
+    /// * `max_volumes` -- The maximum volume of each tetrahedron of the current mesh (`len = ntet`).
+    ///   A zero or negative value means no constraint.
+    /// * `global_min_angle` -- The quality constraint (see [Tetgen::generate_mesh])
+    ///
+    /// # Warning
+    ///
+    /// The current mesh must have linear tetrahedra (nnode = 4); thus, only the last refinement
+    /// of a sequence may generate the middle nodes (`o2`).
+    ///
+    /// Tetgen enforces the constraints only approximately (e.g., it enlarges the constraint of each
+    /// new tetrahedron by 20%). Thus, the refinement is repeated until every tetrahedron satisfies
+    /// the maximum volume of the tetrahedron it came from. If this cannot be achieved after 20
+    /// passes of Tetgen, [Error::GeneratorFailed] is returned with `status = 44`
+    /// (`TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED` in `c_code/constants.h`). The current mesh
+    /// is kept if this function fails for any reason (e.g., also if Tetgen runs out of memory).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Tetgen};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut tetgen = Tetgen::from_input(
+    ///         &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    ///         &vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]],
+    ///         &[],
+    ///         &[],
+    ///     )?;
+    ///     tetgen.generate_mesh(false, false, None, None)?;
+    ///     assert_eq!(tetgen.ntet(), 1);
+    ///
+    ///     tetgen.refine_mesh(false, false, &[0.001], None)?;
+    ///     assert!(tetgen.ntet() > 50);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn refine_mesh(
+        &mut self,
+        verbose: bool,
+        o2: bool,
+        max_volumes: &[f64],
+        global_min_angle: Option<f64>,
+    ) -> Result<(), Error> {
+        if self.ntet() == 0 {
+            return Err(Error::InvalidInput(
+                "there is no mesh to refine (call generate_mesh first)",
+            ));
+        }
+        if self.nnode() != 4 {
+            return Err(Error::InvalidInput(
+                "the mesh to refine must have linear tetrahedra (nnode = 4)",
+            ));
+        }
+        if max_volumes.len() != self.ntet() {
+            return Err(Error::InvalidInput(
+                "the number of max volumes must equal the number of tetrahedra",
+            ));
+        }
+        if max_volumes.iter().any(|v| v.is_nan()) {
+            return Err(Error::InvalidInput("the max volumes must not be NaN"));
+        }
+        let min_angle = global_min_angle.unwrap_or(0.0);
+        unsafe {
+            let status = tet_run_refine(
+                self.ext_tetgen,
+                if verbose { 1 } else { 0 },
+                if o2 { 1 } else { 0 },
+                max_volumes.as_ptr(),
+                min_angle,
+            );
+            check_status(status, Stage::TetgenRefine)?;
         }
         Ok(())
     }
@@ -498,8 +890,10 @@ impl Tetgen {
     ///
     /// # Warning
     ///
-    /// This function will return 0.0 if either `index` or `dim` are out of range.
+    /// This function panics in debug builds if `index` or `dim` is out of range; release builds return 0.0 instead.
     pub fn point(&self, index: usize, dim: usize) -> f64 {
+        debug_assert!(index < self.npoint());
+        debug_assert!(dim < 3);
         unsafe { tet_get_point(self.ext_tetgen, to_i32(index), to_i32(dim)) }
     }
 
@@ -542,11 +936,13 @@ impl Tetgen {
     ///
     /// # Warning
     ///
-    /// This function will return 0 if either `index` or `m` are out of range.
+    /// This function panics in debug builds if `index` or `m` is out of range; release builds return 0 instead.
     pub fn tet_node(&self, index: usize, m: usize) -> usize {
+        debug_assert!(index < self.ntet());
+        debug_assert!(m < self.nnode());
         unsafe {
-            let corner = constants::TRITET_TO_TETGEN[m];
-            tet_get_tetrahedron_corner(self.ext_tetgen, to_i32(index), to_i32(corner)) as usize
+            // the nodes are numbered equally in tritet and Tetgen
+            tet_get_tetrahedron_corner(self.ext_tetgen, to_i32(index), to_i32(m)) as usize
         }
     }
 
@@ -554,11 +950,241 @@ impl Tetgen {
     ///
     /// # Warning
     ///
-    /// This function will return 0 if either `index` is out of range.
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
     pub fn tet_attribute(&self, index: usize) -> usize {
+        debug_assert!(index < self.ntet());
         unsafe { tet_get_tetrahedron_attribute(self.ext_tetgen, to_i32(index)) as usize }
     }
 
+    /// Returns the index of the tetrahedron sharing a face with a tetrahedron
+    ///
+    /// The face `f` is the face opposite to the node `f`, as follows:
+    ///
+    /// ```text
+    /// face   nodes (corners)   middle nodes
+    ///  0        1 2 3             5 8 9
+    ///  1        0 2 3             6 7 9
+    ///  2        0 1 3             4 7 8
+    ///  3        0 1 2             4 5 6
+    /// ```
+    ///
+    /// See [Tetgen::tet_node] for the local numbering of nodes.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the tetrahedron and goes from 0 to `ntet`
+    /// * `face` -- is the local index of the face and goes from 0 to 4
+    ///
+    /// # Output
+    ///
+    /// Returns the index of the neighbor or None if the face is on the boundary.
+    ///
+    /// # Warning
+    ///
+    /// This function will return None if either `index` or `face` are out of range.
+    pub fn tet_neighbor(&self, index: usize, face: usize) -> Option<usize> {
+        unsafe {
+            // the first four corners are numbered equally in tritet and Tetgen and
+            // Tetgen lists the neighbor opposite to each corner
+            let id = tet_get_tetrahedron_neighbor(self.ext_tetgen, to_i32(index), to_i32(face));
+            if id < 0 {
+                None
+            } else {
+                Some(id as usize)
+            }
+        }
+    }
+
+    /// Returns the number of triangular faces on the boundary
+    ///
+    /// After [Tetgen::generate_mesh], the faces are the (possibly subdivided) input facets.
+    /// After [Tetgen::generate_delaunay], the faces are the faces of the convex hull.
+    pub fn nface(&self) -> usize {
+        unsafe { tet_get_nface(self.ext_tetgen) as usize }
+    }
+
+    /// Returns the ID of a node of a triangular face on the boundary
+    ///
+    /// ```text
+    ///           NODES
+    ///             2
+    ///            / \
+    ///           /   \
+    ///          5     4
+    ///         /       \
+    ///        /         \
+    ///       0-----3-----1
+    /// ```
+    ///
+    /// The middle nodes (3, 4, 5) are only available if `o2` was passed to [Tetgen::generate_mesh].
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the face and goes from 0 to `nface`
+    /// * `m` -- is the local index of the node and goes from 0 to 3 (or 6 if `o2`)
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` or `m` is out of range; release builds return 0 instead.
+    pub fn face_node(&self, index: usize, m: usize) -> usize {
+        debug_assert!(index < self.nface());
+        debug_assert!(m < if self.nnode() == 10 { 6 } else { 3 });
+        unsafe { tet_get_face_corner(self.ext_tetgen, to_i32(index), to_i32(m)) as usize }
+    }
+
+    /// Returns the marker of a triangular face on the boundary
+    ///
+    /// The marker is the one given to the input facet containing the face (see [Tetgen::set_facet_marker]).
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the face and goes from 0 to `nface`
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
+    pub fn face_marker(&self, index: usize) -> i32 {
+        debug_assert!(index < self.nface());
+        unsafe { tet_get_face_marker(self.ext_tetgen, to_i32(index)) }
+    }
+
+    /// Returns the x-y-z coordinates of all points (zero-copy)
+    ///
+    /// The coordinates are stored as `[x0, y0, z0, x1, y1, z1, ...]`; thus, the length of the slice is `3 * npoint`.
+    pub fn points(&self) -> &[f64] {
+        unsafe { to_slice(tet_get_point_list(self.ext_tetgen), 3 * self.npoint()) }
+    }
+
+    /// Returns the IDs of the nodes of all tetrahedra
+    ///
+    /// The nodes of tetrahedron `i` are at `[i * nnode, (i + 1) * nnode)` and follow the
+    /// local numbering of [Tetgen::tet_node]. This is an owned vector because Tetgen
+    /// stores the connectivity as `i32`.
+    pub fn tets(&self) -> Vec<usize> {
+        self.tet_list().iter().map(|p| *p as usize).collect()
+    }
+
+    /// Returns the attribute IDs of all tetrahedra
+    ///
+    /// See [Tetgen::tet_attribute]. The attributes are zero if no region has been set.
+    pub fn tet_attributes(&self) -> Vec<usize> {
+        let ntet = self.ntet();
+        let nattribute = unsafe { tet_get_ntetrahedron_attribute(self.ext_tetgen) as usize };
+        if nattribute == 0 {
+            return vec![0; ntet];
+        }
+        let list = unsafe { to_slice(tet_get_tetrahedron_attribute_list(self.ext_tetgen), ntet * nattribute) };
+        list.chunks_exact(nattribute).map(|values| values[0] as usize).collect()
+    }
+
+    /// Returns the neighbors of all tetrahedra
+    ///
+    /// See [Tetgen::tet_neighbor]. The neighbor across face `f` is at index `f`.
+    pub fn tet_neighbors(&self) -> Vec<[Option<usize>; 4]> {
+        let ntet = self.ntet();
+        let list = unsafe { to_slice(tet_get_neighbor_list(self.ext_tetgen), 4 * ntet) };
+        if list.is_empty() {
+            return vec![[None; 4]; ntet];
+        }
+        list.chunks_exact(4)
+            .map(|ids| {
+                let mut neighbors = [None; 4];
+                for (neighbor, id) in neighbors.iter_mut().zip(ids) {
+                    if *id >= 0 {
+                        *neighbor = Some(*id as usize);
+                    }
+                }
+                neighbors
+            })
+            .collect()
+    }
+
+    /// Returns the IDs of the nodes of all triangular faces on the boundary
+    ///
+    /// The nodes of face `i` are at `[i * n, (i + 1) * n)` and follow the local numbering
+    /// of [Tetgen::face_node], where `n` is 6 if `o2` was passed to [Tetgen::generate_mesh]
+    /// or 3 otherwise.
+    pub fn faces(&self) -> Vec<usize> {
+        let nface = self.nface();
+        let corners = unsafe { to_slice(tet_get_face_list(self.ext_tetgen), 3 * nface) };
+        let middles = unsafe { to_slice(tet_get_face_middle_list(self.ext_tetgen), 3 * nface) };
+        if middles.is_empty() {
+            return corners.iter().map(|p| *p as usize).collect();
+        }
+        corners
+            .chunks_exact(3)
+            .zip(middles.chunks_exact(3))
+            .flat_map(|(a, b)| a.iter().chain(b.iter()).map(|p| *p as usize))
+            .collect()
+    }
+
+    /// Returns the markers of all triangular faces on the boundary (zero-copy)
+    ///
+    /// See [Tetgen::face_marker]. The slice is empty if the markers are not available.
+    pub fn face_markers(&self) -> &[i32] {
+        unsafe { to_slice(tet_get_face_marker_list(self.ext_tetgen), self.nface()) }
+    }
+
+    /// Returns an iterator over the tetrahedra yielding the x-y-z coordinates of their nodes
+    ///
+    /// The nodes follow the local numbering of [Tetgen::tet_node].
+    pub fn tet_points(&self) -> impl Iterator<Item = Vec<[f64; 3]>> + '_ {
+        let points = self.points();
+        self.tet_list()
+            .chunks_exact(usize::max(self.nnode(), 1))
+            .map(move |nodes| {
+                nodes
+                    .iter()
+                    .map(|p| {
+                        let p = *p as usize;
+                        [points[3 * p], points[3 * p + 1], points[3 * p + 2]]
+                    })
+                    .collect()
+            })
+    }
+
+    /// Returns the connectivity as stored by Tetgen (zero-copy)
+    fn tet_list(&self) -> &[i32] {
+        unsafe { to_slice(tet_get_tetrahedron_list(self.ext_tetgen), self.ntet() * self.nnode()) }
+    }
+
+    /// Returns an error if not all points and facets have been set
+    pub(crate) fn check_facets_set(&self, stage: Stage) -> Result<(), Error> {
+        self.points_set.check(Item::Point, stage)?;
+        check_each(&self.facet_points_set, Item::Facet, stage)
+    }
+
+    /// Returns the input points as passed down to `set_point` (x0, y0, z0, x1, y1, z1, ...)
+    pub(crate) fn input_points(&self) -> &[f64] {
+        unsafe { to_slice(tet_get_input_point_list(self.ext_tetgen), 3 * self.npoint) }
+    }
+
+    /// Returns the IDs of the input points on each polygon of each facet (empty if there are no facets)
+    pub(crate) fn input_facets(&self) -> Vec<Vec<Vec<usize>>> {
+        let facets = match &self.facet_polygon_npoint {
+            Some(facets) => facets,
+            None => return Vec::new(),
+        };
+        facets
+            .iter()
+            .enumerate()
+            .map(|(index, polygons)| {
+                polygons
+                    .iter()
+                    .enumerate()
+                    .map(|(polygon, npoint)| {
+                        (0..*npoint)
+                            .map(|m| unsafe {
+                                tet_get_input_facet_point(self.ext_tetgen, to_i32(index), to_i32(polygon), to_i32(m))
+                                    as usize
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
     #[cfg(feature = "plotpy")]
     /// Draws wireframe representing the edges of tetrahedra
     pub fn draw_wireframe(
@@ -572,125 +1198,34 @@ impl Tetgen {
         fontsize_triangle_ids: Option<f64>,
         fontsize_attribute_ids: Option<f64>,
     ) {
-        let ntet = self.ntet();
-        if ntet < 1 {
-            return;
-        }
-        let mut canvas = Canvas::new();
-        let mut point_ids = Text::new();
-        let mut tetrahedron_ids = Text::new();
-        let mut attribute_ids = Text::new();
-        if with_point_ids {
-            point_ids
-                .set_color("red")
-                .set_align_horizontal("center")
-                .set_align_vertical("center")
-                .set_bbox(true)
-                .set_bbox_facecolor("white")
-                .set_bbox_alpha(0.8)
-                .set_bbox_style("circle");
-            if let Some(fsz) = fontsize_point_ids {
-                point_ids.set_fontsize(fsz);
-            }
-        }
-        if with_triangle_ids {
-            tetrahedron_ids
-                .set_color("blue")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_triangle_ids {
-                tetrahedron_ids.set_fontsize(fsz);
-            }
-        }
-        if with_attribute_ids {
-            attribute_ids
-                .set_color("black")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_attribute_ids {
-                attribute_ids.set_fontsize(fsz);
-            }
-        }
-        const EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
-        let mut x = vec![0.0; 3];
-        let mut xa = vec![0.0; 3];
-        let mut xb = vec![0.0; 3];
-        let mut xcen = vec![0.0; 3];
-        let mut xatt = vec![0.0; 3];
-        let mut min = vec![f64::MAX; 3];
-        let mut max = vec![f64::MIN; 3];
-        let mut colors: HashMap<usize, &'static str> = HashMap::new();
-        let mut index_color = 0;
-        let clr = constants::DARK_COLORS;
-        for tet in 0..ntet {
-            let attribute = self.tet_attribute(tet);
-            let color = match colors.get(&attribute) {
-                Some(c) => c,
-                None => {
-                    let c = clr[index_color % clr.len()];
-                    colors.insert(attribute, c);
-                    index_color += 1;
-                    c
-                }
-            };
-            canvas.set_edge_color(color);
-            for dim in 0..3 {
-                xcen[dim] = 0.0;
-            }
-            for m in 0..4 {
-                let p = self.tet_node(tet, m);
-                for dim in 0..3 {
-                    x[dim] = self.point(p, dim);
-                    min[dim] = f64::min(min[dim], x[dim]);
-                    max[dim] = f64::max(max[dim], x[dim]);
-                    xcen[dim] += x[dim] / 4.0;
-                }
-            }
-            for (ma, mb) in &EDGES {
-                let a = self.tet_node(tet, *ma);
-                let b = self.tet_node(tet, *mb);
-                for dim in 0..3 {
-                    xa[dim] = self.point(a, dim);
-                    xb[dim] = self.point(b, dim);
-                }
-                canvas.polyline_3d_begin();
-                canvas.polyline_3d_add(xa[0], xa[1], xa[2]);
-                canvas.polyline_3d_add(xb[0], xb[1], xb[2]);
-                canvas.polyline_3d_end();
-            }
-            if with_triangle_ids {
-                tetrahedron_ids.draw_3d(xcen[0], xcen[1], xcen[2], format!("{}", tet).as_str());
-            }
-            if with_attribute_ids {
-                for dim in 0..3 {
-                    x[dim] = self.point(self.tet_node(tet, 0), dim);
-                    xatt[dim] = (x[dim] + xcen[dim]) / 2.0;
-                }
-                attribute_ids.draw_3d(xatt[0], xatt[1], xatt[2], format!("[{}]", attribute).as_str());
-            }
-        }
-        if with_point_ids {
-            for p in 0..self.npoint() {
-                let x = self.point(p, 0);
-                let y = self.point(p, 1);
-                let z = self.point(p, 2);
-                point_ids.draw_3d(x, y, z, format!("{}", p).as_str());
-            }
-        }
-        plot.add(&canvas);
-        if with_triangle_ids {
-            plot.add(&tetrahedron_ids);
-        }
-        if with_point_ids {
-            plot.add(&point_ids);
-        }
-        if with_attribute_ids {
-            plot.add(&attribute_ids);
-        }
-        if set_range {
-            plot.set_range_3d(min[0], max[0], min[1], max[1], min[2], max[2]);
-        }
+        let tets = self.tets();
+        let nnode = usize::max(self.nnode(), 1); // nnode is zero before the generation
+        mesh::draw_wireframe(
+            plot,
+            self.points(),
+            tets.chunks_exact(nnode),
+            &self.tet_attributes(),
+            set_range,
+            [with_point_ids, with_triangle_ids, with_attribute_ids],
+            [fontsize_point_ids, fontsize_triangle_ids, fontsize_attribute_ids],
+        );
+    }
+}
+
+/// Converts the status returned by Tetgen into an error
+fn check_status(status: i32, stage: Stage) -> Result<(), Error> {
+    if status == constants::TRITET_SUCCESS {
+        return Ok(());
     }
+    if status == constants::TRITET_ERROR_TETGEN_FAILED
+        || status == constants::TRITET_ERROR_TETGEN_OUT_OF_MEMORY
+        || status == constants::TRITET_ERROR_TETGEN_INTERNAL_ERROR
+        || status == constants::TRITET_ERROR_TETGEN_INVALID_INPUT
+        || status == constants::TRITET_ERROR_TETGEN_REFINE_NOT_CONVERGED
+    {
+        return Err(Error::GeneratorFailed { stage, status });
+    }
+    Err(Error::Internal { status })
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
@@ -698,127 +1233,510 @@ impl Tetgen {
 #[cfg(test)]
 mod tests {
     use super::Tetgen;
-    use crate::{write_tet_vtu, StrError};
+    use crate::bitset::BitSet;
+    use crate::constants;
+    use crate::test_fixtures::{cube, set_cube, total_volume, CUBE_FACETS, CUBE_POINTS};
+    use crate::{write_tet_vtu, Error, Item, Stage};
+    use std::panic::{self, AssertUnwindSafe};
 
     #[cfg(feature = "plotpy")]
     use plotpy::Plot;
 
+    // checks that the neighbors share the nodes of the faces
+    fn check_neighbors(tetgen: &Tetgen) {
+        for tet in 0..tetgen.ntet() {
+            for face in 0..4 {
+                if let Some(neighbor) = tetgen.tet_neighbor(tet, face) {
+                    let nodes: Vec<_> = (0..4).map(|m| tetgen.tet_node(neighbor, m)).collect();
+                    for m in 0..4 {
+                        if m != face {
+                            assert!(nodes.contains(&tetgen.tet_node(tet, m)));
+                        }
+                    }
+                    assert!(!nodes.contains(&tetgen.tet_node(tet, face)));
+                }
+            }
+        }
+    }
+
     #[test]
     fn new_captures_some_errors() {
-        assert_eq!(Tetgen::new(3, None, None, None).err(), Some("npoint must be ≥ 4"));
+        assert_eq!(
+            Tetgen::new(3, None, None, None).err(),
+            Some(Error::TooFew {
+                item: Item::Point,
+                count: 3,
+                min: 4
+            })
+        );
         assert_eq!(
             Tetgen::new(4, Some(vec![]), None, None).err(),
-            Some("nfacet must be ≥ 4")
+            Some(Error::TooFew {
+                item: Item::Facet,
+                count: 0,
+                min: 4
+            })
         );
         assert_eq!(
             Tetgen::new(4, Some(vec![3, 3, 3, 2]), None, None).err(),
-            Some("facet npoint must be ≥ 3")
+            Some(Error::TooFew {
+                item: Item::FacetPoint,
+                count: 2,
+                min: 3
+            })
+        );
+    }
+
+    #[test]
+    fn new_with_facet_polygons_captures_some_errors() {
+        assert_eq!(
+            Tetgen::new_with_facet_polygons(4, Some(vec![vec![3], vec![3], vec![3], vec![]]), None, None, None).err(),
+            Some(Error::TooFew {
+                item: Item::FacetPolygon,
+                count: 0,
+                min: 1
+            })
+        );
+        assert_eq!(
+            Tetgen::new_with_facet_polygons(4, Some(vec![vec![3], vec![3], vec![3], vec![3, 2]]), None, None, None)
+                .err(),
+            Some(Error::TooFew {
+                item: Item::FacetPoint,
+                count: 2,
+                min: 3
+            })
+        );
+        assert_eq!(
+            Tetgen::new_with_facet_polygons(4, Some(vec![vec![3]; 4]), Some(vec![0, 0, 0]), None, None).err(),
+            Some(Error::InvalidInput(
+                "facet_nhole must have the same length as facet_polygon_npoint"
+            ))
+        );
+        assert_eq!(
+            Tetgen::new_with_facet_polygons(4, None, Some(vec![0, 0, 0, 0]), None, None).err(),
+            Some(Error::InvalidInput("facet_nhole requires facet_polygon_npoint"))
         );
     }
 
     #[test]
-    fn new_works() -> Result<(), StrError> {
+    fn new_works() -> Result<(), Error> {
         let tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
         assert_eq!(tetgen.ext_tetgen.is_null(), false);
         assert_eq!(tetgen.npoint, 4);
-        assert_eq!(tetgen.facet_npoint, Some(vec![3, 3, 3, 3]));
+        assert_eq!(
+            tetgen.facet_polygon_npoint,
+            Some(vec![vec![3], vec![3], vec![3], vec![3]])
+        );
+        assert_eq!(tetgen.facet_nhole, None);
+        assert_eq!(tetgen.nregion, None);
+        assert_eq!(tetgen.nhole, None);
+        assert_eq!(tetgen.points_set, BitSet::new(4));
+        assert_eq!(tetgen.facet_points_set, vec![BitSet::new(3); 4]);
+        assert_eq!(tetgen.facet_holes_set, &[] as &[BitSet]);
+        assert_eq!(tetgen.regions_set, BitSet::new(0));
+        assert_eq!(tetgen.holes_set, BitSet::new(0));
+        Ok(())
+    }
+
+    #[test]
+    fn from_input_captures_some_errors() {
+        let points = &[[0.0, 1.0, 0.0], [0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 1.0]];
+        let facets = vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]];
+        assert_eq!(
+            Tetgen::from_input(&points[..3], &[], &[], &[]).err(),
+            Some(Error::TooFew {
+                item: Item::Point,
+                count: 3,
+                min: 4
+            })
+        );
+        assert_eq!(
+            Tetgen::from_input(
+                &[[0.0, 1.0, 0.0], [0.0, 0.0, f64::NAN], [1.0, 1.0, 0.0], [0.0, 1.0, 1.0]],
+                &[],
+                &[],
+                &[]
+            )
+            .err(),
+            Some(Error::InvalidInput("the coordinates of all points must be finite"))
+        );
+        assert_eq!(
+            Tetgen::from_input(points, &facets[..3], &[], &[]).err(),
+            Some(Error::TooFew {
+                item: Item::Facet,
+                count: 3,
+                min: 4
+            })
+        );
+        assert_eq!(
+            Tetgen::from_input(
+                points,
+                &[vec![0, 2, 1], vec![0, 1, 3], vec![0, 3], vec![1, 2, 3]],
+                &[],
+                &[]
+            )
+            .err(),
+            Some(Error::TooFew {
+                item: Item::FacetPoint,
+                count: 2,
+                min: 3
+            })
+        );
+        assert_eq!(
+            Tetgen::from_input(
+                points,
+                &[vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 4], vec![1, 2, 3]],
+                &[],
+                &[]
+            )
+            .err(),
+            Some(Error::PointIdOutOfBounds {
+                item: Item::Facet,
+                id: 4,
+                npoint: 4
+            })
+        );
+        assert_eq!(
+            Tetgen::from_input(points, &facets, &[(0.1, f64::NAN, 0.1, 1, None)], &[]).err(),
+            Some(Error::InvalidInput("the coordinates of all regions must be finite"))
+        );
+        assert_eq!(
+            Tetgen::from_input(points, &facets, &[(0.1, 0.9, 0.1, 1, Some(-1.0))], &[]).err(),
+            Some(Error::InvalidInput("the max_volume of all regions must be positive"))
+        );
+        assert_eq!(
+            Tetgen::from_input(points, &facets, &[], &[[0.1, 0.9, f64::INFINITY]]).err(),
+            Some(Error::InvalidInput("the coordinates of all holes must be finite"))
+        );
+    }
+
+    #[test]
+    fn from_input_works() -> Result<(), Error> {
+        let points = &[[0.0, 1.0, 0.0], [0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 1.0]];
+        let mut tetgen = Tetgen::from_input(points, &[], &[], &[])?;
+        assert_eq!(tetgen.facet_polygon_npoint, None);
         assert_eq!(tetgen.nregion, None);
         assert_eq!(tetgen.nhole, None);
-        assert_eq!(tetgen.all_points_set, false);
-        assert_eq!(tetgen.all_facets_set, false);
-        assert_eq!(tetgen.all_regions_set, false);
-        assert_eq!(tetgen.all_holes_set, false);
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+
+        let facets = vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]];
+        let mut tetgen = Tetgen::from_input(points, &facets, &[(0.1, 0.9, 0.1, 1, None)], &[])?;
+        assert_eq!(
+            tetgen.facet_polygon_npoint,
+            Some(vec![vec![3], vec![3], vec![3], vec![3]])
+        );
+        assert_eq!(tetgen.nregion, Some(1));
+        assert_eq!(tetgen.nhole, None);
+        tetgen.generate_mesh(false, false, Some(0.01), None)?;
+        assert_eq!(tetgen.ntet(), 12);
+        assert_eq!(tetgen.npoint(), 11);
+        assert_eq!(tetgen.tet_attributes(), vec![1; 12]);
         Ok(())
     }
 
     #[test]
-    fn set_point_captures_some_errors() -> Result<(), StrError> {
+    fn set_point_captures_some_errors() -> Result<(), Error> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         assert_eq!(
             tetgen.set_point(5, 0.0, 0.0, 0.0).err(),
-            Some("index of point is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Point,
+                index: 5,
+                limit: 4
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn set_facet_point_captures_some_errors() -> Result<(), StrError> {
+    fn set_facet_point_captures_some_errors() -> Result<(), Error> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         assert_eq!(
             tetgen.set_facet_point(0, 0, 0).err(),
-            Some("cannot set facet point because facet_npoint is None")
+            Some(Error::NotAllocated { item: Item::Facet })
         );
         let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
         assert_eq!(
             tetgen.set_facet_point(5, 0, 0).err(),
-            Some("index of facet is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Facet,
+                index: 5,
+                limit: 4
+            })
         );
         assert_eq!(
             tetgen.set_facet_point(0, 4, 0).err(),
-            Some("index of facet point is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::FacetPoint,
+                index: 4,
+                limit: 3
+            })
         );
         assert_eq!(
             tetgen.set_facet_point(0, 0, 5).err(),
-            Some("id of facet point is out of bounds")
+            Some(Error::PointIdOutOfBounds {
+                item: Item::Facet,
+                id: 5,
+                npoint: 4
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_facet_polygon_point_captures_some_errors() -> Result<(), Error> {
+        let mut tetgen =
+            Tetgen::new_with_facet_polygons(4, Some(vec![vec![3], vec![3], vec![3], vec![3, 3]]), None, None, None)?;
+        assert_eq!(
+            tetgen.set_facet_polygon_point(0, 1, 0, 0).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::FacetPolygon,
+                index: 1,
+                limit: 1
+            })
+        );
+        assert_eq!(
+            tetgen.set_facet_polygon_point(3, 1, 3, 0).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::FacetPoint,
+                index: 3,
+                limit: 3
+            })
+        );
+        tetgen.set_facet_polygon_point(3, 1, 2, 0)?;
+        Ok(())
+    }
+
+    #[test]
+    fn set_facet_hole_captures_some_errors() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        assert_eq!(
+            tetgen.set_facet_hole(0, 0, 0.0, 0.0, 0.0).err(),
+            Some(Error::NotAllocated { item: Item::FacetHole })
+        );
+        let mut tetgen =
+            Tetgen::new_with_facet_polygons(4, Some(vec![vec![3]; 4]), Some(vec![0, 1, 0, 0]), None, None)?;
+        assert_eq!(
+            tetgen.set_facet_hole(4, 0, 0.0, 0.0, 0.0).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::Facet,
+                index: 4,
+                limit: 4
+            })
+        );
+        assert_eq!(
+            tetgen.set_facet_hole(0, 0, 0.0, 0.0, 0.0).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::FacetHole,
+                index: 0,
+                limit: 0
+            })
+        );
+        assert_eq!(
+            tetgen.set_facet_hole(1, 1, 0.0, 0.0, 0.0).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::FacetHole,
+                index: 1,
+                limit: 1
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn set_region_captures_some_errors() -> Result<(), StrError> {
+    fn set_facet_marker_captures_some_errors() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.set_facet_marker(0, -1).err(),
+            Some(Error::NotAllocated { item: Item::Facet })
+        );
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        assert_eq!(
+            tetgen.set_facet_marker(4, -1).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::Facet,
+                index: 4,
+                limit: 4
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_region_captures_some_errors() -> Result<(), Error> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         assert_eq!(
             tetgen.set_region(0, 0.33, 0.33, 0.33, 1, Some(0.1)).err(),
-            Some("cannot set region because the number of regions is None")
+            Some(Error::NotAllocated { item: Item::Region })
         );
         let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), None)?;
         assert_eq!(
             tetgen.set_region(1, 0.33, 0.33, 0.33, 1, Some(0.1)).err(),
-            Some("index of region is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Region,
+                index: 1,
+                limit: 1
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn set_hole_captures_some_errors() -> Result<(), StrError> {
+    fn set_hole_captures_some_errors() -> Result<(), Error> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         assert_eq!(
             tetgen.set_hole(0, 0.33, 0.33, 0.33).err(),
-            Some("cannot set hole because the number of holes is None")
+            Some(Error::NotAllocated { item: Item::Hole })
         );
         let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), Some(1), Some(1))?;
         assert_eq!(
             tetgen.set_hole(1, 0.33, 0.33, 0.33).err(),
-            Some("index of hole is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Hole,
+                index: 1,
+                limit: 1
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn generate_methods_capture_some_errors() -> Result<(), StrError> {
-        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+    fn generate_methods_capture_some_errors() -> Result<(), Error> {
+        let mut tetgen =
+            Tetgen::new_with_facet_polygons(4, Some(vec![vec![3]; 4]), Some(vec![0, 0, 1, 0]), Some(1), Some(1))?;
         assert_eq!(
             tetgen.generate_delaunay(false).err(),
-            Some("cannot generate Delaunay tetrahedralization because not all points are set")
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TetgenDelaunay,
+                missing: vec![0, 1, 2, 3],
+            })
+        );
+        tetgen.set_point(3, 0.0, 0.0, 1.0)?.set_point(1, 1.0, 0.0, 0.0)?;
+        assert_eq!(
+            tetgen.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TetgenMesh,
+                missing: vec![0, 2],
+            })
         );
+        tetgen.set_point(0, 0.0, 0.0, 0.0)?.set_point(2, 0.0, 1.0, 0.0)?;
         assert_eq!(
             tetgen.generate_mesh(false, false, None, None).err(),
-            Some("cannot generate mesh of tetrahedra because not all points are set")
+            Some(Error::NotAllSet {
+                item: Item::Facet,
+                stage: Stage::TetgenMesh,
+                missing: vec![0, 1, 2, 3],
+            })
         );
+        // facets set out of order, with the second point of facet 2 missing
+        tetgen
+            .set_facet_point(3, 0, 1)?
+            .set_facet_point(3, 1, 2)?
+            .set_facet_point(3, 2, 3)?
+            .set_facet_point(0, 0, 0)?
+            .set_facet_point(0, 1, 2)?
+            .set_facet_point(0, 2, 1)?
+            .set_facet_point(2, 0, 0)?
+            .set_facet_point(2, 2, 2)?
+            .set_facet_point(1, 0, 0)?
+            .set_facet_point(1, 1, 1)?
+            .set_facet_point(1, 2, 3)?;
+        assert_eq!(
+            tetgen.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::Facet,
+                stage: Stage::TetgenMesh,
+                missing: vec![2],
+            })
+        );
+        tetgen.set_facet_point(2, 1, 3)?;
+        assert_eq!(
+            tetgen.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::FacetHole,
+                stage: Stage::TetgenMesh,
+                missing: vec![2],
+            })
+        );
+        tetgen.set_facet_hole(2, 0, 0.0, 0.1, 0.1)?;
+        assert_eq!(
+            tetgen.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::Region,
+                stage: Stage::TetgenMesh,
+                missing: vec![0],
+            })
+        );
+        tetgen.set_region(0, 0.1, 0.1, 0.1, 1, None)?;
+        assert_eq!(
+            tetgen.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::Hole,
+                stage: Stage::TetgenMesh,
+                missing: vec![0],
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generate_methods_return_tetgen_errors() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 1.0, 1.0, 1.0)?
+            .set_point(1, 1.0, 1.0, 1.0)?
+            .set_point(2, 1.0, 1.0, 1.0)?
+            .set_point(3, 1.0, 1.0, 1.0)?;
+        assert_eq!(
+            tetgen.generate_delaunay(false).err(),
+            Some(Error::GeneratorFailed {
+                stage: Stage::TetgenDelaunay,
+                status: constants::TRITET_ERROR_TETGEN_INVALID_INPUT
+            })
+        );
+        assert_eq!(tetgen.npoint(), 0);
+        assert_eq!(tetgen.ntet(), 0);
+        // the instance can still be used
+        tetgen
+            .set_point(1, 2.0, 1.0, 1.0)?
+            .set_point(2, 1.0, 2.0, 1.0)?
+            .set_point(3, 1.0, 1.0, 2.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.npoint(), 4);
+        assert_eq!(tetgen.ntet(), 1);
+
+        // two triangles crossing each other (and the tetrahedron)
+        let mut tetgen = Tetgen::new(6, Some(vec![3, 3, 3, 3, 3]), None, None)?;
         tetgen
             .set_point(0, 0.0, 0.0, 0.0)?
             .set_point(1, 1.0, 0.0, 0.0)?
             .set_point(2, 0.0, 1.0, 0.0)?
-            .set_point(3, 0.0, 0.0, 1.0)?;
+            .set_point(3, 0.0, 0.0, 1.0)?
+            .set_point(4, -1.0, 0.2, 0.2)?
+            .set_point(5, 2.0, 0.2, 0.2)?;
+        let facets = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3], [4, 5, 3]];
+        for (index, points) in facets.iter().enumerate() {
+            for (m, p) in points.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
         assert_eq!(
             tetgen.generate_mesh(false, false, None, None).err(),
-            Some("cannot generate mesh of tetrahedra because not all facets are set")
+            Some(Error::GeneratorFailed {
+                stage: Stage::TetgenMesh,
+                status: constants::TRITET_ERROR_TETGEN_INTERNAL_ERROR
+            })
         );
+        assert_eq!(tetgen.npoint(), 0);
+        assert_eq!(tetgen.nface(), 0);
         Ok(())
     }
 
     #[test]
-    fn generate_delaunay_works() -> Result<(), StrError> {
+    fn generate_delaunay_works() -> Result<(), Error> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         tetgen
             .set_point(0, 0.0, 0.0, 0.0)?
@@ -828,11 +1746,40 @@ mod tests {
         tetgen.generate_delaunay(false)?;
         assert_eq!(tetgen.ntet(), 1);
         assert_eq!(tetgen.npoint(), 4);
+        for face in 0..4 {
+            assert_eq!(tetgen.tet_neighbor(0, face), None);
+        }
+        assert_eq!(tetgen.nface(), 4);
+        for face in 0..4 {
+            assert_eq!(tetgen.face_marker(face), 0);
+        }
+        assert_eq!(tetgen.tet_neighbor(100, 0), None);
+        assert_eq!(tetgen.tet_neighbor(0, 100), None);
+        // the other methods panic in debug builds
+        if cfg!(debug_assertions) {
+            let calls: [fn(&Tetgen); 8] = [
+                |t| _ = t.point(100, 0),
+                |t| _ = t.point(0, 100),
+                |t| _ = t.tet_node(100, 0),
+                |t| _ = t.tet_node(0, 100),
+                |t| _ = t.tet_attribute(100),
+                |t| _ = t.face_node(100, 0),
+                |t| _ = t.face_node(0, 100),
+                |t| _ = t.face_marker(100),
+            ];
+            for call in calls {
+                assert!(panic::catch_unwind(AssertUnwindSafe(|| call(&tetgen))).is_err());
+            }
+        } else {
+            assert_eq!(tetgen.face_node(100, 0), 0);
+            assert_eq!(tetgen.face_node(0, 100), 0);
+            assert_eq!(tetgen.face_marker(100), 0);
+        }
         Ok(())
     }
 
     #[test]
-    fn draw_wireframe_works() -> Result<(), StrError> {
+    fn draw_wireframe_works() -> Result<(), Box<dyn std::error::Error>> {
         let mut tetgen = Tetgen::new(4, None, None, None)?;
         tetgen
             .set_point(0, 0.0, 0.0, 0.0)?
@@ -856,7 +1803,7 @@ mod tests {
     }
 
     #[test]
-    fn generate_delaunay_works_1() -> Result<(), StrError> {
+    fn generate_delaunay_works_1() -> Result<(), Box<dyn std::error::Error>> {
         let mut tetgen = Tetgen::new(8, None, None, None)?;
         tetgen
             .set_point(0, 0.0, 0.0, 0.0)?
@@ -870,6 +1817,7 @@ mod tests {
         tetgen.generate_delaunay(false)?;
         assert_eq!(tetgen.ntet(), 6);
         assert_eq!(tetgen.npoint(), 8);
+        check_neighbors(&tetgen);
         #[cfg(feature = "plotpy")]
         {
             let mut plot = Plot::new();
@@ -884,7 +1832,7 @@ mod tests {
     }
 
     #[test]
-    fn generate_mesh_works_1() -> Result<(), StrError> {
+    fn generate_mesh_works_1() -> Result<(), Box<dyn std::error::Error>> {
         let mut tetgen = Tetgen::new(
             16,
             Some(vec![
@@ -981,6 +1929,7 @@ mod tests {
         tetgen.generate_mesh(false, false, None, None)?;
         assert_eq!(tetgen.ntet(), 116);
         assert_eq!(tetgen.npoint(), 50);
+        check_neighbors(&tetgen);
 
         #[cfg(feature = "plotpy")]
         {
@@ -995,4 +1944,374 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn generate_mesh_works_2() -> Result<(), Error> {
+        // facets and markers: -1 (x=0), -2 (x=1), -3 (y=0), -4 (y=1), -5 (z=0), -6 (z=1)
+        let mut tetgen = cube()?;
+        tetgen.generate_mesh(false, true, None, None)?;
+        assert_eq!(tetgen.nnode(), 10);
+        assert_eq!(tetgen.nface(), 12);
+        for face in 0..tetgen.nface() {
+            // the marker identifies the plane containing the face
+            let marker = tetgen.face_marker(face);
+            let dim = ((-marker - 1) / 2) as usize;
+            let coord = ((-marker - 1) % 2) as f64;
+            for m in 0..6 {
+                assert_eq!(tetgen.point(tetgen.face_node(face, m), dim), coord);
+            }
+            // the middle nodes are located halfway between the corners
+            for (m, (a, b)) in [(3, (0, 1)), (4, (1, 2)), (5, (2, 0))] {
+                let mid = tetgen.face_node(face, m);
+                let na = tetgen.face_node(face, a);
+                let nb = tetgen.face_node(face, b);
+                for dim in 0..3 {
+                    let xm = (tetgen.point(na, dim) + tetgen.point(nb, dim)) / 2.0;
+                    assert_eq!(tetgen.point(mid, dim), xm);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tet_node_numbers_middle_nodes_correctly() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, Some(vec![3, 3, 3, 3]), None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        let facets = [[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]];
+        for (index, points) in facets.iter().enumerate() {
+            for (m, p) in points.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen.generate_mesh(false, true, None, None)?;
+        assert_eq!(tetgen.ntet(), 1);
+        assert_eq!(tetgen.nnode(), 10);
+        // see the figure in the documentation of tet_node
+        let edges = [
+            (4, (0, 1)),
+            (5, (1, 2)),
+            (6, (0, 2)),
+            (7, (0, 3)),
+            (8, (1, 3)),
+            (9, (2, 3)),
+        ];
+        for tet in 0..tetgen.ntet() {
+            for (m, (a, b)) in edges {
+                let mid = tetgen.tet_node(tet, m);
+                let na = tetgen.tet_node(tet, a);
+                let nb = tetgen.tet_node(tet, b);
+                for dim in 0..3 {
+                    let xm = (tetgen.point(na, dim) + tetgen.point(nb, dim)) / 2.0;
+                    assert_eq!(tetgen.point(mid, dim), xm);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn tet_neighbor_faces_match_the_documentation() -> Result<(), Error> {
+        // see the table in the documentation of tet_neighbor: corners and middle nodes of each face
+        let faces = [
+            [1, 2, 3, 5, 8, 9],
+            [0, 2, 3, 6, 7, 9],
+            [0, 1, 3, 4, 7, 8],
+            [0, 1, 2, 4, 5, 6],
+        ];
+        let mut tetgen = cube()?;
+        tetgen.generate_mesh(false, true, Some(0.1), None)?;
+        let mut nshared = 0;
+        for tet in 0..tetgen.ntet() {
+            for (face, locals) in faces.iter().enumerate() {
+                if let Some(neighbor) = tetgen.tet_neighbor(tet, face) {
+                    let nodes: Vec<_> = (0..10).map(|m| tetgen.tet_node(neighbor, m)).collect();
+                    for m in locals {
+                        assert!(nodes.contains(&tetgen.tet_node(tet, *m)));
+                    }
+                    assert!(!nodes.contains(&tetgen.tet_node(tet, face)));
+                    nshared += 1;
+                }
+            }
+        }
+        assert!(nshared > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_captures_some_errors() -> Result<(), Error> {
+        let mut tetgen = Tetgen::from_input(
+            &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            &[vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 2, 3]],
+            &[],
+            &[],
+        )?;
+        assert_eq!(
+            tetgen.refine_mesh(false, false, &[], None).err(),
+            Some(Error::InvalidInput(
+                "there is no mesh to refine (call generate_mesh first)"
+            ))
+        );
+        tetgen.generate_mesh(false, false, None, None)?;
+        assert_eq!(
+            tetgen.refine_mesh(false, false, &[0.1, 0.1], None).err(),
+            Some(Error::InvalidInput(
+                "the number of max volumes must equal the number of tetrahedra"
+            ))
+        );
+        assert_eq!(
+            tetgen.refine_mesh(false, false, &[f64::NAN], None).err(),
+            Some(Error::InvalidInput("the max volumes must not be NaN"))
+        );
+        tetgen.generate_mesh(false, true, None, None)?;
+        assert_eq!(
+            tetgen.refine_mesh(false, false, &[0.1], None).err(),
+            Some(Error::InvalidInput(
+                "the mesh to refine must have linear tetrahedra (nnode = 4)"
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_works() -> Result<(), Error> {
+        // two unit cubes (regions) side by side, split by the facet at x = 1
+        let points = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [2.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [2.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 1.0],
+            [2.0, 1.0, 1.0],
+        ];
+        let facets = vec![
+            vec![0, 3, 4, 1], // bottom (left)
+            vec![1, 4, 5, 2], // bottom (right)
+            vec![6, 7, 10, 9],
+            vec![7, 8, 11, 10],
+            vec![0, 1, 7, 6],
+            vec![1, 2, 8, 7],
+            vec![3, 9, 10, 4],
+            vec![4, 10, 11, 5],
+            vec![0, 6, 9, 3],
+            vec![2, 5, 11, 8],
+            vec![1, 4, 10, 7], // interface
+        ];
+        let regions = [(0.5, 0.5, 0.5, 1, None), (1.5, 0.5, 0.5, 2, None)];
+        let mut tetgen = Tetgen::from_input(&points, &facets, &regions, &[])?;
+        tetgen
+            .set_facet_marker(0, -10)?
+            .set_facet_marker(1, -10)?
+            .set_facet_marker(10, -70)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+
+        // refine the left region only (twice)
+        for max_volume in [0.05, 0.005] {
+            let max_volumes: Vec<_> = (0..tetgen.ntet())
+                .map(|tet| {
+                    if tetgen.tet_attribute(tet) == 1 {
+                        max_volume
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            tetgen.refine_mesh(false, false, &max_volumes, None)?;
+            check_neighbors(&tetgen);
+            let quality = tetgen.quality(None)?;
+            let mut total = 0.0;
+            for (tet, metrics) in quality.tets.iter().enumerate() {
+                if tetgen.tet_attribute(tet) == 1 {
+                    assert!(metrics.volume <= max_volume);
+                }
+                total += metrics.volume;
+            }
+            assert!(f64::abs(total - 2.0) < 1e-14);
+        }
+        let attributes = tetgen.tet_attributes();
+        let nleft = attributes.iter().filter(|a| **a == 1).count();
+        let nright = attributes.iter().filter(|a| **a == 2).count();
+        assert_eq!(nleft + nright, tetgen.ntet());
+        assert!(nleft >= 100);
+        assert!(nright < nleft / 2);
+
+        // the face markers are kept
+        let mut ninterface = 0;
+        for face in 0..tetgen.nface() {
+            let x: Vec<[f64; 3]> = (0..3)
+                .map(|m| {
+                    let p = tetgen.face_node(face, m);
+                    [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)]
+                })
+                .collect();
+            if x.iter().all(|x| x[2] == 0.0) {
+                assert_eq!(tetgen.face_marker(face), -10);
+            } else if x.iter().all(|x| x[0] == 1.0) {
+                assert_eq!(tetgen.face_marker(face), -70);
+                ninterface += 1;
+            } else {
+                assert_eq!(tetgen.face_marker(face), 0);
+            }
+        }
+        assert!(ninterface > 2);
+
+        // the last refinement may generate the middle nodes
+        let ntet = tetgen.ntet();
+        let max_volumes: Vec<_> = (0..ntet)
+            .map(|tet| if tetgen.tet_attribute(tet) == 2 { 0.002 } else { 0.0 })
+            .collect();
+        tetgen.refine_mesh(false, true, &max_volumes, None)?;
+        assert_eq!(tetgen.nnode(), 10);
+        assert!(tetgen.ntet() > ntet);
+        let quality = tetgen.quality(None)?;
+        for (tet, metrics) in quality.tets.iter().enumerate() {
+            if tetgen.tet_attribute(tet) == 2 {
+                assert!(metrics.volume <= 0.002);
+            }
+        }
+        let mut used = vec![false; tetgen.npoint()];
+        for p in tetgen.tets() {
+            used[p] = true;
+        }
+        assert!(used.iter().all(|u| *u));
+        Ok(())
+    }
+
+    #[test]
+    fn bulk_methods_work() -> Result<(), Error> {
+        for o2 in [false, true] {
+            let mut tetgen = Tetgen::new(8, Some(vec![4, 4, 4, 4, 4, 4]), Some(1), None)?;
+            assert_eq!(tetgen.points().len(), 0);
+            assert_eq!(tetgen.tets().len(), 0);
+            assert_eq!(tetgen.faces().len(), 0);
+            assert_eq!(tetgen.tet_points().count(), 0);
+            set_cube(&mut tetgen)?;
+            tetgen.set_region(0, 0.1, 0.2, 0.3, 3, None)?;
+            tetgen.generate_mesh(false, o2, Some(0.1), None)?;
+            let (npoint, ntet, nnode, nface) = (tetgen.npoint(), tetgen.ntet(), tetgen.nnode(), tetgen.nface());
+            let points = tetgen.points();
+            assert_eq!(points.len(), 3 * npoint);
+            for p in 0..npoint {
+                for dim in 0..3 {
+                    assert_eq!(points[3 * p + dim], tetgen.point(p, dim));
+                }
+            }
+            let cells = tetgen.tets();
+            assert_eq!(cells.len(), ntet * nnode);
+            for (i, coords) in tetgen.tet_points().enumerate() {
+                assert_eq!(coords.len(), nnode);
+                for m in 0..nnode {
+                    let p = tetgen.tet_node(i, m);
+                    assert_eq!(cells[i * nnode + m], p);
+                    assert_eq!(coords[m], [tetgen.point(p, 0), tetgen.point(p, 1), tetgen.point(p, 2)]);
+                }
+            }
+            assert_eq!(tetgen.tet_attributes(), vec![3; ntet]);
+            for (i, neighbors) in tetgen.tet_neighbors().iter().enumerate() {
+                for (face, neighbor) in neighbors.iter().enumerate() {
+                    assert_eq!(*neighbor, tetgen.tet_neighbor(i, face));
+                }
+            }
+            let n = if o2 { 6 } else { 3 };
+            let faces = tetgen.faces();
+            let markers = tetgen.face_markers();
+            assert_eq!(faces.len(), nface * n);
+            assert_eq!(markers.len(), nface);
+            for i in 0..nface {
+                for m in 0..n {
+                    assert_eq!(faces[i * n + m], tetgen.face_node(i, m));
+                }
+                assert_eq!(markers[i], tetgen.face_marker(i));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_works_3() -> Result<(), Error> {
+        // a unit cube with a small box (chimney) on top; the top facet of the cube has
+        // a window (second polygon) which is removed by a facet hole
+        let mut facet_polygon_npoint = vec![vec![4]; 11];
+        facet_polygon_npoint[5] = vec![4, 4];
+        let mut facet_nhole = vec![0; 11];
+        facet_nhole[5] = 1;
+        let mut tetgen =
+            Tetgen::new_with_facet_polygons(16, Some(facet_polygon_npoint), Some(facet_nhole), None, None)?;
+        for (p, x) in CUBE_POINTS.iter().enumerate() {
+            tetgen.set_point(p, x[0], x[1], x[2])?;
+        }
+        tetgen
+            .set_point(8, 0.25, 0.25, 1.0)?
+            .set_point(9, 0.75, 0.25, 1.0)?
+            .set_point(10, 0.75, 0.75, 1.0)?
+            .set_point(11, 0.25, 0.75, 1.0)?
+            .set_point(12, 0.25, 0.25, 1.5)?
+            .set_point(13, 0.75, 0.25, 1.5)?
+            .set_point(14, 0.75, 0.75, 1.5)?
+            .set_point(15, 0.25, 0.75, 1.5)?;
+        // the top of the cube (facet 5) is the outer polygon
+        let chimney = [
+            [8, 9, 13, 12],
+            [9, 10, 14, 13],
+            [10, 11, 15, 14],
+            [11, 8, 12, 15],
+            [12, 13, 14, 15],
+        ];
+        for (index, points) in CUBE_FACETS.iter().chain(chimney.iter()).enumerate() {
+            for (m, p) in points.iter().enumerate() {
+                tetgen.set_facet_point(index, m, *p)?;
+            }
+        }
+        tetgen
+            .set_facet_polygon_point(5, 1, 0, 8)?
+            .set_facet_polygon_point(5, 1, 1, 9)?
+            .set_facet_polygon_point(5, 1, 2, 10)?
+            .set_facet_polygon_point(5, 1, 3, 11)?;
+        assert_eq!(
+            tetgen.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::FacetHole,
+                stage: Stage::TetgenMesh,
+                missing: vec![5],
+            })
+        );
+        tetgen.set_facet_hole(5, 0, 0.5, 0.5, 1.0)?;
+        tetgen.set_facet_marker(5, -1)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        check_neighbors(&tetgen);
+
+        // total volume
+        let volume = total_volume(&tetgen)?;
+        assert!(f64::abs(volume - 1.125) < 1e-15);
+
+        // no face on the window
+        let mut nface_top = 0;
+        for face in 0..tetgen.nface() {
+            let xc: Vec<f64> = (0..3)
+                .map(|dim| {
+                    (0..3)
+                        .map(|m| tetgen.point(tetgen.face_node(face, m), dim))
+                        .sum::<f64>()
+                        / 3.0
+                })
+                .collect();
+            if xc[2] == 1.0 {
+                assert!(!(xc[0] > 0.25 && xc[0] < 0.75 && xc[1] > 0.25 && xc[1] < 0.75));
+                assert_eq!(tetgen.face_marker(face), -1);
+                nface_top += 1;
+            }
+        }
+        assert!(nface_top > 0);
+        Ok(())
+    }
 }
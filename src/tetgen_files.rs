@@ -0,0 +1,663 @@
+use crate::file_io::{read_file, read_node_section, write_file, DataLines};
+use crate::{Error, Tetgen};
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Holds the data of a facet read from a file
+struct FacetData {
+    polygons: Vec<Vec<usize>>, // point IDs of each polygon
+    holes: Vec<[f64; 3]>,      // coordinates of the holes within the facet
+    marker: Option<i32>,       // boundary marker
+}
+
+/// Reads a Tetgen's .node file and allocates a Tetgen with the points
+///
+/// The file format is described in [Tetgen's manual](https://wias-berlin.de/software/tetgen/fformats.node.html).
+/// The points may be numbered from zero or one (the first point determines the numbering).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Point attributes and boundary markers are ignored.
+pub fn read_tetgen_node<P>(full_path: &P) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let contents = read_file(full_path)?;
+    let mut lines = DataLines::new(&contents);
+    let (points, _) = read_node_section(&mut lines, 3)?;
+    let mut tetgen = Tetgen::new(points.len(), None, None, None)?;
+    for (index, x) in points.iter().enumerate() {
+        tetgen.set_point(index, x[0], x[1], x[2])?;
+    }
+    Ok(tetgen)
+}
+
+/// Reads a Tetgen's .poly file and allocates a Tetgen with the Piecewise Linear Complex (PLC)
+///
+/// The file format is described in [Tetgen's manual](https://wias-berlin.de/software/tetgen/fformats.poly.html).
+/// The points, facets (polygons, holes, and markers), holes, and regions (attributes and maximum
+/// volume constraints) are read from the file. If the number of points in the .poly file is zero,
+/// the points are read from the .node file with the same name (e.g., `box.node` for `box.poly`).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Point attributes and boundary markers are ignored. The regional attributes must be non-negative integers.
+pub fn read_tetgen_poly<P>(full_path: &P) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    read_tetgen_plc(full_path, false)
+}
+
+/// Reads a Tetgen's .smesh file and allocates a Tetgen with the Piecewise Linear Complex (PLC)
+///
+/// The file format is described in [Tetgen's manual](https://wias-berlin.de/software/tetgen/fformats.smesh.html).
+/// This format is the same as .poly, except that each facet is a single polygon given in one line.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Point attributes and boundary markers are ignored. The regional attributes must be non-negative integers.
+pub fn read_tetgen_smesh<P>(full_path: &P) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    read_tetgen_plc(full_path, true)
+}
+
+/// Writes the points of the generated mesh as a Tetgen's .node file
+///
+/// The points are numbered from zero.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tetgen_node<P>(tetgen: &Tetgen, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let npoint = tetgen.npoint();
+    if npoint < 1 {
+        return Err(Error::InvalidInput("there are no points to write"));
+    }
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} 3 0 0", npoint).unwrap();
+    for index in 0..npoint {
+        writeln!(
+            &mut buffer,
+            "{} {} {} {}",
+            index,
+            tetgen.point(index, 0),
+            tetgen.point(index, 1),
+            tetgen.point(index, 2)
+        )
+        .unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+/// Writes the tetrahedra of the generated mesh as a Tetgen's .ele file
+///
+/// The nodes are written in the order given by [Tetgen::tet_node], which is also Tetgen's order.
+/// The attribute of each tetrahedron is written as the single attribute (see [Tetgen::tet_attribute]).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tetgen_ele<P>(tetgen: &Tetgen, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ntet = tetgen.ntet();
+    if ntet < 1 {
+        return Err(Error::InvalidInput("there are no tetrahedra to write"));
+    }
+    let nnode = tetgen.nnode();
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} {} 1", ntet, nnode).unwrap();
+    for index in 0..ntet {
+        write!(&mut buffer, "{}", index).unwrap();
+        for m in 0..nnode {
+            write!(&mut buffer, " {}", tetgen.tet_node(index, m)).unwrap();
+        }
+        writeln!(&mut buffer, " {}", tetgen.tet_attribute(index)).unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+/// Writes the triangular faces on the boundary of the generated mesh as a Tetgen's .face file
+///
+/// Only the corners of the faces are written (as Tetgen does). The boundary markers
+/// of the faces are written (see [Tetgen::face_marker]).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_tetgen_face<P>(tetgen: &Tetgen, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let nface = tetgen.nface();
+    if nface < 1 {
+        return Err(Error::InvalidInput("there are no faces to write"));
+    }
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} 1", nface).unwrap();
+    for index in 0..nface {
+        writeln!(
+            &mut buffer,
+            "{} {} {} {} {}",
+            index,
+            tetgen.face_node(index, 0),
+            tetgen.face_node(index, 1),
+            tetgen.face_node(index, 2),
+            tetgen.face_marker(index)
+        )
+        .unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+/// Reads .poly or .smesh files
+fn read_tetgen_plc<P>(full_path: &P, smesh: bool) -> Result<Tetgen, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let contents = read_file(full_path)?;
+    let mut lines = DataLines::new(&contents);
+
+    // points
+    let (mut points, mut first_id) = read_node_section(&mut lines, 3)?;
+    if points.is_empty() {
+        let node_path = Path::new(full_path).with_extension("node");
+        let node_contents = read_file(&node_path)?;
+        let mut node_lines = DataLines::new(&node_contents);
+        (points, first_id) = read_node_section(&mut node_lines, 3)?;
+    }
+    let npoint = points.len();
+
+    // facets
+    let fields = lines.expect_fields(1, "cannot read the facets header")?;
+    let nfacet: usize = lines.parse(fields[0], "cannot parse the number of facets")?;
+    let nmarker: usize = match fields.get(1) {
+        Some(field) => lines.parse(field, "cannot parse the number of facet markers")?,
+        None => 0,
+    };
+    let mut facets = Vec::new();
+    for _ in 0..nfacet {
+        let facet = if smesh {
+            read_smesh_facet(&mut lines, nmarker, npoint, first_id)?
+        } else {
+            read_poly_facet(&mut lines, nmarker, npoint, first_id)?
+        };
+        facets.push(facet);
+    }
+
+    // holes
+    let fields = lines.expect_fields(1, "cannot read the holes header")?;
+    let nhole: usize = lines.parse(fields[0], "cannot parse the number of holes")?;
+    let mut holes = Vec::new();
+    for _ in 0..nhole {
+        let fields = lines.expect_fields(4, "cannot read hole")?;
+        holes.push(parse_coordinates(
+            &lines,
+            &fields[1..4],
+            "cannot parse the coordinates of hole",
+        )?);
+    }
+
+    // regions (optional)
+    let mut regions = Vec::new();
+    if let Some(fields) = lines.next_fields() {
+        let nregion: usize = lines.parse(fields[0], "cannot parse the number of regions")?;
+        for _ in 0..nregion {
+            let fields = lines.expect_fields(5, "cannot read region")?;
+            let x = parse_coordinates(&lines, &fields[1..4], "cannot parse the coordinates of region")?;
+            let value: f64 = lines.parse(fields[4], "cannot parse the attribute of region")?;
+            if value < 0.0 || value.fract() != 0.0 {
+                return Err(lines.error("the attribute of region must be a non-negative integer"));
+            }
+            let max_volume: Option<f64> = match fields.get(5) {
+                Some(field) => {
+                    let volume: f64 = lines.parse(field, "cannot parse the maximum volume of region")?;
+                    if volume > 0.0 {
+                        Some(volume)
+                    } else {
+                        None
+                    }
+                }
+                None => None,
+            };
+            regions.push((x, value as usize, max_volume));
+        }
+    }
+
+    // allocate and set the data
+    let with_facet_holes = facets.iter().any(|facet| !facet.holes.is_empty());
+    let mut tetgen = Tetgen::new_with_facet_polygons(
+        npoint,
+        if nfacet > 0 {
+            Some(
+                facets
+                    .iter()
+                    .map(|f| f.polygons.iter().map(|p| p.len()).collect())
+                    .collect(),
+            )
+        } else {
+            None
+        },
+        if with_facet_holes {
+            Some(facets.iter().map(|f| f.holes.len()).collect())
+        } else {
+            None
+        },
+        if regions.is_empty() { None } else { Some(regions.len()) },
+        if nhole > 0 { Some(nhole) } else { None },
+    )?;
+    for (index, x) in points.iter().enumerate() {
+        tetgen.set_point(index, x[0], x[1], x[2])?;
+    }
+    for (index, facet) in facets.iter().enumerate() {
+        for (polygon, ids) in facet.polygons.iter().enumerate() {
+            for (m, p) in ids.iter().enumerate() {
+                tetgen.set_facet_polygon_point(index, polygon, m, *p)?;
+            }
+        }
+        for (hole, x) in facet.holes.iter().enumerate() {
+            tetgen.set_facet_hole(index, hole, x[0], x[1], x[2])?;
+        }
+        if let Some(marker) = facet.marker {
+            tetgen.set_facet_marker(index, marker)?;
+        }
+    }
+    for (index, x) in holes.iter().enumerate() {
+        tetgen.set_hole(index, x[0], x[1], x[2])?;
+    }
+    for (index, (x, attribute, max_volume)) in regions.iter().enumerate() {
+        tetgen.set_region(index, x[0], x[1], x[2], *attribute, *max_volume)?;
+    }
+    Ok(tetgen)
+}
+
+/// Reads a facet of a .poly file (a header line, the polygons, and the holes)
+fn read_poly_facet(lines: &mut DataLines, nmarker: usize, npoint: usize, first_id: usize) -> Result<FacetData, Error> {
+    let fields = lines.expect_fields(1, "cannot read the facet header")?;
+    let npolygon: usize = lines.parse(fields[0], "cannot parse the number of polygons of facet")?;
+    let nhole: usize = match fields.get(1) {
+        Some(field) => lines.parse(field, "cannot parse the number of holes of facet")?,
+        None => 0,
+    };
+    let marker: Option<i32> = match fields.get(2) {
+        Some(field) if nmarker > 0 => Some(lines.parse(field, "cannot parse the marker of facet")?),
+        _ => None,
+    };
+    let mut polygons = Vec::new();
+    for _ in 0..npolygon {
+        let fields = lines.expect_fields(1, "cannot read polygon")?;
+        polygons.push(parse_corners(lines, &fields, npoint, first_id)?.0);
+    }
+    let mut holes = Vec::new();
+    for _ in 0..nhole {
+        let fields = lines.expect_fields(4, "cannot read the hole of facet")?;
+        holes.push(parse_coordinates(
+            lines,
+            &fields[1..4],
+            "cannot parse the coordinates of the hole of facet",
+        )?);
+    }
+    Ok(FacetData {
+        polygons,
+        holes,
+        marker,
+    })
+}
+
+/// Reads a facet of a .smesh file (a single polygon in one line)
+fn read_smesh_facet(lines: &mut DataLines, nmarker: usize, npoint: usize, first_id: usize) -> Result<FacetData, Error> {
+    let fields = lines.expect_fields(1, "cannot read facet")?;
+    let (corners, rest) = parse_corners(lines, &fields, npoint, first_id)?;
+    let marker: Option<i32> = match rest.first() {
+        Some(field) if nmarker > 0 => Some(lines.parse(field, "cannot parse the marker of facet")?),
+        _ => None,
+    };
+    Ok(FacetData {
+        polygons: vec![corners],
+        holes: Vec::new(),
+        marker,
+    })
+}
+
+/// Parses a polygon given by `<# of corners> <corner 1> ... <corner #>`
+///
+/// Returns the point IDs (numbered from zero) and the remaining fields.
+fn parse_corners<'a>(
+    lines: &DataLines,
+    fields: &'a [&'a str],
+    npoint: usize,
+    first_id: usize,
+) -> Result<(Vec<usize>, &'a [&'a str]), Error> {
+    let ncorner: usize = lines.parse(fields[0], "cannot parse the number of corners of polygon")?;
+    if ncorner > fields.len() - 1 {
+        return Err(lines.error("cannot read the corners of polygon"));
+    }
+    let mut corners = Vec::with_capacity(ncorner); // ncorner ≤ number of fields
+    for field in &fields[1..(1 + ncorner)] {
+        let id: usize = lines.parse(field, "cannot parse the point id of polygon")?;
+        if id < first_id || id - first_id >= npoint {
+            return Err(lines.error("the point id of polygon is out of bounds"));
+        }
+        corners.push(id - first_id);
+    }
+    Ok((corners, &fields[(1 + ncorner)..]))
+}
+
+/// Parses the x-y-z coordinates
+fn parse_coordinates(lines: &DataLines, fields: &[&str], message: &'static str) -> Result<[f64; 3], Error> {
+    let mut x = [0.0; 3];
+    for (value, field) in x.iter_mut().zip(fields) {
+        *value = lines.parse(field, message)?;
+    }
+    Ok(x)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_tetgen_node, read_tetgen_poly, read_tetgen_smesh, write_tetgen_ele, write_tetgen_face, write_tetgen_node,
+    };
+    use crate::file_io::{read_file, write_file};
+    use crate::test_fixtures::{cube, total_volume};
+    use crate::{Error, Tetgen};
+
+    const CUBE_POINTS: &str = "# points\n\
+        8 3 0 0\n\
+        1 0.0 0.0 0.0\n2 1.0 0.0 0.0\n3 1.0 1.0 0.0\n4 0.0 1.0 0.0\n\
+        5 0.0 0.0 1.0\n6 1.0 0.0 1.0\n7 1.0 1.0 1.0\n8 0.0 1.0 1.0\n";
+
+    // checks that the faces are on the planes given by their markers
+    fn check_face_markers(tetgen: &Tetgen) {
+        assert!(tetgen.nface() > 0);
+        for face in 0..tetgen.nface() {
+            let marker = tetgen.face_marker(face);
+            let dim = ((-marker - 1) / 2) as usize;
+            let coord = ((-marker - 1) % 2) as f64;
+            for m in 0..3 {
+                assert_eq!(tetgen.point(tetgen.face_node(face, m), dim), coord);
+            }
+        }
+    }
+
+    #[test]
+    fn read_tetgen_node_works() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_read_tetgen_node.node";
+        write_file(file_path, CUBE_POINTS)?;
+        let mut tetgen = read_tetgen_node(file_path)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.npoint(), 8);
+        assert!(tetgen.ntet() >= 5);
+        Ok(())
+    }
+
+    #[test]
+    fn read_tetgen_poly_captures_some_errors() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_read_tetgen_poly_errors.poly";
+        write_file(file_path, &format!("{}6 1\n1 0 -1\n4 1 5 8 9\n", CUBE_POINTS))?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "the point id of polygon is out of bounds"
+            })
+        );
+        write_file(file_path, &format!("{}6 1\n1 0 -1\n4 1 5 8\n", CUBE_POINTS))?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot read the corners of polygon"
+            })
+        );
+        let file_path = "/tmp/tritet/test_read_tetgen_smesh_errors.smesh";
+        write_file(file_path, &format!("{}6 1\n4 1 5 8 4 -1\n", CUBE_POINTS))?;
+        assert_eq!(
+            read_tetgen_smesh(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot read facet"
+            })
+        );
+        // the numbers read from the file are not used to preallocate memory
+        let file_path = "/tmp/tritet/test_read_tetgen_poly_errors.poly";
+        write_file(file_path, &format!("{}99999999999999999 1\n", CUBE_POINTS))?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 12,
+                message: "cannot read the facet header"
+            })
+        );
+        write_file(file_path, &format!("{}1 1\n99999999999999999 0 -1\n", CUBE_POINTS))?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot read polygon"
+            })
+        );
+        write_file(
+            file_path,
+            &format!("{}1 1\n1 99999999999999999 -1\n4 1 2 3 4\n", CUBE_POINTS),
+        )?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 14,
+                message: "cannot read the hole of facet"
+            })
+        );
+        write_file(
+            file_path,
+            &format!("{}1 1\n1 0 -1\n18446744073709551615 1 2 3 4\n", CUBE_POINTS),
+        )?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot read the corners of polygon"
+            })
+        );
+        write_file(file_path, &format!("{}0 1\n99999999999999999\n", CUBE_POINTS))?;
+        assert_eq!(
+            read_tetgen_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 13,
+                message: "cannot read hole"
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_tetgen_poly_works() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_read_tetgen_poly.poly";
+        write_file(
+            file_path,
+            &format!(
+                "{}# facets\n\
+                 6 1\n\
+                 1 0 -1\n4 1 5 8 4\n\
+                 1 0 -2\n4 2 3 7 6\n\
+                 1 0 -3\n4 1 2 6 5\n\
+                 1 0 -4\n4 3 4 8 7\n\
+                 1 0 -5\n4 1 4 3 2\n\
+                 1 0 -6\n4 5 6 7 8\n\
+                 # holes\n\
+                 0\n\
+                 # regions\n\
+                 1\n\
+                 1 0.5 0.5 0.5 7 -1\n",
+                CUBE_POINTS
+            ),
+        )?;
+        let mut tetgen = read_tetgen_poly(file_path)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let mut reference = cube()?;
+        reference.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.ntet(), reference.ntet());
+        assert_eq!(tetgen.nface(), reference.nface());
+        check_face_markers(&tetgen);
+        for index in 0..tetgen.ntet() {
+            assert_eq!(tetgen.tet_attribute(index), 7);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn read_tetgen_poly_with_facet_holes_works() -> Result<(), Error> {
+        // a unit cube with a small box (chimney) on top; the top facet of the cube has
+        // a window (second polygon) which is removed by a facet hole
+        let file_path = "/tmp/tritet/test_read_tetgen_poly_facet_holes.poly";
+        write_file(
+            file_path,
+            "16 3 0 0\n\
+             0 0.0 0.0 0.0\n1 1.0 0.0 0.0\n2 1.0 1.0 0.0\n3 0.0 1.0 0.0\n\
+             4 0.0 0.0 1.0\n5 1.0 0.0 1.0\n6 1.0 1.0 1.0\n7 0.0 1.0 1.0\n\
+             8 0.25 0.25 1.0\n9 0.75 0.25 1.0\n10 0.75 0.75 1.0\n11 0.25 0.75 1.0\n\
+             12 0.25 0.25 1.5\n13 0.75 0.25 1.5\n14 0.75 0.75 1.5\n15 0.25 0.75 1.5\n\
+             11 1\n\
+             1\n4 0 4 7 3\n\
+             1\n4 1 2 6 5\n\
+             1\n4 0 1 5 4\n\
+             1\n4 2 3 7 6\n\
+             1\n4 0 3 2 1\n\
+             2 1 -1 # top of cube with a window\n4 4 5 6 7\n4 8 9 10 11\n0 0.5 0.5 1.0\n\
+             1\n4 8 9 13 12\n\
+             1\n4 9 10 14 13\n\
+             1\n4 10 11 15 14\n\
+             1\n4 11 8 12 15\n\
+             1\n4 12 13 14 15\n\
+             0\n",
+        )?;
+        let mut tetgen = read_tetgen_poly(file_path)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let volume = total_volume(&tetgen)?;
+        assert!(f64::abs(volume - 1.125) < 1e-15);
+        for face in 0..tetgen.nface() {
+            if tetgen.face_marker(face) == -1 {
+                for m in 0..3 {
+                    assert_eq!(tetgen.point(tetgen.face_node(face, m), 2), 1.0);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn read_tetgen_smesh_works() -> Result<(), Error> {
+        let node_path = "/tmp/tritet/test_read_tetgen_smesh.node";
+        let smesh_path = "/tmp/tritet/test_read_tetgen_smesh.smesh";
+        write_file(node_path, CUBE_POINTS)?;
+        write_file(
+            smesh_path,
+            "0 3 0 0\n\
+             6 1\n\
+             4 1 5 8 4 -1\n\
+             4 2 3 7 6 -2\n\
+             4 1 2 6 5 -3\n\
+             4 3 4 8 7 -4\n\
+             4 1 4 3 2 -5\n\
+             4 5 6 7 8 -6\n\
+             0\n",
+        )?;
+        let mut tetgen = read_tetgen_smesh(smesh_path)?;
+        tetgen.generate_mesh(false, false, None, None)?;
+        let mut reference = cube()?;
+        reference.generate_mesh(false, false, None, None)?;
+        assert_eq!(tetgen.ntet(), reference.ntet());
+        assert_eq!(tetgen.nface(), reference.nface());
+        check_face_markers(&tetgen);
+        Ok(())
+    }
+
+    #[test]
+    fn write_methods_capture_some_errors() -> Result<(), Error> {
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        let file_path = "/tmp/tritet/test_write_tetgen_errors.txt";
+        assert_eq!(
+            write_tetgen_node(&tetgen, file_path).err(),
+            Some(Error::InvalidInput("there are no points to write"))
+        );
+        assert_eq!(
+            write_tetgen_ele(&tetgen, file_path).err(),
+            Some(Error::InvalidInput("there are no tetrahedra to write"))
+        );
+        assert_eq!(
+            write_tetgen_face(&tetgen, file_path).err(),
+            Some(Error::InvalidInput("there are no faces to write"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_methods_work() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+
+        // node
+        let file_path = "/tmp/tritet/test_write_tetgen.node";
+        write_tetgen_node(&tetgen, file_path)?;
+        let contents = read_file(file_path)?;
+        assert_eq!(contents, "4 3 0 0\n0 0 0 0\n1 1 0 0\n2 0 1 0\n3 0 0 1\n");
+
+        // ele
+        let file_path = "/tmp/tritet/test_write_tetgen.ele";
+        write_tetgen_ele(&tetgen, file_path)?;
+        let contents = read_file(file_path)?;
+        assert_eq!(contents, "1 4 1\n0 1 0 3 2 0\n");
+
+        // face
+        let file_path = "/tmp/tritet/test_write_tetgen.face";
+        write_tetgen_face(&tetgen, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "4 1");
+        for index in 0..4 {
+            let correct = format!(
+                "{} {} {} {} {}",
+                index,
+                tetgen.face_node(index, 0),
+                tetgen.face_node(index, 1),
+                tetgen.face_node(index, 2),
+                tetgen.face_marker(index)
+            );
+            assert_eq!(lines[1 + index], correct);
+        }
+
+        // the files can be read back
+        let file_path = "/tmp/tritet/test_write_tetgen.node";
+        let mut tetgen = read_tetgen_node(file_path)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        Ok(())
+    }
+}
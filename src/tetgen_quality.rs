@@ -0,0 +1,551 @@
+use crate::conversion::to_g;
+use crate::{Error, Tetgen};
+use std::fmt;
+
+/// Holds the upper bounds of the bins of the radius-edge ratio histogram of tetrahedra (as in TetGen)
+///
+/// The last bin (index 11) holds the ratios greater than the last bound.
+pub const TET_RADIUS_EDGE_RATIO_BINS: [f64; 11] = [0.707, 1.0, 1.1, 1.2, 1.4, 1.6, 1.8, 2.0, 2.5, 3.0, 10.0];
+
+/// Holds the upper bounds of the bins of the aspect ratio histogram of tetrahedra (as in TetGen)
+///
+/// The last bin (index 11) holds the aspect ratios greater than the last bound.
+pub const TET_ASPECT_RATIO_BINS: [f64; 11] = [1.5, 2.0, 2.5, 3.0, 4.0, 6.0, 10.0, 15.0, 25.0, 50.0, 100.0];
+
+/// Holds the lower bounds of the bins of the dihedral angle histogram (degrees; as in TetGen)
+///
+/// The bins are 0-5, 5-10, 10-20, ..., 70-80, 80-110, 110-120, ..., 160-170, 170-175, and 175-180.
+pub const TET_DIHEDRAL_ANGLE_BINS: [f64; 18] = [
+    0.0, 5.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 110.0, 120.0, 130.0, 140.0, 150.0, 160.0, 170.0, 175.0,
+];
+
+/// Defines the default smallest dihedral angle (degrees) below which a well-shaped tetrahedron is a sliver
+pub const TET_SLIVER_MIN_DIHEDRAL_DEFAULT: f64 = 5.0;
+
+/// Defines the largest radius-edge ratio of a sliver (the default quality bound of TetGen)
+///
+/// Tetrahedra with a larger ratio have a short edge (needles, wedges, caps) and are not counted as slivers.
+pub const TET_SLIVER_MAX_RADIUS_EDGE_RATIO: f64 = 2.0;
+
+/// Defines the tolerance to flag degenerate tetrahedra (relative to the cube of the longest edge)
+pub const TET_DEGENERATE_TOLERANCE: f64 = 1e-12;
+
+/// Holds the quality metrics of a tetrahedron
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TetMetrics {
+    /// Volume (positive if the fourth node lies on the counterclockwise side of the first three nodes, as generated by TetGen)
+    pub volume: f64,
+
+    /// Radius of the circumsphere divided by the shortest edge (√6/4 ≈ 0.6124 for the regular tetrahedron)
+    pub radius_edge_ratio: f64,
+
+    /// Smallest dihedral angle (degrees)
+    pub min_dihedral: f64,
+
+    /// Largest dihedral angle (degrees)
+    pub max_dihedral: f64,
+
+    /// Longest edge divided by the smallest height (√(3/2) ≈ 1.2247 for the regular tetrahedron)
+    pub aspect_ratio: f64,
+
+    /// The volume is negative (the nodes are ordered in the opposite orientation)
+    pub inverted: bool,
+
+    /// The volume is (nearly) zero; i.e., the nodes are coplanar, collinear, or coincident
+    pub degenerate: bool,
+
+    /// The smallest dihedral angle is too small although the radius-edge ratio is bounded
+    pub sliver: bool,
+}
+
+/// Holds the quality metrics of a mesh of tetrahedra
+///
+/// The statistics are equivalent to the ones printed by TetGen with the `-V` switch
+/// (except the face angles). The [fmt::Display] implementation prints the statistics in the same layout.
+///
+/// # Warning
+///
+/// The degenerate tetrahedra are not included in the statistics and histograms,
+/// except for the smallest and largest volumes.
+#[derive(Clone, Debug)]
+pub struct TetQuality {
+    /// Metrics of each tetrahedron
+    pub tets: Vec<TetMetrics>,
+
+    /// Smallest volume
+    pub min_volume: f64,
+
+    /// Largest volume
+    pub max_volume: f64,
+
+    /// Shortest edge
+    pub min_edge: f64,
+
+    /// Longest edge
+    pub max_edge: f64,
+
+    /// Smallest aspect ratio
+    pub min_aspect_ratio: f64,
+
+    /// Largest aspect ratio
+    pub max_aspect_ratio: f64,
+
+    /// Largest radius-edge ratio
+    pub max_radius_edge_ratio: f64,
+
+    /// Smallest dihedral angle (degrees)
+    pub min_dihedral: f64,
+
+    /// Largest dihedral angle (degrees)
+    pub max_dihedral: f64,
+
+    /// Number of tetrahedra in each bin of radius-edge ratios (see [TET_RADIUS_EDGE_RATIO_BINS])
+    pub radius_edge_ratio_histogram: [usize; 12],
+
+    /// Number of tetrahedra in each bin of aspect ratios (see [TET_ASPECT_RATIO_BINS])
+    pub aspect_ratio_histogram: [usize; 12],
+
+    /// Number of smallest and largest dihedral angles (two per tetrahedron) in each bin (see [TET_DIHEDRAL_ANGLE_BINS])
+    pub dihedral_histogram: [usize; 18],
+
+    /// Indices of the inverted tetrahedra
+    pub inverted: Vec<usize>,
+
+    /// Indices of the degenerate tetrahedra
+    pub degenerate: Vec<usize>,
+
+    /// Indices of the slivers
+    pub slivers: Vec<usize>,
+}
+
+impl Tetgen {
+    /// Computes the quality metrics of the generated tetrahedra
+    ///
+    /// Only the corners of the tetrahedra are used (i.e., the middle nodes are ignored).
+    ///
+    /// # Input
+    ///
+    /// * `sliver_min_dihedral` -- The smallest dihedral angle (degrees) below which a tetrahedron
+    ///   with radius-edge ratio smaller than [TET_SLIVER_MAX_RADIUS_EDGE_RATIO] is a sliver
+    ///   (the default is [TET_SLIVER_MIN_DIHEDRAL_DEFAULT])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Tetgen};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut tetgen = Tetgen::new(8, None, None, None)?;
+    ///     tetgen
+    ///         .set_point(0, 0.0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0, 0.0)?
+    ///         .set_point(3, 0.0, 1.0, 0.0)?
+    ///         .set_point(4, 0.0, 0.0, 1.0)?
+    ///         .set_point(5, 1.0, 0.0, 1.0)?
+    ///         .set_point(6, 1.0, 1.0, 1.0)?
+    ///         .set_point(7, 0.0, 1.0, 1.0)?;
+    ///     tetgen.generate_delaunay(false)?;
+    ///     let quality = tetgen.quality(None)?;
+    ///     assert!(quality.inverted.is_empty());
+    ///     println!("{}", quality);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn quality(&self, sliver_min_dihedral: Option<f64>) -> Result<TetQuality, Error> {
+        let ntet = self.ntet();
+        if ntet < 1 {
+            return Err(Error::InvalidInput("there are no tetrahedra to evaluate"));
+        }
+        let sliver_min_dihedral = sliver_min_dihedral.unwrap_or(TET_SLIVER_MIN_DIHEDRAL_DEFAULT);
+        let mut quality = TetQuality {
+            tets: Vec::with_capacity(ntet),
+            min_volume: f64::MAX,
+            max_volume: f64::MIN,
+            min_edge: f64::MAX,
+            max_edge: 0.0,
+            min_aspect_ratio: f64::MAX,
+            max_aspect_ratio: 0.0,
+            max_radius_edge_ratio: 0.0,
+            min_dihedral: 180.0,
+            max_dihedral: 0.0,
+            radius_edge_ratio_histogram: [0; 12],
+            aspect_ratio_histogram: [0; 12],
+            dihedral_histogram: [0; 18],
+            inverted: Vec::new(),
+            degenerate: Vec::new(),
+            slivers: Vec::new(),
+        };
+        for (index, x) in self.tet_points().enumerate() {
+            let metrics = tet_metrics(&x, sliver_min_dihedral);
+            quality.min_volume = f64::min(quality.min_volume, metrics.volume);
+            quality.max_volume = f64::max(quality.max_volume, metrics.volume);
+            if metrics.inverted {
+                quality.inverted.push(index);
+            }
+            if metrics.degenerate {
+                quality.degenerate.push(index);
+                quality.tets.push(metrics);
+                continue;
+            }
+            if metrics.sliver {
+                quality.slivers.push(index);
+            }
+            for i in 0..4 {
+                for j in (i + 1)..4 {
+                    let length = norm(&sub(&x[j], &x[i]));
+                    quality.min_edge = f64::min(quality.min_edge, length);
+                    quality.max_edge = f64::max(quality.max_edge, length);
+                }
+            }
+            quality.min_aspect_ratio = f64::min(quality.min_aspect_ratio, metrics.aspect_ratio);
+            quality.max_aspect_ratio = f64::max(quality.max_aspect_ratio, metrics.aspect_ratio);
+            quality.max_radius_edge_ratio = f64::max(quality.max_radius_edge_ratio, metrics.radius_edge_ratio);
+            quality.min_dihedral = f64::min(quality.min_dihedral, metrics.min_dihedral);
+            quality.max_dihedral = f64::max(quality.max_dihedral, metrics.max_dihedral);
+            quality.radius_edge_ratio_histogram[ratio_bin(metrics.radius_edge_ratio, &TET_RADIUS_EDGE_RATIO_BINS)] += 1;
+            quality.aspect_ratio_histogram[ratio_bin(metrics.aspect_ratio, &TET_ASPECT_RATIO_BINS)] += 1;
+            quality.dihedral_histogram[dihedral_bin(metrics.min_dihedral)] += 1;
+            quality.dihedral_histogram[dihedral_bin(metrics.max_dihedral)] += 1;
+            quality.tets.push(metrics);
+        }
+        if quality.degenerate.len() == ntet {
+            quality.min_edge = 0.0;
+            quality.min_aspect_ratio = 0.0;
+            quality.min_dihedral = 0.0;
+        }
+        Ok(quality)
+    }
+}
+
+/// Computes the metrics of a tetrahedron given the coordinates of its corners
+fn tet_metrics(x: &[[f64; 3]], sliver_min_dihedral: f64) -> TetMetrics {
+    // edges
+    let a = sub(&x[1], &x[0]);
+    let b = sub(&x[2], &x[0]);
+    let c = sub(&x[3], &x[0]);
+    let mut shortest = f64::MAX;
+    let mut longest = 0.0;
+    for i in 0..4 {
+        for j in (i + 1)..4 {
+            let length = norm(&sub(&x[j], &x[i]));
+            shortest = f64::min(shortest, length);
+            longest = f64::max(longest, length);
+        }
+    }
+
+    // volume (TetGen generates tetrahedra with positive triple product)
+    let triple = dot(&a, &cross(&b, &c));
+    let volume = triple / 6.0;
+    let degenerate = f64::abs(volume) <= TET_DEGENERATE_TOLERANCE * longest * longest * longest;
+    let inverted = !degenerate && volume < 0.0;
+
+    // outward normals of the faces opposite to each corner (length = twice the area)
+    let mut normals = [[0.0; 3]; 4];
+    let mut max_area = 0.0;
+    for k in 0..4 {
+        let (p, q, r) = ((k + 1) % 4, (k + 2) % 4, (k + 3) % 4);
+        let mut n = cross(&sub(&x[q], &x[p]), &sub(&x[r], &x[p]));
+        if dot(&n, &sub(&x[p], &x[k])) < 0.0 {
+            n = [-n[0], -n[1], -n[2]];
+        }
+        max_area = f64::max(max_area, 0.5 * norm(&n));
+        normals[k] = n;
+    }
+
+    // dihedral angles at each edge (between the faces opposite to the other two corners)
+    let mut min_dihedral = 180.0;
+    let mut max_dihedral = 0.0;
+    for k in 0..4 {
+        for l in (k + 1)..4 {
+            let (nk, nl) = (&normals[k], &normals[l]);
+            let angle = 180.0 - f64::atan2(norm(&cross(nk, nl)), dot(nk, nl)).to_degrees();
+            min_dihedral = f64::min(min_dihedral, angle);
+            max_dihedral = f64::max(max_dihedral, angle);
+        }
+    }
+
+    // circumradius
+    let (aa, bb, cc) = (dot(&a, &a), dot(&b, &b), dot(&c, &c));
+    let (bxc, cxa, axb) = (cross(&b, &c), cross(&c, &a), cross(&a, &b));
+    let center = [
+        aa * bxc[0] + bb * cxa[0] + cc * axb[0],
+        aa * bxc[1] + bb * cxa[1] + cc * axb[1],
+        aa * bxc[2] + bb * cxa[2] + cc * axb[2],
+    ];
+    let circumradius = norm(&center) / (2.0 * f64::abs(triple));
+
+    // ratios
+    let min_height = 3.0 * f64::abs(volume) / max_area;
+    let aspect_ratio = longest / min_height;
+    let radius_edge_ratio = circumradius / shortest;
+    let sliver =
+        !degenerate && min_dihedral < sliver_min_dihedral && radius_edge_ratio <= TET_SLIVER_MAX_RADIUS_EDGE_RATIO;
+    TetMetrics {
+        volume,
+        radius_edge_ratio,
+        min_dihedral,
+        max_dihedral,
+        aspect_ratio,
+        inverted,
+        degenerate,
+        sliver,
+    }
+}
+
+/// Returns the index of the bin of a ratio histogram (the last bin holds the values above the last bound)
+fn ratio_bin(value: f64, bounds: &[f64; 11]) -> usize {
+    bounds.iter().position(|bound| value <= *bound).unwrap_or(11)
+}
+
+/// Returns the index of the bin of the dihedral angle histogram
+fn dihedral_bin(angle: f64) -> usize {
+    TET_DIHEDRAL_ANGLE_BINS
+        .iter()
+        .rposition(|bound| angle >= *bound)
+        .unwrap_or(0)
+}
+
+fn sub(u: &[f64; 3], v: &[f64; 3]) -> [f64; 3] {
+    [u[0] - v[0], u[1] - v[1], u[2] - v[2]]
+}
+
+fn dot(u: &[f64; 3], v: &[f64; 3]) -> f64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn cross(u: &[f64; 3], v: &[f64; 3]) -> [f64; 3] {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+fn norm(u: &[f64; 3]) -> f64 {
+    f64::sqrt(dot(u, u))
+}
+
+impl fmt::Display for TetQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Mesh quality statistics:\n")?;
+        writeln!(
+            f,
+            "  Smallest volume: {:>16}   |  Largest volume: {:>16}",
+            to_g(self.min_volume, 5),
+            to_g(self.max_volume, 5)
+        )?;
+        writeln!(
+            f,
+            "  Shortest edge:   {:>16}   |  Longest edge:   {:>16}",
+            to_g(self.min_edge, 5),
+            to_g(self.max_edge, 5)
+        )?;
+        writeln!(
+            f,
+            "  Smallest aspect ratio:  {:>9}   |  Largest aspect ratio:  {:>9}",
+            to_g(self.min_aspect_ratio, 5),
+            to_g(self.max_aspect_ratio, 5)
+        )?;
+        writeln!(
+            f,
+            "  Smallest dihedral: {:>14}   |  Largest dihedral: {:>14}\n",
+            to_g(self.min_dihedral, 5),
+            to_g(self.max_dihedral, 5)
+        )?;
+        writeln!(f, "  Radius-edge ratio histogram:")?;
+        write_ratio_histogram(f, &TET_RADIUS_EDGE_RATIO_BINS, &self.radius_edge_ratio_histogram)?;
+        writeln!(
+            f,
+            "  (A tetrahedron's radius-edge ratio is its radius of circumsphere divided"
+        )?;
+        writeln!(f, "    by its shortest edge length)\n")?;
+        writeln!(f, "  Aspect ratio histogram:")?;
+        write_ratio_histogram(f, &TET_ASPECT_RATIO_BINS, &self.aspect_ratio_histogram)?;
+        writeln!(
+            f,
+            "  (A tetrahedron's aspect ratio is its longest edge length divided by its"
+        )?;
+        writeln!(f, "    smallest side height)\n")?;
+        writeln!(f, "  Dihedral angle histogram:")?;
+        let bins = &TET_DIHEDRAL_ANGLE_BINS;
+        for i in 0..9 {
+            let right_end = if i == 8 { 180.0 } else { bins[i + 10] };
+            writeln!(
+                f,
+                "     {:>3} - {:>2} degrees:  {:>8}      |    {:>3} - {:>3} degrees:  {:>8}",
+                bins[i],
+                bins[i + 1],
+                self.dihedral_histogram[i],
+                bins[i + 9],
+                right_end,
+                self.dihedral_histogram[i + 9]
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "  Number of slivers:               {:>8}", self.slivers.len())?;
+        writeln!(f, "  Number of inverted tetrahedra:   {:>8}", self.inverted.len())?;
+        writeln!(f, "  Number of degenerate tetrahedra: {:>8}", self.degenerate.len())?;
+        Ok(())
+    }
+}
+
+/// Writes a ratio histogram in two columns (as in TetGen)
+fn write_ratio_histogram(f: &mut fmt::Formatter<'_>, bins: &[f64; 11], h: &[usize; 12]) -> fmt::Result {
+    writeln!(
+        f,
+        "         < {:<6}    :  {:>8}      | {:>6} - {:<6}     :  {:>8}",
+        bins[0], h[0], bins[5], bins[6], h[6]
+    )?;
+    for i in 1..5 {
+        writeln!(
+            f,
+            "  {:>6} - {:<6}    :  {:>8}      | {:>6} - {:<6}     :  {:>8}",
+            bins[i - 1],
+            bins[i],
+            h[i],
+            bins[i + 5],
+            bins[i + 6],
+            h[i + 6]
+        )?;
+    }
+    writeln!(
+        f,
+        "  {:>6} - {:<6}    :  {:>8}      | {:>6} -            :  {:>8}",
+        bins[4], bins[5], h[5], bins[10], h[11]
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{dihedral_bin, ratio_bin, tet_metrics, TET_ASPECT_RATIO_BINS};
+    use crate::test_fixtures::cube;
+    use crate::{Error, Tetgen};
+
+    #[test]
+    fn bins_work() {
+        assert_eq!(ratio_bin(1.2247, &TET_ASPECT_RATIO_BINS), 0);
+        assert_eq!(ratio_bin(1.5, &TET_ASPECT_RATIO_BINS), 0);
+        assert_eq!(ratio_bin(1.6, &TET_ASPECT_RATIO_BINS), 1);
+        assert_eq!(ratio_bin(100.0, &TET_ASPECT_RATIO_BINS), 10);
+        assert_eq!(ratio_bin(100.1, &TET_ASPECT_RATIO_BINS), 11);
+        assert_eq!(dihedral_bin(0.0), 0);
+        assert_eq!(dihedral_bin(4.9), 0);
+        assert_eq!(dihedral_bin(5.0), 1);
+        assert_eq!(dihedral_bin(15.0), 2);
+        assert_eq!(dihedral_bin(79.9), 8);
+        assert_eq!(dihedral_bin(90.0), 9);
+        assert_eq!(dihedral_bin(115.0), 10);
+        assert_eq!(dihedral_bin(172.0), 16);
+        assert_eq!(dihedral_bin(180.0), 17);
+    }
+
+    #[test]
+    fn tet_metrics_works() {
+        // regular tetrahedron with edge = 2√2 (nodes ordered as in TetGen)
+        let x = [[1.0, 1.0, 1.0], [1.0, -1.0, -1.0], [-1.0, -1.0, 1.0], [-1.0, 1.0, -1.0]];
+        let m = tet_metrics(&x, 5.0);
+        assert!(f64::abs(m.volume - 8.0 / 3.0) < 1e-15);
+        assert!(f64::abs(m.radius_edge_ratio - f64::sqrt(6.0) / 4.0) < 1e-15);
+        let dihedral = f64::acos(1.0 / 3.0).to_degrees();
+        assert!(f64::abs(m.min_dihedral - dihedral) < 1e-13);
+        assert!(f64::abs(m.max_dihedral - dihedral) < 1e-13);
+        assert!(f64::abs(m.aspect_ratio - f64::sqrt(1.5)) < 1e-15);
+        assert!(!m.inverted && !m.degenerate && !m.sliver);
+
+        // inverted
+        let x = [[1.0, 1.0, 1.0], [-1.0, -1.0, 1.0], [1.0, -1.0, -1.0], [-1.0, 1.0, -1.0]];
+        let m = tet_metrics(&x, 5.0);
+        assert!(f64::abs(m.volume + 8.0 / 3.0) < 1e-15);
+        assert!(m.inverted && !m.degenerate && !m.sliver);
+
+        // sliver: the corners of a square with one diagonal slightly lifted
+        let h = 0.02;
+        let x = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, h]];
+        let m = tet_metrics(&x, 5.0);
+        assert!(f64::abs(m.volume - h / 6.0) < 1e-15);
+        assert!(m.min_dihedral < 5.0);
+        assert!(m.max_dihedral > 175.0);
+        assert!(m.radius_edge_ratio < 1.0);
+        assert!(!m.inverted && !m.degenerate && m.sliver);
+        let m = tet_metrics(&x, 1.0);
+        assert!(!m.sliver);
+
+        // cap: small dihedral angle, but large radius-edge ratio
+        let x = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.3, 0.3, 0.01]];
+        let m = tet_metrics(&x, 5.0);
+        assert!(m.min_dihedral < 5.0);
+        assert!(m.radius_edge_ratio > 2.0);
+        assert!(!m.sliver);
+
+        // degenerate: coplanar
+        let x = [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]];
+        let m = tet_metrics(&x, 5.0);
+        assert!(!m.inverted && m.degenerate && !m.sliver);
+    }
+
+    #[test]
+    fn quality_captures_some_errors() -> Result<(), Error> {
+        let tetgen = Tetgen::new(4, None, None, None)?;
+        assert_eq!(
+            tetgen.quality(None).err(),
+            Some(Error::InvalidInput("there are no tetrahedra to evaluate"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn quality_works() -> Result<(), Error> {
+        let mut tetgen = Tetgen::new(4, None, None, None)?;
+        tetgen
+            .set_point(0, 0.0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0, 0.0)?
+            .set_point(2, 0.0, 1.0, 0.0)?
+            .set_point(3, 0.0, 0.0, 1.0)?;
+        tetgen.generate_delaunay(false)?;
+        assert_eq!(tetgen.ntet(), 1);
+        let quality = tetgen.quality(None)?;
+        let m = &quality.tets[0];
+        assert!(f64::abs(m.volume - 1.0 / 6.0) < 1e-15);
+        assert!(f64::abs(m.min_dihedral - f64::acos(1.0 / f64::sqrt(3.0)).to_degrees()) < 1e-13);
+        assert!(f64::abs(m.max_dihedral - 90.0) < 1e-13);
+        assert!(f64::abs(m.radius_edge_ratio - f64::sqrt(3.0) / 2.0) < 1e-15); // (√3/2) / 1
+        assert!(f64::abs(m.aspect_ratio - f64::sqrt(2.0) / (1.0 / f64::sqrt(3.0))) < 1e-15);
+        assert_eq!(quality.min_volume, m.volume);
+        assert_eq!(quality.max_volume, m.volume);
+        assert_eq!(quality.min_edge, 1.0);
+        assert_eq!(quality.max_edge, f64::sqrt(2.0));
+        assert_eq!(quality.radius_edge_ratio_histogram[1], 1); // 0.707 - 1
+        assert_eq!(quality.aspect_ratio_histogram[2], 1); // 2 - 2.5 (√6 ≈ 2.449)
+        assert_eq!(quality.dihedral_histogram[6], 1); // 50 - 60 (54.7)
+        assert_eq!(quality.dihedral_histogram[8] + quality.dihedral_histogram[9], 1); // 90 (round-off)
+        assert!(quality.inverted.is_empty());
+        assert!(quality.degenerate.is_empty());
+        assert!(quality.slivers.is_empty());
+        let report = format!("{}", quality);
+        assert!(report.contains("  Shortest edge:                  1   |  Longest edge:             1.4142\n"));
+        assert!(report.contains("      50 - 60 degrees:         1      |    160 - 170 degrees:         0\n"));
+        assert!(report.contains("  Number of slivers:                      0\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn quality_works_with_generated_mesh() -> Result<(), Error> {
+        let mut tetgen = cube()?;
+        tetgen.generate_mesh(false, true, Some(0.01), None)?;
+        let quality = tetgen.quality(None)?;
+        assert_eq!(quality.tets.len(), tetgen.ntet());
+        assert!(quality.inverted.is_empty());
+        assert!(quality.degenerate.is_empty());
+        assert!(quality.max_volume <= 0.01);
+        assert!(quality.max_radius_edge_ratio <= 2.0 + 1e-10);
+        let total: f64 = quality.tets.iter().map(|t| t.volume).sum();
+        assert!(f64::abs(total - 1.0) < 1e-14);
+        assert_eq!(quality.radius_edge_ratio_histogram.iter().sum::<usize>(), tetgen.ntet());
+        assert_eq!(quality.aspect_ratio_histogram.iter().sum::<usize>(), tetgen.ntet());
+        assert_eq!(quality.dihedral_histogram.iter().sum::<usize>(), 2 * tetgen.ntet());
+        Ok(())
+    }
+}
@@ -0,0 +1,382 @@
+use crate::triangle_validation::write_list;
+use crate::{Error, Stage, Tetgen};
+use std::fmt;
+
+/// Holds the tolerance to flag degenerate and non-planar facets, relative to the size of the facet
+///
+/// A polygon is degenerate if twice its area is not greater than this tolerance times the squared
+/// diagonal of its bounding box. A facet is non-planar if the distance of any of its points to the
+/// plane of its first non-degenerate polygon is greater than this tolerance times the diagonal of
+/// its bounding box.
+pub const PLC_PLANARITY_TOLERANCE: f64 = 1e-8;
+
+/// Holds the problems found in the Piecewise Linear Complex (PLC) given to Tetgen
+///
+/// The report is returned by [Tetgen::validate_plc]. Tetgen may fail with an internal
+/// error or silently generate a wrong mesh if any of these problems is present.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlcReport {
+    /// Pairs of facets that intersect each other (see [Tetgen::check_intersections])
+    pub intersecting_facets: Vec<[usize; 2]>,
+
+    /// Facets whose polygons are all degenerate; e.g., their points are collinear (see [PLC_PLANARITY_TOLERANCE])
+    pub degenerate_facets: Vec<usize>,
+
+    /// Facets whose points are not on the same plane (see [PLC_PLANARITY_TOLERANCE])
+    pub non_planar_facets: Vec<usize>,
+
+    /// Facets with a polygon that repeats a point
+    pub repeated_point_facets: Vec<usize>,
+
+    /// Edges (pairs of point IDs) that belong to a single facet; i.e., the surface is not watertight
+    pub open_edges: Vec<[usize; 2]>,
+}
+
+impl PlcReport {
+    /// Returns true if no problem has been found
+    pub fn is_valid(&self) -> bool {
+        self.intersecting_facets.is_empty()
+            && self.degenerate_facets.is_empty()
+            && self.non_planar_facets.is_empty()
+            && self.repeated_point_facets.is_empty()
+            && self.open_edges.is_empty()
+    }
+}
+
+impl Tetgen {
+    /// Validates the Piecewise Linear Complex (PLC) before calling Tetgen
+    ///
+    /// Detects degenerate facets (all polygons with collinear points), non-planar facets, facets
+    /// repeating a point, open edges (the surface is not watertight), and, if none of the previous
+    /// problems with the facets is found, intersecting facets with [Tetgen::check_intersections].
+    ///
+    /// Call [Tetgen::set_plc_validation] to run this function automatically in `generate_mesh`.
+    ///
+    /// **Note:** The output of a previous generation is kept.
+    ///
+    /// # Warning
+    ///
+    /// Polygons within a facet that do not bound a hole (e.g., internal constraints) are
+    /// accepted by Tetgen; however, their edges are reported as open edges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Tetgen};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let tetgen = Tetgen::from_input(
+    ///         &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    ///         &vec![vec![0, 2, 1], vec![0, 1, 3], vec![0, 3, 2], vec![1, 3, 3]],
+    ///         &[],
+    ///         &[],
+    ///     )?;
+    ///     let report = tetgen.validate_plc()?;
+    ///     assert_eq!(report.repeated_point_facets, &[3]);
+    ///     assert_eq!(report.open_edges, &[[1, 2], [2, 3]]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn validate_plc(&self) -> Result<PlcReport, Error> {
+        self.check_facets_set(Stage::TetgenMesh)?;
+        let points: Vec<[f64; 3]> = self
+            .input_points()
+            .chunks_exact(3)
+            .map(|x| [x[0], x[1], x[2]])
+            .collect();
+        let facets = self.input_facets();
+        let mut report = PlcReport::default();
+        let mut edges: Vec<[usize; 2]> = Vec::new();
+        for (index, polygons) in facets.iter().enumerate() {
+            // repeated points
+            if polygons.iter().any(|ids| {
+                let mut sorted = ids.clone();
+                sorted.sort();
+                sorted.windows(2).any(|w| w[0] == w[1])
+            }) {
+                report.repeated_point_facets.push(index);
+            }
+
+            // degeneracy and planarity
+            match facet_plane(&points, polygons) {
+                Some((origin, normal)) => {
+                    if !is_planar(&points, polygons, origin, &normal) {
+                        report.non_planar_facets.push(index);
+                    }
+                }
+                None => report.degenerate_facets.push(index),
+            }
+
+            // edges of this facet (counted once per facet)
+            let mut facet_edges: Vec<[usize; 2]> = polygons
+                .iter()
+                .flat_map(|ids| {
+                    (0..ids.len()).map(move |m| {
+                        let (a, b) = (ids[m], ids[(m + 1) % ids.len()]);
+                        [usize::min(a, b), usize::max(a, b)]
+                    })
+                })
+                .filter(|[a, b]| a != b)
+                .collect();
+            facet_edges.sort();
+            facet_edges.dedup();
+            edges.extend(facet_edges);
+        }
+
+        // open edges
+        edges.sort();
+        let mut start = 0;
+        while start < edges.len() {
+            let mut end = start + 1;
+            while end < edges.len() && edges[end] == edges[start] {
+                end += 1;
+            }
+            if end - start == 1 {
+                report.open_edges.push(edges[start]);
+            }
+            start = end;
+        }
+
+        // intersections (Tetgen may fail if the facets are ill-defined)
+        if !facets.is_empty()
+            && report.degenerate_facets.is_empty()
+            && report.repeated_point_facets.is_empty()
+            && report.non_planar_facets.is_empty()
+        {
+            report.intersecting_facets = self.check_intersections(false)?;
+        }
+        Ok(report)
+    }
+}
+
+/// Returns the origin (point ID) and the normal vector of the first non-degenerate polygon of a facet
+///
+/// Returns None if all polygons are degenerate (e.g., their points are collinear).
+fn facet_plane(points: &[[f64; 3]], polygons: &[Vec<usize>]) -> Option<(usize, [f64; 3])> {
+    polygons.iter().find_map(|ids| {
+        // normal vector by Newell's method (its length is twice the area)
+        let mut normal = [0.0; 3];
+        for m in 0..ids.len() {
+            let a = &points[ids[m]];
+            let b = &points[ids[(m + 1) % ids.len()]];
+            normal[0] += (a[1] - b[1]) * (a[2] + b[2]);
+            normal[1] += (a[2] - b[2]) * (a[0] + b[0]);
+            normal[2] += (a[0] - b[0]) * (a[1] + b[1]);
+        }
+        let diagonal = bounding_box_diagonal(points, ids);
+        if ids.len() < 3 || norm(&normal) <= PLC_PLANARITY_TOLERANCE * diagonal * diagonal {
+            None
+        } else {
+            Some((ids[0], normal))
+        }
+    })
+}
+
+/// Returns true if all the points of a facet are on the plane given by an origin (point ID) and a normal vector
+fn is_planar(points: &[[f64; 3]], polygons: &[Vec<usize>], origin: usize, normal: &[f64; 3]) -> bool {
+    let ids: Vec<usize> = polygons.iter().flatten().copied().collect();
+    let diagonal = bounding_box_diagonal(points, &ids);
+    let length = norm(normal);
+    let origin = &points[origin];
+    ids.iter().all(|p| {
+        let x = &points[*p];
+        let distance = (0..3).map(|dim| normal[dim] * (x[dim] - origin[dim])).sum::<f64>() / length;
+        f64::abs(distance) <= PLC_PLANARITY_TOLERANCE * diagonal
+    })
+}
+
+/// Returns the diagonal of the bounding box of some points
+fn bounding_box_diagonal(points: &[[f64; 3]], ids: &[usize]) -> f64 {
+    let mut min = [f64::MAX; 3];
+    let mut max = [f64::MIN; 3];
+    for p in ids {
+        for dim in 0..3 {
+            min[dim] = f64::min(min[dim], points[*p][dim]);
+            max[dim] = f64::max(max[dim], points[*p][dim]);
+        }
+    }
+    norm(&[max[0] - min[0], max[1] - min[1], max[2] - min[2]])
+}
+
+/// Returns the Euclidean norm of a vector
+fn norm(v: &[f64; 3]) -> f64 {
+    f64::sqrt(v[0] * v[0] + v[1] * v[1] + v[2] * v[2])
+}
+
+impl fmt::Display for PlcReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "no problems found");
+        }
+        let singles: [(&str, &Vec<usize>); 3] = [
+            ("degenerate facets", &self.degenerate_facets),
+            ("non-planar facets", &self.non_planar_facets),
+            ("facets repeating a point", &self.repeated_point_facets),
+        ];
+        let mut first = true;
+        if !self.intersecting_facets.is_empty() {
+            write!(f, "intersecting facets: ")?;
+            write_list(f, &self.intersecting_facets, |f, [i, j]| write!(f, "{} & {}", i, j))?;
+            first = false;
+        }
+        for (name, list) in singles {
+            if !list.is_empty() {
+                write!(f, "{}{}: ", if first { "" } else { "; " }, name)?;
+                write_list(f, list, |f, i| write!(f, "{}", i))?;
+                first = false;
+            }
+        }
+        if !self.open_edges.is_empty() {
+            write!(f, "{}open edges: ", if first { "" } else { "; " })?;
+            write_list(f, &self.open_edges, |f, [a, b]| write!(f, "{}-{}", a, b))?;
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{facet_plane, is_planar, PlcReport};
+    use crate::test_fixtures::{cube_facets, CUBE_POINTS};
+    use crate::{Error, Item, Stage, Tetgen};
+
+    #[test]
+    fn facet_plane_works() {
+        let mut points = CUBE_POINTS.to_vec();
+        assert_eq!(facet_plane(&points, &[vec![0, 1, 2, 3]]), Some((0, [0.0, 0.0, 2.0])));
+        assert_eq!(facet_plane(&points, &[vec![0, 0, 1]]), None);
+        assert_eq!(facet_plane(&points, &[vec![0, 1]]), None);
+        points.push([0.5, 0.0, 0.0]);
+        assert_eq!(facet_plane(&points, &[vec![0, 8, 1]]), None); // collinear
+        assert_eq!(
+            facet_plane(&points, &[vec![0, 8, 1], vec![1, 2, 3]]),
+            Some((1, [0.0, 0.0, 1.0]))
+        );
+    }
+
+    #[test]
+    fn is_planar_works() {
+        let mut points = CUBE_POINTS.to_vec();
+        let normal = [0.0, 0.0, 1.0];
+        assert!(is_planar(&points, &[vec![0, 1, 2, 3]], 0, &normal));
+        assert!(is_planar(&points, &[vec![0, 1, 2], vec![0, 2, 3]], 0, &normal));
+        assert!(!is_planar(&points, &[vec![0, 1, 2], vec![0, 2, 6]], 0, &normal));
+        points[3][2] = 1e-10;
+        assert!(is_planar(&points, &[vec![0, 1, 2, 3]], 0, &normal));
+        points[3][2] = 1e-6;
+        assert!(!is_planar(&points, &[vec![0, 1, 2, 3]], 0, &normal));
+    }
+
+    #[test]
+    fn check_intersections_captures_some_errors() -> Result<(), Error> {
+        let tetgen = Tetgen::from_input(&CUBE_POINTS, &[], &[], &[])?;
+        assert_eq!(
+            tetgen.check_intersections(false).err(),
+            Some(Error::NotAllocated { item: Item::Facet })
+        );
+        let tetgen = Tetgen::new(8, Some(vec![4; 6]), None, None)?;
+        assert_eq!(
+            tetgen.check_intersections(false).err(),
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TetgenMesh,
+                missing: (0..8).collect(),
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_intersections_works() -> Result<(), Error> {
+        // closed cube
+        let mut tetgen = Tetgen::from_input(&CUBE_POINTS, &cube_facets(), &[], &[])?;
+        assert_eq!(tetgen.check_intersections(false)?, &[] as &[[usize; 2]]);
+
+        // the check can be followed by the generation and keeps the mesh
+        tetgen.generate_mesh(false, false, None, None)?;
+        let ntet = tetgen.ntet();
+        assert!(ntet > 0);
+        assert_eq!(tetgen.check_intersections(false)?, &[] as &[[usize; 2]]);
+        assert_eq!(tetgen.ntet(), ntet);
+        assert_eq!(tetgen.validate_plc()?, PlcReport::default());
+        assert_eq!(tetgen.ntet(), ntet);
+
+        // a square cutting through the cube (z = 0.5) and a copy of the bottom facet
+        let mut points = CUBE_POINTS.to_vec();
+        points.extend([[-0.5, -0.5, 0.5], [1.5, -0.5, 0.5], [1.5, 1.5, 0.5], [-0.5, 1.5, 0.5]]);
+        let mut facets = cube_facets();
+        facets.push(vec![8, 9, 10, 11]);
+        facets.push(vec![1, 2, 3, 0]);
+        let tetgen = Tetgen::from_input(&points, &facets, &[], &[])?;
+        assert_eq!(
+            tetgen.check_intersections(false)?,
+            &[[0, 6], [1, 6], [2, 6], [3, 6], [4, 7]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn validate_plc_works() -> Result<(), Error> {
+        let tetgen = Tetgen::from_input(&CUBE_POINTS, &cube_facets(), &[], &[])?;
+        let report = tetgen.validate_plc()?;
+        assert_eq!(report, PlcReport::default());
+        assert_eq!(format!("{}", report), "no problems found");
+
+        // open, non-planar and repeating a point
+        let mut points = CUBE_POINTS.to_vec();
+        points[6][0] = 1.1;
+        let mut facets = cube_facets();
+        facets[2] = vec![0, 1, 1, 5, 4];
+        facets.pop(); // no top facet
+        let tetgen = Tetgen::from_input(&points, &facets, &[], &[])?;
+        let report = tetgen.validate_plc()?;
+        assert_eq!(report.intersecting_facets, &[] as &[[usize; 2]]); // not checked
+        assert_eq!(report.degenerate_facets, &[] as &[usize]);
+        assert_eq!(report.non_planar_facets, &[1]);
+        assert_eq!(report.repeated_point_facets, &[2]);
+        assert_eq!(report.open_edges, &[[4, 5], [4, 7], [5, 6], [6, 7]]);
+        assert_eq!(
+            format!("{}", report),
+            "non-planar facets: 1; facets repeating a point: 2; open edges: 4-5, 4-7, 5-6, 6-7"
+        );
+
+        // collinear (not non-planar)
+        let mut points = CUBE_POINTS.to_vec();
+        points.push([0.5, 0.0, 0.0]);
+        let mut facets = cube_facets();
+        facets.push(vec![0, 8, 1]);
+        let tetgen = Tetgen::from_input(&points, &facets, &[], &[])?;
+        let report = tetgen.validate_plc()?;
+        assert_eq!(report.intersecting_facets, &[] as &[[usize; 2]]); // not checked
+        assert_eq!(report.degenerate_facets, &[6]);
+        assert_eq!(report.non_planar_facets, &[] as &[usize]);
+        assert_eq!(format!("{}", report), "degenerate facets: 6; open edges: 0-8, 1-8");
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_validates_the_plc() -> Result<(), Error> {
+        let mut points = CUBE_POINTS.to_vec();
+        points.extend([[-0.5, -0.5, 0.5], [1.5, -0.5, 0.5], [1.5, 1.5, 0.5], [-0.5, 1.5, 0.5]]);
+        let mut facets = cube_facets();
+        facets.push(vec![8, 9, 10, 11]);
+        let mut tetgen = Tetgen::from_input(&points, &facets, &[], &[])?;
+        tetgen.set_plc_validation(true);
+        let error = tetgen.generate_mesh(false, false, None, None).err().unwrap();
+        assert_eq!(
+            format!("{}", error),
+            "the PLC is invalid: intersecting facets: 0 & 6, 1 & 6, 2 & 6, 3 & 6; \
+             open edges: 8-9, 8-11, 9-10, 10-11"
+        );
+
+        // valid PLC
+        let mut tetgen = Tetgen::from_input(&CUBE_POINTS, &cube_facets(), &[], &[])?;
+        tetgen
+            .set_plc_validation(true)
+            .generate_mesh(false, false, None, None)?;
+        assert!(tetgen.ntet() > 0);
+        Ok(())
+    }
+}
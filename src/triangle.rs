@@ -1,12 +1,13 @@
+use crate::bitset::BitSet;
 use crate::constants;
-use crate::conversion::to_i32;
-use crate::StrError;
+use crate::conversion::{to_i32, to_slice};
+use crate::{Error, Item, Stage};
 
 #[cfg(feature = "plotpy")]
-use std::collections::HashMap;
+use crate::mesh;
 
 #[cfg(feature = "plotpy")]
-use plotpy::{Canvas, Curve, Plot, PolyCode, Text};
+use plotpy::{Canvas, Curve, Plot, PolyCode};
 
 #[repr(C)]
 pub(crate) struct ExtTriangle {
@@ -19,6 +20,7 @@ extern "C" {
     fn drop_triangle(triangle: *mut ExtTriangle);
     fn set_point(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64) -> i32;
     fn set_segment(triangle: *mut ExtTriangle, index: i32, a: i32, b: i32) -> i32;
+    fn set_segment_marker(triangle: *mut ExtTriangle, index: i32, marker: i32) -> i32;
     fn set_region(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64, attribute: i32, max_area: f64) -> i32;
     fn set_hole(triangle: *mut ExtTriangle, index: i32, x: f64, y: f64) -> i32;
     fn run_delaunay(triangle: *mut ExtTriangle, verbose: i32) -> i32;
@@ -30,12 +32,39 @@ extern "C" {
         global_max_area: f64,
         global_min_angle: f64,
     ) -> i32;
+    fn run_refine(
+        triangle: *mut ExtTriangle,
+        verbose: i32,
+        quadratic: i32,
+        max_areas: *const f64,
+        global_min_angle: f64,
+    ) -> i32;
     fn get_npoint(triangle: *mut ExtTriangle) -> i32;
     fn get_ntriangle(triangle: *mut ExtTriangle) -> i32;
     fn get_ncorner(triangle: *mut ExtTriangle) -> i32;
     fn get_point(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
     fn get_triangle_corner(triangle: *mut ExtTriangle, index: i32, corner: i32) -> i32;
     fn get_triangle_attribute(triangle: *mut ExtTriangle, index: i32) -> i32;
+    fn get_triangle_neighbor(triangle: *mut ExtTriangle, index: i32, side: i32) -> i32;
+    fn get_point_marker(triangle: *mut ExtTriangle, index: i32) -> i32;
+    fn get_nsegment(triangle: *mut ExtTriangle) -> i32;
+    fn get_segment_point(triangle: *mut ExtTriangle, index: i32, side: i32) -> i32;
+    fn get_segment_marker(triangle: *mut ExtTriangle, index: i32) -> i32;
+    fn get_point_list(triangle: *mut ExtTriangle) -> *const f64;
+    fn get_point_marker_list(triangle: *mut ExtTriangle) -> *const i32;
+    fn get_triangle_list(triangle: *mut ExtTriangle) -> *const i32;
+    fn get_ntriangle_attribute(triangle: *mut ExtTriangle) -> i32;
+    fn get_triangle_attribute_list(triangle: *mut ExtTriangle) -> *const f64;
+    fn get_neighbor_list(triangle: *mut ExtTriangle) -> *const i32;
+    fn get_segment_list(triangle: *mut ExtTriangle) -> *const i32;
+    fn get_segment_marker_list(triangle: *mut ExtTriangle) -> *const i32;
+    fn get_input_point_list(triangle: *mut ExtTriangle) -> *const f64;
+    fn get_input_segment_list(triangle: *mut ExtTriangle) -> *const i32;
+    fn get_input_region_list(triangle: *mut ExtTriangle) -> *const f64;
+    fn get_input_hole_list(triangle: *mut ExtTriangle) -> *const f64;
+    fn get_nedge(triangle: *mut ExtTriangle) -> i32;
+    fn get_edge_point(triangle: *mut ExtTriangle, index: i32, side: i32) -> i32;
+    fn get_edge_marker(triangle: *mut ExtTriangle, index: i32) -> i32;
     fn get_voronoi_npoint(triangle: *mut ExtTriangle) -> i32;
     fn get_voronoi_point(triangle: *mut ExtTriangle, index: i32, dim: i32) -> f64;
     fn get_voronoi_nedge(triangle: *mut ExtTriangle) -> i32;
@@ -62,9 +91,9 @@ pub enum VoronoiEdgePoint {
 /// ## Delaunay triangulation
 ///
 /// ```
-/// use tritet::{StrError, Triangle};
+/// use tritet::{Error, Triangle};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // allocate data for 10 points
 ///     let mut triangle = Triangle::new(10, None, None, None)?;
 ///
@@ -99,9 +128,9 @@ pub enum VoronoiEdgePoint {
 /// ## Voronoi tessellation
 ///
 /// ```
-/// use tritet::{StrError, Triangle};
+/// use tritet::{Error, Triangle};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // allocate data for 10 points
 ///     let mut triangle = Triangle::new(10, None, None, None)?;
 ///
@@ -136,9 +165,9 @@ pub enum VoronoiEdgePoint {
 /// ## Mesh generation
 ///
 /// ```
-/// use tritet::{StrError, Triangle};
+/// use tritet::{Error, Triangle};
 ///
-/// fn main() -> Result<(), StrError> {
+/// fn main() -> Result<(), Error> {
 ///     // allocate data for 12 points, 10 segments, 2 regions, and 1 hole
 ///     let mut triangle = Triangle::new(12, Some(10), Some(2), Some(1))?;
 ///
@@ -220,10 +249,11 @@ pub struct Triangle {
     nsegment: Option<usize>,        // number of segments
     nregion: Option<usize>,         // number of regions
     nhole: Option<usize>,           // number of holes
-    all_points_set: bool,           // indicates that all points have been set
-    all_segments_set: bool,         // indicates that all segments have been set
-    all_regions_set: bool,          // indicates that all regions have been set
-    all_holes_set: bool,            // indicates that all holes have been set
+    points_set: BitSet,             // indicates which points have been set
+    segments_set: BitSet,           // indicates which segments have been set
+    regions_set: BitSet,            // indicates which regions have been set
+    holes_set: BitSet,              // indicates which holes have been set
+    pslg_validation: bool,          // validates the PSLG before generating the mesh
 }
 
 impl Drop for Triangle {
@@ -242,13 +272,21 @@ impl Triangle {
         nsegment: Option<usize>,
         nregion: Option<usize>,
         nhole: Option<usize>,
-    ) -> Result<Self, StrError> {
+    ) -> Result<Self, Error> {
         if npoint < 3 {
-            return Err("npoint must be ≥ 3");
+            return Err(Error::TooFew {
+                item: Item::Point,
+                count: npoint,
+                min: 3,
+            });
         }
         if let Some(ns) = nsegment {
             if ns < 3 {
-                return Err("nsegment must be ≥ 3");
+                return Err(Error::TooFew {
+                    item: Item::Segment,
+                    count: ns,
+                    min: 3,
+                });
             }
         }
         let npoint_i32: i32 = to_i32(npoint);
@@ -267,7 +305,7 @@ impl Triangle {
         unsafe {
             let ext_triangle = new_triangle(npoint_i32, nsegment_i32, nregion_i32, nhole_i32);
             if ext_triangle.is_null() {
-                return Err("INTERNAL ERROR: cannot allocate ExtTriangle");
+                return Err(Error::AllocationFailed);
             }
             Ok(Triangle {
                 ext_triangle,
@@ -275,36 +313,109 @@ impl Triangle {
                 nsegment,
                 nregion,
                 nhole,
-                all_points_set: false,
-                all_segments_set: false,
-                all_regions_set: false,
-                all_holes_set: false,
+                points_set: BitSet::new(npoint),
+                segments_set: BitSet::new(nsegment.unwrap_or(0)),
+                regions_set: BitSet::new(nregion.unwrap_or(0)),
+                holes_set: BitSet::new(nhole.unwrap_or(0)),
+                pslg_validation: false,
             })
         }
     }
 
+    /// Allocates a new instance and sets all points, segments, regions and holes at once
+    ///
+    /// All input is validated before the c-code is called; thus, the returned instance is
+    /// ready for `generate_delaunay`, `generate_voronoi` or `generate_mesh`. Segment markers
+    /// may still be set afterwards with [Triangle::set_segment_marker].
+    ///
+    /// # Input
+    ///
+    /// * `points` -- are the point coordinates (npoint ≥ 3)
+    /// * `segments` -- are the IDs of the endpoints of each segment (empty or nsegment ≥ 3)
+    /// * `regions` -- are the `(x, y, attribute, max_area)` data of each region (see [Triangle::set_region])
+    /// * `holes` -- are the coordinates of a point inside each hole
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Triangle};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut triangle = Triangle::from_input(
+    ///         &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+    ///         &[[0, 1], [1, 2], [2, 3], [3, 0]],
+    ///         &[(0.5, 0.5, 1, None)],
+    ///         &[],
+    ///     )?;
+    ///     triangle.generate_mesh(false, false, Some(0.1), None)?;
+    ///     assert_eq!(triangle.triangle_attribute(0), 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn from_input(
+        points: &[[f64; 2]],
+        segments: &[[usize; 2]],
+        regions: &[(f64, f64, usize, Option<f64>)],
+        holes: &[[f64; 2]],
+    ) -> Result<Self, Error> {
+        let npoint = points.len();
+        if points.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(Error::InvalidInput("the coordinates of all points must be finite"));
+        }
+        for segment in segments {
+            if let Some(id) = segment.iter().find(|id| **id >= npoint) {
+                return Err(Error::PointIdOutOfBounds {
+                    item: Item::Segment,
+                    id: *id,
+                    npoint,
+                });
+            }
+        }
+        if regions.iter().any(|(x, y, _, _)| !x.is_finite() || !y.is_finite()) {
+            return Err(Error::InvalidInput("the coordinates of all regions must be finite"));
+        }
+        if regions
+            .iter()
+            .any(|(_, _, _, max_area)| matches!(max_area, Some(a) if a.is_nan() || *a <= 0.0))
+        {
+            return Err(Error::InvalidInput("the max_area of all regions must be positive"));
+        }
+        if holes.iter().flatten().any(|v| !v.is_finite()) {
+            return Err(Error::InvalidInput("the coordinates of all holes must be finite"));
+        }
+        let count = |n: usize| if n > 0 { Some(n) } else { None };
+        let mut triangle = Triangle::new(npoint, count(segments.len()), count(regions.len()), count(holes.len()))?;
+        for (index, x) in points.iter().enumerate() {
+            triangle.set_point(index, x[0], x[1])?;
+        }
+        for (index, segment) in segments.iter().enumerate() {
+            triangle.set_segment(index, segment[0], segment[1])?;
+        }
+        for (index, (x, y, attribute, max_area)) in regions.iter().enumerate() {
+            triangle.set_region(index, *x, *y, *attribute, *max_area)?;
+        }
+        for (index, x) in holes.iter().enumerate() {
+            triangle.set_hole(index, x[0], x[1])?;
+        }
+        Ok(triangle)
+    }
+
     /// Sets the point coordinates
-    pub fn set_point(&mut self, index: usize, x: f64, y: f64) -> Result<&mut Self, StrError> {
+    pub fn set_point(&mut self, index: usize, x: f64, y: f64) -> Result<&mut Self, Error> {
         unsafe {
             let status = set_point(self.ext_triangle, to_i32(index), x, y);
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_POINT_INDEX {
-                    return Err("index of point is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Point,
+                        index,
+                        limit: self.npoint,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == self.npoint - 1 {
-            self.all_points_set = true;
-        } else {
-            self.all_points_set = false;
-        }
+        self.points_set.insert(index);
         Ok(self)
     }
 
@@ -315,33 +426,65 @@ impl Triangle {
     /// * `index` -- is the index of the segment and goes from 0 to `nsegment` (passed down to `new`)
     /// * `a` -- is the ID (index) of the first point on the segment
     /// * `b` -- is the ID (index) of the second point on the segment
-    pub fn set_segment(&mut self, index: usize, a: usize, b: usize) -> Result<&mut Self, StrError> {
+    pub fn set_segment(&mut self, index: usize, a: usize, b: usize) -> Result<&mut Self, Error> {
         let nsegment = match self.nsegment {
             Some(n) => n,
-            None => return Err("cannot set segment because the number of segments is None"),
+            None => return Err(Error::NotAllocated { item: Item::Segment }),
         };
         unsafe {
             let status = set_segment(self.ext_triangle, to_i32(index), to_i32(a), to_i32(b));
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_SEGMENT_LIST {
-                    return Err("INTERNAL ERROR: found NULL segment list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_SEGMENT_INDEX {
-                    return Err("index of segment is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Segment,
+                        index,
+                        limit: nsegment,
+                    });
                 }
                 if status == constants::TRITET_ERROR_INVALID_SEGMENT_POINT_ID {
-                    return Err("id of segment point is out of bounds");
+                    return Err(Error::PointIdOutOfBounds {
+                        item: Item::Segment,
+                        id: if a >= self.npoint { a } else { b },
+                        npoint: self.npoint,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == nsegment - 1 {
-            self.all_segments_set = true;
-        } else {
-            self.all_segments_set = false;
+        self.segments_set.insert(index);
+        Ok(self)
+    }
+
+    /// Sets the boundary marker of a segment
+    ///
+    /// The markers are propagated to the output points and segments (see [Triangle::point_marker]
+    /// and [Triangle::segment_marker]). Segments without a marker have a zero marker.
+    ///
+    /// **Note:** Triangle assigns the marker 1 to points and segments on the boundary
+    /// of the mesh that would otherwise have a zero marker. Thus, non-zero and
+    /// non-unitary markers should be used to tag boundaries.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the segment and goes from 0 to `nsegment` (passed down to `new`)
+    /// * `marker` -- is the marker (tag) of the segment
+    pub fn set_segment_marker(&mut self, index: usize, marker: i32) -> Result<&mut Self, Error> {
+        let nsegment = match self.nsegment {
+            Some(n) => n,
+            None => return Err(Error::NotAllocated { item: Item::Segment }),
+        };
+        unsafe {
+            let status = set_segment_marker(self.ext_triangle, to_i32(index), marker);
+            if status != constants::TRITET_SUCCESS {
+                if status == constants::TRITET_ERROR_INVALID_SEGMENT_INDEX {
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Segment,
+                        index,
+                        limit: nsegment,
+                    });
+                }
+                return Err(Error::Internal { status });
+            }
         }
         Ok(self)
     }
@@ -362,10 +505,10 @@ impl Triangle {
         y: f64,
         attribute: usize,
         max_area: Option<f64>,
-    ) -> Result<&mut Self, StrError> {
+    ) -> Result<&mut Self, Error> {
         let nregion = match self.nregion {
             Some(n) => n,
-            None => return Err("cannot set region because the number of regions is None"),
+            None => return Err(Error::NotAllocated { item: Item::Region }),
         };
         let area_constraint = match max_area {
             Some(v) => v,
@@ -381,23 +524,17 @@ impl Triangle {
                 area_constraint,
             );
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_REGION_LIST {
-                    return Err("INTERNAL ERROR: found NULL region list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_REGION_INDEX {
-                    return Err("index of region is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Region,
+                        index,
+                        limit: nregion,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == nregion - 1 {
-            self.all_regions_set = true;
-        } else {
-            self.all_regions_set = false;
-        }
+        self.regions_set.insert(index);
         Ok(self)
     }
 
@@ -408,54 +545,47 @@ impl Triangle {
     /// * `index` -- is the index of the hole and goes from 0 to `nhole` (passed down to `new`)
     /// * `x` -- is the x-coordinate of the hole
     /// * `y` -- is the y-coordinate of the hole
-    pub fn set_hole(&mut self, index: usize, x: f64, y: f64) -> Result<&mut Self, StrError> {
+    pub fn set_hole(&mut self, index: usize, x: f64, y: f64) -> Result<&mut Self, Error> {
         let nhole = match self.nhole {
             Some(n) => n,
-            None => return Err("cannot set hole because the number of holes is None"),
+            None => return Err(Error::NotAllocated { item: Item::Hole }),
         };
         unsafe {
             let status = set_hole(self.ext_triangle, to_i32(index), x, y);
             if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_HOLE_LIST {
-                    return Err("INTERNAL ERROR: found NULL hole list");
-                }
                 if status == constants::TRITET_ERROR_INVALID_HOLE_INDEX {
-                    return Err("index of hole is out of bounds");
+                    return Err(Error::IndexOutOfBounds {
+                        item: Item::Hole,
+                        index,
+                        limit: nhole,
+                    });
                 }
-                return Err("INTERNAL ERROR: some error occurred");
+                return Err(Error::Internal { status });
             }
         }
-        if index == nhole - 1 {
-            self.all_holes_set = true;
-        } else {
-            self.all_holes_set = false;
-        }
+        self.holes_set.insert(index);
         Ok(self)
     }
 
+    /// Enables or disables the validation of the PSLG by `generate_mesh`
+    ///
+    /// When enabled, `generate_mesh` calls [Triangle::validate_pslg] before calling Triangle
+    /// and returns [Error::InvalidPslg] if any problem is found. The validation is disabled by default.
+    pub fn set_pslg_validation(&mut self, enabled: bool) -> &mut Self {
+        self.pslg_validation = enabled;
+        self
+    }
+
     /// Generates a Delaunay triangulation
     ///
     /// # Input
     ///
     /// * `verbose` -- Prints Triangle's messages to the console
-    pub fn generate_delaunay(&self, verbose: bool) -> Result<(), StrError> {
-        if !self.all_points_set {
-            return Err("cannot generate Delaunay triangulation because not all points are set");
-        }
+    pub fn generate_delaunay(&mut self, verbose: bool) -> Result<(), Error> {
+        self.points_set.check(Item::Point, Stage::TriangleDelaunay)?;
         unsafe {
             let status = run_delaunay(self.ext_triangle, if verbose { 1 } else { 0 });
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
-                return Err("INTERNAL ERROR: some error occurred");
-            }
+            check_status(status, Stage::TriangleDelaunay)?;
         }
         Ok(())
     }
@@ -465,21 +595,11 @@ impl Triangle {
     /// # Input
     ///
     /// * `verbose` -- Prints Triangle's messages to the console
-    pub fn generate_voronoi(&self, verbose: bool) -> Result<(), StrError> {
-        if !self.all_points_set {
-            return Err("cannot generate Voronoi tessellation because not all points are set");
-        }
+    pub fn generate_voronoi(&mut self, verbose: bool) -> Result<(), Error> {
+        self.points_set.check(Item::Point, Stage::TriangleVoronoi)?;
         unsafe {
             let status = run_voronoi(self.ext_triangle, if verbose { 1 } else { 0 });
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
-                return Err("INTERNAL ERROR: some error occurred");
-            }
+            check_status(status, Stage::TriangleVoronoi)?;
         }
         Ok(())
     }
@@ -493,17 +613,18 @@ impl Triangle {
     /// * `global_max_area` -- The maximum area constraint for all generated triangles
     /// * `global_min_angle` -- The minimum angle constraint is given in degrees (the default minimum angle is twenty degrees)
     pub fn generate_mesh(
-        &self,
+        &mut self,
         verbose: bool,
         quadratic: bool,
         global_max_area: Option<f64>,
         global_min_angle: Option<f64>,
-    ) -> Result<(), StrError> {
-        if !self.all_points_set {
-            return Err("cannot generate mesh of triangles because not all points are set");
-        }
-        if !self.all_segments_set {
-            return Err("cannot generate mesh of triangles because not all segments are set");
+    ) -> Result<(), Error> {
+        self.check_input_set(Stage::TriangleMesh)?;
+        if self.pslg_validation {
+            let report = self.validate_pslg()?;
+            if !report.is_valid() {
+                return Err(Error::InvalidPslg(Box::new(report)));
+            }
         }
         let max_area = match global_max_area {
             Some(v) => v,
@@ -521,21 +642,96 @@ impl Triangle {
                 max_area,
                 min_angle,
             );
-            if status != constants::TRITET_SUCCESS {
-                if status == constants::TRITET_ERROR_NULL_DATA {
-                    return Err("INTERNAL ERROR: found NULL data");
-                }
-                if status == constants::TRITET_ERROR_NULL_POINT_LIST {
-                    return Err("INTERNAL ERROR: found NULL point list");
-                }
-                if status == constants::TRITET_ERROR_NULL_SEGMENT_LIST {
-                    return Err("INTERNAL ERROR: list of segments must be defined first");
-                }
-                if status == constants::TRITET_ERROR_STRING_CONCAT {
-                    return Err("INTERNAL ERROR: cannot write string with commands for Triangle");
-                }
-                return Err("INTERNAL ERROR: some error occurred");
-            }
+            check_status(status, Stage::TriangleMesh)?;
+        }
+        Ok(())
+    }
+
+    /// Refines the current mesh with a maximum area constraint for each triangle
+    ///
+    /// The current mesh (e.g., generated by [Triangle::generate_mesh]) is replaced by the refined
+    /// mesh. The triangles are subdivided until their areas satisfy the constraints; the other
+    /// triangles are only modified to keep the quality of the mesh. The segments, the point and
+    /// segment markers, and the attributes of the triangles are preserved.
+    ///
+    /// This function may be called repeatedly, e.g., in an error-estimate and refinement loop.
+    /// The input (PSLG) is not modified; thus, [Triangle::generate_mesh] will start from scratch.
+    ///
+    /// # Input
+    ///
+    /// * `verbose` -- Prints Triangle's messages to the console
+    /// * `quadratic` -- Generates the middle nodes of the refined mesh; e.g., nnode = 6
+    /// * `max_areas` -- The maximum area of each triangle of the current mesh (`len = ntriangle`).
+    ///   A zero or negative value means no constraint.
+    /// * `global_min_angle` -- The minimum angle constraint is given in degrees (the default minimum angle is twenty degrees)
+    ///
+    /// # Warning
+    ///
+    /// The current mesh must have linear triangles (nnode = 3). Thus, only the last mesh
+    /// of a sequence of refinements may be quadratic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Triangle};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut triangle = Triangle::from_input(
+    ///         &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+    ///         &[[0, 1], [1, 2], [2, 3], [3, 0]],
+    ///         &[],
+    ///         &[],
+    ///     )?;
+    ///     triangle.generate_mesh(false, false, None, None)?;
+    ///     assert_eq!(triangle.ntriangle(), 2);
+    ///
+    ///     // refine the triangle containing the origin only
+    ///     let max_areas: Vec<_> = (0..triangle.ntriangle())
+    ///         .map(|t| {
+    ///             let touches_origin = (0..3).any(|m| triangle.triangle_node(t, m) == 0);
+    ///             if touches_origin { 0.01 } else { 0.0 }
+    ///         })
+    ///         .collect();
+    ///     triangle.refine_mesh(false, false, &max_areas, None)?;
+    ///     assert!(triangle.ntriangle() > 50);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn refine_mesh(
+        &mut self,
+        verbose: bool,
+        quadratic: bool,
+        max_areas: &[f64],
+        global_min_angle: Option<f64>,
+    ) -> Result<(), Error> {
+        if self.ntriangle() == 0 {
+            return Err(Error::InvalidInput(
+                "there is no mesh to refine (call generate_mesh first)",
+            ));
+        }
+        if self.nnode() != 3 {
+            return Err(Error::InvalidInput(
+                "the mesh to refine must have linear triangles (nnode = 3)",
+            ));
+        }
+        if max_areas.len() != self.ntriangle() {
+            return Err(Error::InvalidInput(
+                "the number of max areas must equal the number of triangles",
+            ));
+        }
+        if max_areas.iter().any(|a| a.is_nan()) {
+            return Err(Error::InvalidInput("the max areas must not be NaN"));
+        }
+        let min_angle = global_min_angle.unwrap_or(0.0);
+        unsafe {
+            let status = run_refine(
+                self.ext_triangle,
+                if verbose { 1 } else { 0 },
+                if quadratic { 1 } else { 0 },
+                max_areas.as_ptr(),
+                min_angle,
+            );
+            check_status(status, Stage::TriangleRefine)?;
         }
         Ok(())
     }
@@ -564,8 +760,10 @@ impl Triangle {
     ///
     /// # Warning
     ///
-    /// This function will return 0.0 if either `index` or `dim` are out of range.
+    /// This function panics in debug builds if `index` or `dim` is out of range; release builds return 0.0 instead.
     pub fn point(&self, index: usize, dim: usize) -> f64 {
+        debug_assert!(index < self.npoint());
+        debug_assert!(dim < 2);
         unsafe { get_point(self.ext_triangle, to_i32(index), to_i32(dim)) }
     }
 
@@ -589,8 +787,10 @@ impl Triangle {
     ///
     /// # Warning
     ///
-    /// This function will return 0 if either `index` or `m` are out of range.
+    /// This function panics in debug builds if `index` or `m` is out of range; release builds return 0 instead.
     pub fn triangle_node(&self, index: usize, m: usize) -> usize {
+        debug_assert!(index < self.ntriangle());
+        debug_assert!(m < self.nnode());
         unsafe {
             let corner = constants::TRITET_TO_TRIANGLE[m];
             get_triangle_corner(self.ext_triangle, to_i32(index), to_i32(corner)) as usize
@@ -601,11 +801,301 @@ impl Triangle {
     ///
     /// # Warning
     ///
-    /// This function will return 0 if either `index` is out of range.
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
     pub fn triangle_attribute(&self, index: usize) -> usize {
+        debug_assert!(index < self.ntriangle());
         unsafe { get_triangle_attribute(self.ext_triangle, to_i32(index)) as usize }
     }
 
+    /// Returns the index of the triangle sharing a side with a triangle
+    ///
+    /// ```text
+    ///     SIDES
+    ///       2
+    ///      / \     Side s contains the
+    ///     /   \    nodes s and (s+1)%3
+    ///    2     1   and the middle node 3+s
+    ///   /       \
+    ///  /         \
+    /// 0-----0-----1
+    /// ```
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the triangle and goes from 0 to `ntriangle`
+    /// * `side` -- is the local index of the side and goes from 0 to 3
+    ///
+    /// # Output
+    ///
+    /// Returns the index of the neighbor or None if the side is on the boundary.
+    ///
+    /// # Warning
+    ///
+    /// This function will return None if either `index` or `side` are out of range.
+    pub fn triangle_neighbor(&self, index: usize, side: usize) -> Option<usize> {
+        if side > 2 {
+            return None;
+        }
+        unsafe {
+            let neighbor = constants::TRITET_TO_TRIANGLE_SIDE[side];
+            let id = get_triangle_neighbor(self.ext_triangle, to_i32(index), to_i32(neighbor));
+            if id < 0 {
+                None
+            } else {
+                Some(id as usize)
+            }
+        }
+    }
+
+    /// Returns the marker of a point
+    ///
+    /// Points on segments inherit the marker of the segment (see [Triangle::set_segment_marker]).
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the point and goes from 0 to `npoint`
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
+    pub fn point_marker(&self, index: usize) -> i32 {
+        debug_assert!(index < self.npoint());
+        unsafe { get_point_marker(self.ext_triangle, to_i32(index)) }
+    }
+
+    /// Returns the number of segments of the constrained Delaunay triangulation
+    ///
+    /// The output segments are the (possibly subdivided) input segments. This number
+    /// is zero after [Triangle::generate_delaunay] and [Triangle::generate_voronoi].
+    pub fn nsegment(&self) -> usize {
+        unsafe { get_nsegment(self.ext_triangle) as usize }
+    }
+
+    /// Returns the ID of an endpoint of a segment of the constrained Delaunay triangulation
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the segment and goes from 0 to `nsegment`
+    /// * `side` -- is the local index of the endpoint: 0 or 1
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` or `side` is out of range; release builds return 0 instead.
+    pub fn segment_point(&self, index: usize, side: usize) -> usize {
+        debug_assert!(index < self.nsegment());
+        debug_assert!(side < 2);
+        unsafe { get_segment_point(self.ext_triangle, to_i32(index), to_i32(side)) as usize }
+    }
+
+    /// Returns the marker of a segment of the constrained Delaunay triangulation
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the segment and goes from 0 to `nsegment`
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
+    pub fn segment_marker(&self, index: usize) -> i32 {
+        debug_assert!(index < self.nsegment());
+        unsafe { get_segment_marker(self.ext_triangle, to_i32(index)) }
+    }
+
+    /// Returns the number of edges of the Delaunay triangulation (constrained or not)
+    ///
+    /// Each edge shared by two triangles is listed only once.
+    pub fn nedge(&self) -> usize {
+        unsafe { get_nedge(self.ext_triangle) as usize }
+    }
+
+    /// Returns the ID of an endpoint of an edge of the Delaunay triangulation (constrained or not)
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the edge and goes from 0 to `nedge`
+    /// * `side` -- is the local index of the endpoint: 0 or 1
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` or `side` is out of range; release builds return 0 instead.
+    pub fn edge_point(&self, index: usize, side: usize) -> usize {
+        debug_assert!(index < self.nedge());
+        debug_assert!(side < 2);
+        unsafe { get_edge_point(self.ext_triangle, to_i32(index), to_i32(side)) as usize }
+    }
+
+    /// Returns the marker of an edge of the Delaunay triangulation (constrained or not)
+    ///
+    /// Edges on segments inherit the marker of the segment (see [Triangle::set_segment_marker]),
+    /// other edges on the boundary have marker 1, and interior edges have marker 0.
+    ///
+    /// # Input
+    ///
+    /// * `index` -- is the index of the edge and goes from 0 to `nedge`
+    ///
+    /// # Warning
+    ///
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
+    pub fn edge_marker(&self, index: usize) -> i32 {
+        debug_assert!(index < self.nedge());
+        unsafe { get_edge_marker(self.ext_triangle, to_i32(index)) }
+    }
+
+    /// Returns the x-y coordinates of all points (zero-copy)
+    ///
+    /// The coordinates are stored as `[x0, y0, x1, y1, ...]`; thus, the length of the slice is `2 * npoint`.
+    pub fn points(&self) -> &[f64] {
+        unsafe { to_slice(get_point_list(self.ext_triangle), 2 * self.npoint()) }
+    }
+
+    /// Returns the markers of all points (zero-copy)
+    ///
+    /// See [Triangle::point_marker]. The slice is empty if the markers are not available.
+    pub fn point_markers(&self) -> &[i32] {
+        unsafe { to_slice(get_point_marker_list(self.ext_triangle), self.npoint()) }
+    }
+
+    /// Returns the IDs of the nodes of all triangles
+    ///
+    /// The nodes of triangle `i` are at `[i * nnode, (i + 1) * nnode)` and follow the
+    /// local numbering of [Triangle::triangle_node]. This is an owned vector because
+    /// Triangle stores the connectivity as `i32` and with a different local numbering.
+    pub fn triangles(&self) -> Vec<usize> {
+        let nnode = usize::max(self.nnode(), 1); // nnode is zero before the generation
+        self.triangle_list()
+            .chunks_exact(nnode)
+            .flat_map(|corners| (0..nnode).map(move |m| corners[constants::TRITET_TO_TRIANGLE[m]] as usize))
+            .collect()
+    }
+
+    /// Returns the attribute IDs of all triangles
+    ///
+    /// See [Triangle::triangle_attribute]. The attributes are zero if no region has been set.
+    pub fn triangle_attributes(&self) -> Vec<usize> {
+        let ntriangle = self.ntriangle();
+        let nattribute = unsafe { get_ntriangle_attribute(self.ext_triangle) as usize };
+        if nattribute == 0 {
+            return vec![0; ntriangle];
+        }
+        let list = unsafe { to_slice(get_triangle_attribute_list(self.ext_triangle), ntriangle * nattribute) };
+        list.chunks_exact(nattribute).map(|values| values[0] as usize).collect()
+    }
+
+    /// Returns the neighbors of all triangles
+    ///
+    /// See [Triangle::triangle_neighbor]. The neighbor across side `s` is at index `s`.
+    pub fn triangle_neighbors(&self) -> Vec<[Option<usize>; 3]> {
+        let ntriangle = self.ntriangle();
+        let list = unsafe { to_slice(get_neighbor_list(self.ext_triangle), 3 * ntriangle) };
+        if list.is_empty() {
+            return vec![[None; 3]; ntriangle];
+        }
+        list.chunks_exact(3)
+            .map(|ids| {
+                let mut neighbors = [None; 3];
+                for (side, neighbor) in neighbors.iter_mut().enumerate() {
+                    let id = ids[constants::TRITET_TO_TRIANGLE_SIDE[side]];
+                    if id >= 0 {
+                        *neighbor = Some(id as usize);
+                    }
+                }
+                neighbors
+            })
+            .collect()
+    }
+
+    /// Returns the IDs of the endpoints of all segments of the constrained Delaunay triangulation
+    ///
+    /// See [Triangle::segment_point].
+    pub fn segments(&self) -> Vec<[usize; 2]> {
+        let list = unsafe { to_slice(get_segment_list(self.ext_triangle), 2 * self.nsegment()) };
+        list.chunks_exact(2)
+            .map(|ab| [ab[0] as usize, ab[1] as usize])
+            .collect()
+    }
+
+    /// Returns the markers of all segments of the constrained Delaunay triangulation (zero-copy)
+    ///
+    /// See [Triangle::segment_marker]. The slice is empty if the markers are not available.
+    pub fn segment_markers(&self) -> &[i32] {
+        unsafe { to_slice(get_segment_marker_list(self.ext_triangle), self.nsegment()) }
+    }
+
+    /// Returns an iterator over the triangles yielding the x-y coordinates of their nodes
+    ///
+    /// The nodes follow the local numbering of [Triangle::triangle_node].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Triangle};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut triangle = Triangle::new(3, None, None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 0.0, 1.0)?;
+    ///     triangle.generate_delaunay(false)?;
+    ///     let areas: Vec<_> = triangle
+    ///         .triangle_points()
+    ///         .map(|x| 0.5 * ((x[1][0] - x[0][0]) * (x[2][1] - x[0][1]) - (x[1][1] - x[0][1]) * (x[2][0] - x[0][0])))
+    ///         .collect();
+    ///     assert_eq!(areas, &[0.5]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn triangle_points(&self) -> impl Iterator<Item = Vec<[f64; 2]>> + '_ {
+        let nnode = usize::max(self.nnode(), 1); // nnode is zero before the generation
+        let points = self.points();
+        self.triangle_list().chunks_exact(nnode).map(move |corners| {
+            (0..nnode)
+                .map(|m| {
+                    let p = corners[constants::TRITET_TO_TRIANGLE[m]] as usize;
+                    [points[2 * p], points[2 * p + 1]]
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the connectivity as stored by Triangle (zero-copy)
+    fn triangle_list(&self) -> &[i32] {
+        unsafe { to_slice(get_triangle_list(self.ext_triangle), self.ntriangle() * self.nnode()) }
+    }
+
+    /// Returns an error if not all points, segments, regions and holes have been set
+    pub(crate) fn check_input_set(&self, stage: Stage) -> Result<(), Error> {
+        self.points_set.check(Item::Point, stage)?;
+        self.segments_set.check(Item::Segment, stage)?;
+        self.regions_set.check(Item::Region, stage)?;
+        self.holes_set.check(Item::Hole, stage)
+    }
+
+    /// Returns the input points as passed down to `set_point` (x0, y0, x1, y1, ...)
+    pub(crate) fn input_points(&self) -> &[f64] {
+        unsafe { to_slice(get_input_point_list(self.ext_triangle), 2 * self.npoint) }
+    }
+
+    /// Returns the input segments as passed down to `set_segment` (a0, b0, a1, b1, ...)
+    pub(crate) fn input_segments(&self) -> &[i32] {
+        unsafe {
+            to_slice(
+                get_input_segment_list(self.ext_triangle),
+                2 * self.nsegment.unwrap_or(0),
+            )
+        }
+    }
+
+    /// Returns the input regions as passed down to `set_region` (x, y, attribute, max_area per region)
+    pub(crate) fn input_regions(&self) -> &[f64] {
+        unsafe { to_slice(get_input_region_list(self.ext_triangle), 4 * self.nregion.unwrap_or(0)) }
+    }
+
+    /// Returns the input holes as passed down to `set_hole` (x0, y0, x1, y1, ...)
+    pub(crate) fn input_holes(&self) -> &[f64] {
+        unsafe { to_slice(get_input_hole_list(self.ext_triangle), 2 * self.nhole.unwrap_or(0)) }
+    }
+
     /// Returns the number of points of the Voronoi tessellation
     pub fn voronoi_npoint(&self) -> usize {
         unsafe { get_voronoi_npoint(self.ext_triangle) as usize }
@@ -620,8 +1110,10 @@ impl Triangle {
     ///
     /// # Warning
     ///
-    /// This function will return 0.0 if either `index` or `dim` are out of range.
+    /// This function panics in debug builds if `index` or `dim` is out of range; release builds return 0.0 instead.
     pub fn voronoi_point(&self, index: usize, dim: usize) -> f64 {
+        debug_assert!(index < self.voronoi_npoint());
+        debug_assert!(dim < 2);
         unsafe { get_voronoi_point(self.ext_triangle, to_i32(index), to_i32(dim)) }
     }
 
@@ -638,8 +1130,9 @@ impl Triangle {
     ///
     /// # Warning
     ///
-    /// This function will return 0 if either `index` is out of range.
+    /// This function panics in debug builds if `index` is out of range; release builds return 0 instead.
     pub fn voronoi_edge_point_a(&self, index: usize) -> usize {
+        debug_assert!(index < self.voronoi_nedge());
         unsafe { get_voronoi_edge_point(self.ext_triangle, to_i32(index), 0) as usize }
     }
 
@@ -651,8 +1144,9 @@ impl Triangle {
     ///
     /// # Warning
     ///
-    /// This function will return Index(0) if either `index` is out of range.
+    /// This function panics in debug builds if `index` is out of range; release builds return Index(0) instead.
     pub fn voronoi_edge_point_b(&self, index: usize) -> VoronoiEdgePoint {
+        debug_assert!(index < self.voronoi_nedge());
         unsafe {
             let index_i32 = to_i32(index);
             let id = get_voronoi_edge_point(self.ext_triangle, index_i32, 1);
@@ -679,116 +1173,17 @@ impl Triangle {
         fontsize_triangle_ids: Option<f64>,
         fontsize_attribute_ids: Option<f64>,
     ) {
-        let n_triangle = self.ntriangle();
-        if n_triangle < 1 {
-            return;
-        }
-        let mut canvas = Canvas::new();
-        let mut point_ids = Text::new();
-        let mut triangle_ids = Text::new();
-        let mut attribute_ids = Text::new();
-        if with_point_ids {
-            point_ids
-                .set_color("red")
-                .set_align_horizontal("center")
-                .set_align_vertical("center")
-                .set_bbox(true)
-                .set_bbox_facecolor("white")
-                .set_bbox_alpha(0.8)
-                .set_bbox_style("circle");
-            if let Some(fsz) = fontsize_point_ids {
-                point_ids.set_fontsize(fsz);
-            }
-        }
-        if with_triangle_ids {
-            triangle_ids
-                .set_color("blue")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_triangle_ids {
-                triangle_ids.set_fontsize(fsz);
-            }
-        }
-        if with_attribute_ids {
-            attribute_ids
-                .set_color("black")
-                .set_align_horizontal("center")
-                .set_align_vertical("center");
-            if let Some(fsz) = fontsize_attribute_ids {
-                attribute_ids.set_fontsize(fsz);
-            }
-        }
-        canvas.set_edge_color("black");
-        let mut x = vec![0.0; 2];
-        let mut xmid = vec![0.0; 2];
-        let mut xatt = vec![0.0; 2];
-        let mut min = vec![f64::MAX; 2];
-        let mut max = vec![f64::MIN; 2];
-        let mut colors: HashMap<usize, &'static str> = HashMap::new();
-        let mut index_color = 0;
-        let clr = constants::LIGHT_COLORS;
-        for tri in 0..n_triangle {
-            let attribute = self.triangle_attribute(tri);
-            let color = match colors.get(&attribute) {
-                Some(c) => c,
-                None => {
-                    let c = clr[index_color % clr.len()];
-                    colors.insert(attribute, c);
-                    index_color += 1;
-                    c
-                }
-            };
-            canvas.set_face_color(color);
-            canvas.polycurve_begin();
-            for dim in 0..2 {
-                xmid[dim] = 0.0;
-            }
-            for m in 0..3 {
-                let p = self.triangle_node(tri, m);
-                for dim in 0..2 {
-                    x[dim] = self.point(p, dim);
-                    min[dim] = f64::min(min[dim], x[dim]);
-                    max[dim] = f64::max(max[dim], x[dim]);
-                    xmid[dim] += x[dim] / 3.0;
-                }
-                if m == 0 {
-                    canvas.polycurve_add(x[0], x[1], PolyCode::MoveTo);
-                } else {
-                    canvas.polycurve_add(x[0], x[1], PolyCode::LineTo);
-                }
-            }
-            canvas.polycurve_end(true);
-            if with_triangle_ids {
-                triangle_ids.draw(xmid[0], xmid[1], format!("{}", tri).as_str());
-            }
-            if with_attribute_ids {
-                for dim in 0..2 {
-                    x[dim] = self.point(self.triangle_node(tri, 0), dim);
-                    xatt[dim] = (x[dim] + xmid[dim]) / 2.0;
-                }
-                attribute_ids.draw(xatt[0], xatt[1], format!("[{}]", attribute).as_str());
-            }
-        }
-        if with_point_ids {
-            for p in 0..self.npoint() {
-                let x = self.point(p, 0);
-                let y = self.point(p, 1);
-                point_ids.draw(x, y, format!("{}", p).as_str());
-            }
-        }
-        plot.add(&canvas);
-        if with_triangle_ids {
-            plot.add(&triangle_ids);
-        }
-        if with_point_ids {
-            plot.add(&point_ids);
-        }
-        if with_attribute_ids {
-            plot.add(&attribute_ids);
-        }
-        if set_range {
-            plot.set_range(min[0], max[0], min[1], max[1]);
-        }
+        let triangles = self.triangles();
+        let nnode = usize::max(self.nnode(), 1); // nnode is zero before the generation
+        mesh::draw_triangles(
+            plot,
+            self.points(),
+            triangles.chunks_exact(nnode),
+            &self.triangle_attributes(),
+            set_range,
+            [with_point_ids, with_triangle_ids, with_attribute_ids],
+            [fontsize_point_ids, fontsize_triangle_ids, fontsize_attribute_ids],
+        );
     }
 
     #[cfg(feature = "plotpy")]
@@ -870,12 +1265,26 @@ impl Triangle {
     }
 }
 
+/// Converts the status returned by Triangle into an error
+fn check_status(status: i32, stage: Stage) -> Result<(), Error> {
+    if status == constants::TRITET_SUCCESS {
+        return Ok(());
+    }
+    if status == constants::TRITET_ERROR_TRIANGLE_FAILED {
+        return Err(Error::GeneratorFailed { stage, status });
+    }
+    Err(Error::Internal { status })
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(test)]
 mod tests {
     use super::Triangle;
-    use crate::{StrError, VoronoiEdgePoint};
+    use crate::bitset::BitSet;
+    use crate::constants;
+    use crate::{Error, Item, Stage, VoronoiEdgePoint};
+    use std::panic::{self, AssertUnwindSafe};
 
     #[cfg(feature = "plotpy")]
     use plotpy::Plot;
@@ -890,115 +1299,314 @@ mod tests {
 
     #[test]
     fn new_captures_some_errors() {
-        assert_eq!(Triangle::new(2, None, None, None).err(), Some("npoint must be ≥ 3"));
+        assert_eq!(
+            Triangle::new(2, None, None, None).err(),
+            Some(Error::TooFew {
+                item: Item::Point,
+                count: 2,
+                min: 3
+            })
+        );
         assert_eq!(
             Triangle::new(3, Some(2), None, None).err(),
-            Some("nsegment must be ≥ 3")
+            Some(Error::TooFew {
+                item: Item::Segment,
+                count: 2,
+                min: 3
+            })
         );
     }
 
     #[test]
-    fn new_works() -> Result<(), StrError> {
+    fn new_works() -> Result<(), Error> {
         let triangle = Triangle::new(3, Some(3), None, None)?;
         assert_eq!(triangle.ext_triangle.is_null(), false);
         assert_eq!(triangle.npoint, 3);
         assert_eq!(triangle.nsegment, Some(3));
         assert_eq!(triangle.nregion, None);
         assert_eq!(triangle.nhole, None);
-        assert_eq!(triangle.all_points_set, false);
-        assert_eq!(triangle.all_segments_set, false);
-        assert_eq!(triangle.all_regions_set, false);
-        assert_eq!(triangle.all_holes_set, false);
+        assert_eq!(triangle.points_set, BitSet::new(3));
+        assert_eq!(triangle.segments_set, BitSet::new(3));
+        assert_eq!(triangle.regions_set, BitSet::new(0));
+        assert_eq!(triangle.holes_set, BitSet::new(0));
+        Ok(())
+    }
+
+    #[test]
+    fn from_input_captures_some_errors() {
+        let points = &[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+        assert_eq!(
+            Triangle::from_input(&points[..2], &[], &[], &[]).err(),
+            Some(Error::TooFew {
+                item: Item::Point,
+                count: 2,
+                min: 3
+            })
+        );
+        assert_eq!(
+            Triangle::from_input(&[[0.0, 0.0], [1.0, f64::NAN], [0.0, 1.0]], &[], &[], &[]).err(),
+            Some(Error::InvalidInput("the coordinates of all points must be finite"))
+        );
+        assert_eq!(
+            Triangle::from_input(points, &[[0, 1], [1, 2]], &[], &[]).err(),
+            Some(Error::TooFew {
+                item: Item::Segment,
+                count: 2,
+                min: 3
+            })
+        );
+        assert_eq!(
+            Triangle::from_input(points, &[[0, 1], [1, 2], [2, 3]], &[], &[]).err(),
+            Some(Error::PointIdOutOfBounds {
+                item: Item::Segment,
+                id: 3,
+                npoint: 3
+            })
+        );
+        assert_eq!(
+            Triangle::from_input(points, &[], &[(f64::INFINITY, 0.1, 1, None)], &[]).err(),
+            Some(Error::InvalidInput("the coordinates of all regions must be finite"))
+        );
+        assert_eq!(
+            Triangle::from_input(points, &[], &[(0.1, 0.1, 1, Some(0.0))], &[]).err(),
+            Some(Error::InvalidInput("the max_area of all regions must be positive"))
+        );
+        assert_eq!(
+            Triangle::from_input(points, &[], &[], &[[0.1, f64::NAN]]).err(),
+            Some(Error::InvalidInput("the coordinates of all holes must be finite"))
+        );
+    }
+
+    #[test]
+    fn from_input_works() -> Result<(), Error> {
+        let mut triangle = Triangle::from_input(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], &[], &[], &[])?;
+        assert_eq!(triangle.nsegment, None);
+        assert_eq!(triangle.nregion, None);
+        assert_eq!(triangle.nhole, None);
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 1);
+
+        // square with a square hole and one region
+        let points = vec![
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [1.0, 1.0],
+            [0.0, 1.0],
+            [0.25, 0.25],
+            [0.75, 0.25],
+            [0.75, 0.75],
+            [0.25, 0.75],
+        ];
+        let segments = vec![[0, 1], [1, 2], [2, 3], [3, 0], [4, 5], [5, 6], [6, 7], [7, 4]];
+        let mut triangle = Triangle::from_input(&points, &segments, &[(0.1, 0.1, 7, None)], &[[0.5, 0.5]])?;
+        assert_eq!(triangle.nsegment, Some(8));
+        assert_eq!(triangle.nregion, Some(1));
+        assert_eq!(triangle.nhole, Some(1));
+        triangle.generate_mesh(false, false, Some(0.05), None)?;
+        assert!(triangle.ntriangle() > 8);
+        let mut area = 0.0;
+        for (index, x) in triangle.triangle_points().enumerate() {
+            assert_eq!(triangle.triangle_attribute(index), 7);
+            let a = ((x[1][0] - x[0][0]) * (x[2][1] - x[0][1]) - (x[2][0] - x[0][0]) * (x[1][1] - x[0][1])) / 2.0;
+            assert!(a <= 0.05);
+            area += a;
+        }
+        assert!(f64::abs(area - 0.75) < 1e-14);
         Ok(())
     }
 
     #[test]
-    fn set_point_captures_some_errors() -> Result<(), StrError> {
+    fn set_point_captures_some_errors() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, None, None, None)?;
         assert_eq!(
             triangle.set_point(4, 0.0, 0.0).err(),
-            Some("index of point is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Point,
+                index: 4,
+                limit: 3
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn set_segment_captures_some_errors() -> Result<(), StrError> {
+    fn set_segment_captures_some_errors() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, None, None, None)?;
         assert_eq!(
             triangle.set_segment(0, 0, 1).err(),
-            Some("cannot set segment because the number of segments is None")
+            Some(Error::NotAllocated { item: Item::Segment })
         );
         let mut triangle = Triangle::new(3, Some(3), None, None)?;
         assert_eq!(
             triangle.set_segment(4, 0, 1).err(),
-            Some("index of segment is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Segment,
+                index: 4,
+                limit: 3
+            })
         );
         assert_eq!(
             triangle.set_segment(0, 0, 4).err(),
-            Some("id of segment point is out of bounds")
+            Some(Error::PointIdOutOfBounds {
+                item: Item::Segment,
+                id: 4,
+                npoint: 3
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_segment_marker_captures_some_errors() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.set_segment_marker(0, -10).err(),
+            Some(Error::NotAllocated { item: Item::Segment })
+        );
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        assert_eq!(
+            triangle.set_segment_marker(4, -10).err(),
+            Some(Error::IndexOutOfBounds {
+                item: Item::Segment,
+                index: 4,
+                limit: 3
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn set_region_captures_some_errors() -> Result<(), StrError> {
+    fn set_region_captures_some_errors() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, None, None, None)?;
         assert_eq!(
             triangle.set_region(0, 0.33, 0.33, 1, Some(0.1)).err(),
-            Some("cannot set region because the number of regions is None")
+            Some(Error::NotAllocated { item: Item::Region })
         );
         let mut triangle = Triangle::new(3, Some(3), Some(1), None)?;
         assert_eq!(
             triangle.set_region(1, 0.33, 0.33, 1, Some(0.1)).err(),
-            Some("index of region is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Region,
+                index: 1,
+                limit: 1
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn set_hole_captures_some_errors() -> Result<(), StrError> {
+    fn set_hole_captures_some_errors() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, None, None, None)?;
         assert_eq!(
             triangle.set_hole(0, 0.33, 0.33).err(),
-            Some("cannot set hole because the number of holes is None")
+            Some(Error::NotAllocated { item: Item::Hole })
         );
         let mut triangle = Triangle::new(3, Some(3), Some(1), Some(1))?;
         assert_eq!(
             triangle.set_hole(1, 0.33, 0.33).err(),
-            Some("index of hole is out of bounds")
+            Some(Error::IndexOutOfBounds {
+                item: Item::Hole,
+                index: 1,
+                limit: 1
+            })
         );
         Ok(())
     }
 
     #[test]
-    fn generate_methods_capture_some_errors() -> Result<(), StrError> {
-        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+    fn generate_methods_capture_some_errors() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, Some(3), Some(1), Some(1))?;
         assert_eq!(
             triangle.generate_delaunay(false).err(),
-            Some("cannot generate Delaunay triangulation because not all points are set")
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TriangleDelaunay,
+                missing: vec![0, 1, 2],
+            })
         );
+        triangle.set_point(2, 0.0, 1.0)?; // the last index does not mean all are set
         assert_eq!(
             triangle.generate_voronoi(false).err(),
-            Some("cannot generate Voronoi tessellation because not all points are set")
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TriangleVoronoi,
+                missing: vec![0, 1],
+            })
         );
+        triangle.set_point(0, 0.0, 0.0)?;
         assert_eq!(
             triangle.generate_mesh(false, false, None, None).err(),
-            Some("cannot generate mesh of triangles because not all points are set")
+            Some(Error::NotAllSet {
+                item: Item::Point,
+                stage: Stage::TriangleMesh,
+                missing: vec![1],
+            })
         );
-        triangle
-            .set_point(0, 0.0, 0.0)?
-            .set_point(1, 1.0, 0.0)?
-            .set_point(2, 0.0, 1.0)?;
+        triangle.set_point(1, 1.0, 0.0)?;
+        triangle.set_segment(1, 1, 2)?;
         assert_eq!(
             triangle.generate_mesh(false, false, None, None).err(),
-            Some("cannot generate mesh of triangles because not all segments are set")
+            Some(Error::NotAllSet {
+                item: Item::Segment,
+                stage: Stage::TriangleMesh,
+                missing: vec![0, 2],
+            })
         );
+        triangle.set_segment(2, 2, 0)?.set_segment(0, 0, 1)?;
+        assert_eq!(
+            triangle.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::Region,
+                stage: Stage::TriangleMesh,
+                missing: vec![0],
+            })
+        );
+        triangle.set_region(0, 0.1, 0.1, 1, None)?;
+        assert_eq!(
+            triangle.generate_mesh(false, false, None, None).err(),
+            Some(Error::NotAllSet {
+                item: Item::Hole,
+                stage: Stage::TriangleMesh,
+                missing: vec![0],
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generate_methods_return_triangle_errors() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, None, None, None)?;
+        triangle
+            .set_point(0, 1.0, 1.0)?
+            .set_point(1, 1.0, 1.0)?
+            .set_point(2, 1.0, 1.0)?;
+        assert_eq!(
+            triangle.generate_delaunay(false).err(),
+            Some(Error::GeneratorFailed {
+                stage: Stage::TriangleDelaunay,
+                status: constants::TRITET_ERROR_TRIANGLE_FAILED
+            })
+        );
+        assert_eq!(triangle.npoint(), 0);
+        assert_eq!(triangle.ntriangle(), 0);
+        assert_eq!(
+            triangle.generate_voronoi(false).err(),
+            Some(Error::GeneratorFailed {
+                stage: Stage::TriangleVoronoi,
+                status: constants::TRITET_ERROR_TRIANGLE_FAILED
+            })
+        );
+        assert_eq!(triangle.voronoi_npoint(), 0);
+        // the instance can still be used
+        triangle.set_point(1, 2.0, 1.0)?.set_point(2, 1.0, 2.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.npoint(), 3);
+        assert_eq!(triangle.ntriangle(), 1);
         Ok(())
     }
 
     #[test]
-    fn delaunay_1_works() -> Result<(), StrError> {
+    fn delaunay_1_works() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, None, None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1017,13 +1625,17 @@ mod tests {
         assert_eq!(triangle.triangle_node(0, 0), 0);
         assert_eq!(triangle.triangle_node(0, 1), 1);
         assert_eq!(triangle.triangle_node(0, 2), 2);
+        assert_eq!(triangle.nedge(), 3);
+        for e in 0..3 {
+            assert_eq!(triangle.edge_marker(e), 1);
+        }
         assert_eq!(triangle.voronoi_npoint(), 0);
         assert_eq!(triangle.voronoi_nedge(), 0);
         Ok(())
     }
 
     #[test]
-    fn voronoi_1_works() -> Result<(), StrError> {
+    fn voronoi_1_works() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, None, None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1062,7 +1674,7 @@ mod tests {
     }
 
     #[test]
-    fn mesh_1_works() -> Result<(), StrError> {
+    fn mesh_1_works() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, Some(3), None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1086,15 +1698,13 @@ mod tests {
         assert_eq!(triangle.triangle_node(0, 1), 1);
         assert_eq!(triangle.triangle_node(0, 2), 2);
         assert_eq!(triangle.triangle_attribute(0), 0);
-        assert_eq!(triangle.triangle_attribute(1), 0);
-        assert_eq!(triangle.triangle_attribute(2), 0);
         assert_eq!(triangle.voronoi_npoint(), 0);
         assert_eq!(triangle.voronoi_nedge(), 0);
         Ok(())
     }
 
     #[test]
-    fn mesh_2_works() -> Result<(), StrError> {
+    fn mesh_2_works() -> Result<(), Error> {
         let mut triangle = Triangle::new(3, Some(3), None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1112,20 +1722,286 @@ mod tests {
     }
 
     #[test]
-    fn get_methods_work_with_wrong_indices() -> Result<(), StrError> {
+    fn mesh_markers_work() -> Result<(), Error> {
+        let mut triangle = Triangle::new(4, Some(4), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?;
+        triangle
+            .set_segment_marker(0, -10)?
+            .set_segment_marker(1, -20)?
+            .set_segment_marker(2, -30)?;
+        triangle.generate_mesh(false, false, Some(0.1), None)?;
+        assert!(triangle.nsegment() > 4);
+        for i in 0..triangle.nsegment() {
+            let a = triangle.segment_point(i, 0);
+            let b = triangle.segment_point(i, 1);
+            let (xa, ya) = (triangle.point(a, 0), triangle.point(a, 1));
+            let (xb, yb) = (triangle.point(b, 0), triangle.point(b, 1));
+            let correct = if ya == 0.0 && yb == 0.0 {
+                -10
+            } else if xa == 1.0 && xb == 1.0 {
+                -20
+            } else if ya == 1.0 && yb == 1.0 {
+                -30
+            } else {
+                1 // the boundary marker is assigned by Triangle
+            };
+            assert_eq!(triangle.segment_marker(i), correct);
+        }
+        for e in 0..triangle.nedge() {
+            let a = triangle.edge_point(e, 0);
+            let b = triangle.edge_point(e, 1);
+            let (ya, yb) = (triangle.point(a, 1), triangle.point(b, 1));
+            if ya == 0.0 && yb == 0.0 {
+                assert_eq!(triangle.edge_marker(e), -10);
+            }
+        }
+        for p in 0..triangle.npoint() {
+            let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+            if y == 0.0 && x > 0.0 && x < 1.0 {
+                assert_eq!(triangle.point_marker(p), -10);
+            }
+            if x > 0.0 && x < 1.0 && y > 0.0 && y < 1.0 {
+                assert_eq!(triangle.point_marker(p), 0);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_captures_some_errors() -> Result<(), Error> {
+        let mut triangle = Triangle::from_input(
+            &[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+            &[[0, 1], [1, 2], [2, 0]],
+            &[],
+            &[],
+        )?;
+        assert_eq!(
+            triangle.refine_mesh(false, false, &[], None).err(),
+            Some(Error::InvalidInput(
+                "there is no mesh to refine (call generate_mesh first)"
+            ))
+        );
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(
+            triangle.refine_mesh(false, false, &[0.1, 0.1], None).err(),
+            Some(Error::InvalidInput(
+                "the number of max areas must equal the number of triangles"
+            ))
+        );
+        assert_eq!(
+            triangle.refine_mesh(false, false, &[f64::NAN], None).err(),
+            Some(Error::InvalidInput("the max areas must not be NaN"))
+        );
+        triangle.generate_mesh(false, true, None, None)?;
+        assert_eq!(
+            triangle.refine_mesh(false, false, &[0.1], None).err(),
+            Some(Error::InvalidInput(
+                "the mesh to refine must have linear triangles (nnode = 3)"
+            ))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refine_mesh_works() -> Result<(), Error> {
+        // two regions split by the segment at x = 0.5
+        let mut triangle = Triangle::from_input(
+            &[[0.0, 0.0], [0.5, 0.0], [1.0, 0.0], [1.0, 1.0], [0.5, 1.0], [0.0, 1.0]],
+            &[[0, 1], [1, 2], [2, 3], [3, 4], [4, 5], [5, 0], [1, 4]],
+            &[(0.25, 0.5, 1, None), (0.75, 0.5, 2, None)],
+            &[],
+        )?;
+        triangle.set_segment_marker(0, -10)?.set_segment_marker(6, -70)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        let area = |triangle: &Triangle, t: usize| {
+            let (a, b, c) = (
+                triangle.triangle_node(t, 0),
+                triangle.triangle_node(t, 1),
+                triangle.triangle_node(t, 2),
+            );
+            let (xa, ya) = (triangle.point(a, 0), triangle.point(a, 1));
+            let (xb, yb) = (triangle.point(b, 0), triangle.point(b, 1));
+            let (xc, yc) = (triangle.point(c, 0), triangle.point(c, 1));
+            ((xb - xa) * (yc - ya) - (xc - xa) * (yb - ya)) / 2.0
+        };
+
+        // refine the left region only (twice)
+        for max_area in [0.05, 0.01] {
+            let max_areas: Vec<_> = (0..triangle.ntriangle())
+                .map(|t| {
+                    if triangle.triangle_attribute(t) == 1 {
+                        max_area
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            triangle.refine_mesh(false, false, &max_areas, None)?;
+            let mut total = 0.0;
+            for t in 0..triangle.ntriangle() {
+                let a = area(&triangle, t);
+                if triangle.triangle_attribute(t) == 1 {
+                    assert!(a <= max_area);
+                }
+                total += a;
+            }
+            assert!(f64::abs(total - 1.0) < 1e-14);
+        }
+        let attributes = triangle.triangle_attributes();
+        let nleft = attributes.iter().filter(|a| **a == 1).count();
+        let nright = attributes.iter().filter(|a| **a == 2).count();
+        assert_eq!(nleft + nright, triangle.ntriangle());
+        assert!(nleft >= 50);
+        assert!(nright < nleft / 2);
+
+        // the segments and markers are kept
+        let mut nsplit = 0;
+        for i in 0..triangle.nsegment() {
+            let (a, b) = (triangle.segment_point(i, 0), triangle.segment_point(i, 1));
+            let (xa, ya) = (triangle.point(a, 0), triangle.point(a, 1));
+            let (xb, yb) = (triangle.point(b, 0), triangle.point(b, 1));
+            if xa == 0.5 && xb == 0.5 {
+                assert_eq!(triangle.segment_marker(i), -70);
+                nsplit += 1;
+            } else if ya == 0.0 && yb == 0.0 && xa <= 0.5 && xb <= 0.5 {
+                assert_eq!(triangle.segment_marker(i), -10);
+            }
+        }
+        assert!(nsplit > 1);
+        for p in 0..triangle.npoint() {
+            let (x, y) = (triangle.point(p, 0), triangle.point(p, 1));
+            if x == 0.5 && y > 0.0 && y < 1.0 {
+                assert_eq!(triangle.point_marker(p), -70);
+            }
+        }
+
+        // the last refinement may be quadratic
+        let ntriangle = triangle.ntriangle();
+        triangle.refine_mesh(false, true, &vec![0.0; ntriangle], None)?;
+        assert_eq!(triangle.nnode(), 6);
+        assert_eq!(triangle.ntriangle(), ntriangle);
+        Ok(())
+    }
+
+    #[test]
+    fn bulk_methods_work() -> Result<(), Error> {
+        let mut triangle = Triangle::new(4, Some(4), Some(1), None)?;
+        assert_eq!(triangle.points().len(), 0);
+        assert_eq!(triangle.triangles().len(), 0);
+        assert_eq!(triangle.triangle_points().count(), 0);
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?;
+        triangle.set_segment_marker(0, -10)?;
+        triangle.set_region(0, 0.5, 0.5, 7, None)?;
+        triangle.generate_mesh(false, true, Some(0.1), None)?;
+        let (npoint, ntriangle, nnode) = (triangle.npoint(), triangle.ntriangle(), triangle.nnode());
+        let points = triangle.points();
+        assert_eq!(points.len(), 2 * npoint);
+        for p in 0..npoint {
+            assert_eq!(points[2 * p], triangle.point(p, 0));
+            assert_eq!(points[2 * p + 1], triangle.point(p, 1));
+        }
+        let markers = triangle.point_markers();
+        assert_eq!(markers.len(), npoint);
+        for (p, marker) in markers.iter().enumerate() {
+            assert_eq!(*marker, triangle.point_marker(p));
+        }
+        let cells = triangle.triangles();
+        assert_eq!(cells.len(), ntriangle * nnode);
+        for (i, coords) in triangle.triangle_points().enumerate() {
+            assert_eq!(coords.len(), nnode);
+            for m in 0..nnode {
+                let p = triangle.triangle_node(i, m);
+                assert_eq!(cells[i * nnode + m], p);
+                assert_eq!(coords[m], [triangle.point(p, 0), triangle.point(p, 1)]);
+            }
+        }
+        assert_eq!(triangle.triangle_attributes(), vec![7; ntriangle]);
+        for (i, neighbors) in triangle.triangle_neighbors().iter().enumerate() {
+            for (side, neighbor) in neighbors.iter().enumerate() {
+                assert_eq!(*neighbor, triangle.triangle_neighbor(i, side));
+            }
+        }
+        let segments = triangle.segments();
+        let segment_markers = triangle.segment_markers();
+        assert_eq!(segments.len(), triangle.nsegment());
+        assert_eq!(segment_markers.len(), triangle.nsegment());
+        for i in 0..triangle.nsegment() {
+            assert_eq!(
+                segments[i],
+                [triangle.segment_point(i, 0), triangle.segment_point(i, 1)]
+            );
+            assert_eq!(segment_markers[i], triangle.segment_marker(i));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn get_methods_work_with_wrong_indices() -> Result<(), Error> {
         let triangle = Triangle::new(3, None, None, None)?;
-        assert_eq!(triangle.point(100, 0), 0.0);
-        assert_eq!(triangle.point(0, 100), 0.0);
-        assert_eq!(triangle.triangle_attribute(100), 0);
-        assert_eq!(triangle.voronoi_point(100, 0), 0.0);
-        assert_eq!(triangle.voronoi_point(0, 100), 0.0);
-        assert_eq!(triangle.voronoi_edge_point_a(100), 0,);
-        assert_eq!(format!("{:?}", triangle.voronoi_edge_point_b(100)), "Index(0)");
+        assert_eq!(triangle.triangle_neighbor(100, 0), None);
+        assert_eq!(triangle.triangle_neighbor(0, 100), None);
+        // the other methods panic in debug builds
+        if cfg!(debug_assertions) {
+            let calls: [fn(&Triangle); 16] = [
+                |t| _ = t.point(100, 0),
+                |t| _ = t.point(0, 100),
+                |t| _ = t.triangle_node(100, 0),
+                |t| _ = t.triangle_node(0, 100),
+                |t| _ = t.triangle_attribute(100),
+                |t| _ = t.point_marker(100),
+                |t| _ = t.segment_point(100, 0),
+                |t| _ = t.segment_point(0, 100),
+                |t| _ = t.segment_marker(100),
+                |t| _ = t.edge_point(100, 0),
+                |t| _ = t.edge_point(0, 100),
+                |t| _ = t.edge_marker(100),
+                |t| _ = t.voronoi_point(100, 0),
+                |t| _ = t.voronoi_point(0, 100),
+                |t| _ = t.voronoi_edge_point_a(100),
+                |t| _ = t.voronoi_edge_point_b(100),
+            ];
+            for call in calls {
+                assert!(panic::catch_unwind(AssertUnwindSafe(|| call(&triangle))).is_err());
+            }
+        } else {
+            assert_eq!(triangle.point(100, 0), 0.0);
+            assert_eq!(triangle.point(0, 100), 0.0);
+            assert_eq!(triangle.triangle_attribute(100), 0);
+            assert_eq!(triangle.point_marker(100), 0);
+            assert_eq!(triangle.segment_point(100, 0), 0);
+            assert_eq!(triangle.segment_point(0, 100), 0);
+            assert_eq!(triangle.segment_marker(100), 0);
+            assert_eq!(triangle.edge_point(100, 0), 0);
+            assert_eq!(triangle.edge_point(0, 100), 0);
+            assert_eq!(triangle.edge_marker(100), 0);
+            assert_eq!(triangle.voronoi_point(100, 0), 0.0);
+            assert_eq!(triangle.voronoi_point(0, 100), 0.0);
+            assert_eq!(triangle.voronoi_edge_point_a(100), 0,);
+            assert_eq!(format!("{:?}", triangle.voronoi_edge_point_b(100)), "Index(0)");
+        }
         Ok(())
     }
 
     #[test]
-    fn draw_triangles_works() -> Result<(), StrError> {
+    fn draw_triangles_works() -> Result<(), Box<dyn std::error::Error>> {
         let mut triangle = Triangle::new(3, Some(3), None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1152,7 +2028,7 @@ mod tests {
 
     #[cfg(feature = "plotpy")]
     #[test]
-    fn draw_voronoi_works() -> Result<(), StrError> {
+    fn draw_voronoi_works() -> Result<(), Box<dyn std::error::Error>> {
         let mut triangle = Triangle::new(5, None, None, None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1177,7 +2053,7 @@ mod tests {
     }
 
     #[test]
-    fn mesh_3_works() -> Result<(), StrError> {
+    fn mesh_3_works() -> Result<(), Box<dyn std::error::Error>> {
         let mut triangle = Triangle::new(4, Some(3), Some(1), None)?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1208,7 +2084,7 @@ mod tests {
     }
 
     #[test]
-    fn mesh_4_works() -> Result<(), StrError> {
+    fn mesh_4_works() -> Result<(), Box<dyn std::error::Error>> {
         let mut triangle = Triangle::new(12, Some(10), Some(2), Some(1))?;
         triangle
             .set_point(0, 0.0, 0.0)?
@@ -1241,6 +2117,23 @@ mod tests {
         assert_eq!(triangle.ntriangle(), 14);
         assert_eq!(triangle.triangle_attribute(0), 1);
         assert_eq!(triangle.triangle_attribute(12), 2);
+        assert_eq!(triangle.nedge(), 28);
+        for tri in 0..triangle.ntriangle() {
+            for side in 0..3 {
+                let a = triangle.triangle_node(tri, side);
+                let b = triangle.triangle_node(tri, (side + 1) % 3);
+                let mid = triangle.triangle_node(tri, 3 + side);
+                match triangle.triangle_neighbor(tri, side) {
+                    Some(n) => {
+                        let nodes: Vec<_> = (0..6).map(|m| triangle.triangle_node(n, m)).collect();
+                        assert!(nodes.contains(&a));
+                        assert!(nodes.contains(&b));
+                        assert!(nodes.contains(&mid));
+                    }
+                    None => assert_ne!(triangle.point_marker(mid), 0),
+                }
+            }
+        }
 
         #[cfg(feature = "plotpy")]
         {
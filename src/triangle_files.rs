@@ -0,0 +1,553 @@
+use crate::constants;
+use crate::file_io::{read_file, read_node_section, write_file, DataLines};
+use crate::{Error, Triangle};
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Reads a Triangle's .node file and allocates a Triangle with the points
+///
+/// The file format is described in [Triangle's website](https://www.cs.cmu.edu/~quake/triangle.node.html).
+/// The points may be numbered from zero or one (the first point determines the numbering).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Point attributes and boundary markers are ignored.
+pub fn read_triangle_node<P>(full_path: &P) -> Result<Triangle, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let contents = read_file(full_path)?;
+    let mut lines = DataLines::new(&contents);
+    let (points, _) = read_node_section(&mut lines, 2)?;
+    let mut triangle = Triangle::new(points.len(), None, None, None)?;
+    for (index, x) in points.iter().enumerate() {
+        triangle.set_point(index, x[0], x[1])?;
+    }
+    Ok(triangle)
+}
+
+/// Reads a Triangle's .poly file and allocates a Triangle with the Planar Straight Line Graph (PSLG)
+///
+/// The file format is described in [Triangle's website](https://www.cs.cmu.edu/~quake/triangle.poly.html).
+/// The points, segments (and markers), holes, and regions (attributes and maximum area constraints)
+/// are read from the file. If the number of points in the .poly file is zero, the points are read
+/// from the .node file with the same name (e.g., `box.node` for `box.poly`).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+///
+/// # Warning
+///
+/// Point attributes and boundary markers are ignored. The regional attributes must be non-negative integers.
+pub fn read_triangle_poly<P>(full_path: &P) -> Result<Triangle, Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let contents = read_file(full_path)?;
+    let mut lines = DataLines::new(&contents);
+
+    // points
+    let (mut points, mut first_id) = read_node_section(&mut lines, 2)?;
+    if points.is_empty() {
+        let node_path = Path::new(full_path).with_extension("node");
+        let node_contents = read_file(&node_path)?;
+        let mut node_lines = DataLines::new(&node_contents);
+        (points, first_id) = read_node_section(&mut node_lines, 2)?;
+    }
+    let npoint = points.len();
+
+    // segments
+    let fields = lines.expect_fields(1, "cannot read the segments header")?;
+    let nsegment: usize = lines.parse(fields[0], "cannot parse the number of segments")?;
+    let nmarker: usize = match fields.get(1) {
+        Some(field) => lines.parse(field, "cannot parse the number of segment markers")?,
+        None => 0,
+    };
+    let mut segments = Vec::new();
+    for _ in 0..nsegment {
+        let fields = lines.expect_fields(3 + nmarker, "cannot read segment")?;
+        let mut ids = [0; 2];
+        for side in 0..2 {
+            let id: usize = lines.parse(fields[1 + side], "cannot parse the point id of segment")?;
+            if id < first_id || id - first_id >= npoint {
+                return Err(lines.error("the point id of segment is out of bounds"));
+            }
+            ids[side] = id - first_id;
+        }
+        let marker: Option<i32> = if nmarker > 0 {
+            Some(lines.parse(fields[3], "cannot parse the marker of segment")?)
+        } else {
+            None
+        };
+        segments.push((ids[0], ids[1], marker));
+    }
+
+    // holes
+    let fields = lines.expect_fields(1, "cannot read the holes header")?;
+    let nhole: usize = lines.parse(fields[0], "cannot parse the number of holes")?;
+    let mut holes = Vec::new();
+    for _ in 0..nhole {
+        let fields = lines.expect_fields(3, "cannot read hole")?;
+        let x: f64 = lines.parse(fields[1], "cannot parse the x-coordinate of hole")?;
+        let y: f64 = lines.parse(fields[2], "cannot parse the y-coordinate of hole")?;
+        holes.push((x, y));
+    }
+
+    // regions (optional)
+    let mut regions = Vec::new();
+    if let Some(fields) = lines.next_fields() {
+        let nregion: usize = lines.parse(fields[0], "cannot parse the number of regions")?;
+        for _ in 0..nregion {
+            let fields = lines.expect_fields(4, "cannot read region")?;
+            let x: f64 = lines.parse(fields[1], "cannot parse the x-coordinate of region")?;
+            let y: f64 = lines.parse(fields[2], "cannot parse the y-coordinate of region")?;
+            let value: f64 = lines.parse(fields[3], "cannot parse the attribute of region")?;
+            if value < 0.0 || value.fract() != 0.0 {
+                return Err(lines.error("the attribute of region must be a non-negative integer"));
+            }
+            let max_area: Option<f64> = match fields.get(4) {
+                Some(field) => {
+                    let area: f64 = lines.parse(field, "cannot parse the maximum area of region")?;
+                    if area > 0.0 {
+                        Some(area)
+                    } else {
+                        None
+                    }
+                }
+                None => None,
+            };
+            regions.push((x, y, value as usize, max_area));
+        }
+    }
+
+    // allocate and set the data
+    let mut triangle = Triangle::new(
+        npoint,
+        if nsegment > 0 { Some(nsegment) } else { None },
+        if regions.is_empty() { None } else { Some(regions.len()) },
+        if nhole > 0 { Some(nhole) } else { None },
+    )?;
+    for (index, x) in points.iter().enumerate() {
+        triangle.set_point(index, x[0], x[1])?;
+    }
+    for (index, (a, b, marker)) in segments.iter().enumerate() {
+        triangle.set_segment(index, *a, *b)?;
+        if let Some(m) = marker {
+            triangle.set_segment_marker(index, *m)?;
+        }
+    }
+    for (index, (x, y)) in holes.iter().enumerate() {
+        triangle.set_hole(index, *x, *y)?;
+    }
+    for (index, (x, y, attribute, max_area)) in regions.iter().enumerate() {
+        triangle.set_region(index, *x, *y, *attribute, *max_area)?;
+    }
+    Ok(triangle)
+}
+
+/// Writes the points of the generated mesh as a Triangle's .node file
+///
+/// The points are numbered from zero and the boundary markers are written (see [Triangle::point_marker]).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_triangle_node<P>(triangle: &Triangle, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let npoint = triangle.npoint();
+    if npoint < 1 {
+        return Err(Error::InvalidInput("there are no points to write"));
+    }
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} 2 0 1", npoint).unwrap();
+    for index in 0..npoint {
+        writeln!(
+            &mut buffer,
+            "{} {} {} {}",
+            index,
+            triangle.point(index, 0),
+            triangle.point(index, 1),
+            triangle.point_marker(index)
+        )
+        .unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+/// Writes the triangles of the generated mesh as a Triangle's .ele file
+///
+/// The nodes are written in Triangle's order; i.e., the middle nodes (if any) are opposite
+/// to the corners 0, 1, and 2, respectively. The attribute of each triangle is written as
+/// the single attribute (see [Triangle::triangle_attribute]).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_triangle_ele<P>(triangle: &Triangle, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ntriangle = triangle.ntriangle();
+    if ntriangle < 1 {
+        return Err(Error::InvalidInput("there are no triangles to write"));
+    }
+    let nnode = triangle.nnode();
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} {} 1", ntriangle, nnode).unwrap();
+    for index in 0..ntriangle {
+        write!(&mut buffer, "{}", index).unwrap();
+        for corner in 0..nnode {
+            let m = constants::TRIANGLE_TO_TRITET[corner];
+            write!(&mut buffer, " {}", triangle.triangle_node(index, m)).unwrap();
+        }
+        writeln!(&mut buffer, " {}", triangle.triangle_attribute(index)).unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+/// Writes the edges of the generated mesh as a Triangle's .edge file
+///
+/// The boundary markers of the edges are written (see [Triangle::edge_marker]).
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_triangle_edge<P>(triangle: &Triangle, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let nedge = triangle.nedge();
+    if nedge < 1 {
+        return Err(Error::InvalidInput("there are no edges to write"));
+    }
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} 1", nedge).unwrap();
+    for index in 0..nedge {
+        writeln!(
+            &mut buffer,
+            "{} {} {} {}",
+            index,
+            triangle.edge_point(index, 0),
+            triangle.edge_point(index, 1),
+            triangle.edge_marker(index)
+        )
+        .unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+/// Writes the neighbors of the triangles of the generated mesh as a Triangle's .neigh file
+///
+/// The neighbors are written in Triangle's order; i.e., the i-th neighbor is opposite to
+/// the i-th corner. A missing neighbor (on the boundary) is written as -1.
+///
+/// # Input
+///
+/// * `full_path` -- may be a String, &str, or Path
+pub fn write_triangle_neigh<P>(triangle: &Triangle, full_path: &P) -> Result<(), Error>
+where
+    P: AsRef<OsStr> + ?Sized,
+{
+    let ntriangle = triangle.ntriangle();
+    if ntriangle < 1 {
+        return Err(Error::InvalidInput("there are no triangles to write"));
+    }
+    let mut buffer = String::new();
+    writeln!(&mut buffer, "{} 3", ntriangle).unwrap();
+    for index in 0..ntriangle {
+        write!(&mut buffer, "{}", index).unwrap();
+        for neighbor in 0..3 {
+            let side = constants::TRIANGLE_TO_TRITET_SIDE[neighbor];
+            match triangle.triangle_neighbor(index, side) {
+                Some(id) => write!(&mut buffer, " {}", id).unwrap(),
+                None => write!(&mut buffer, " -1").unwrap(),
+            }
+        }
+        writeln!(&mut buffer).unwrap();
+    }
+    write_file(full_path, &buffer)
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        read_triangle_node, read_triangle_poly, write_triangle_edge, write_triangle_ele, write_triangle_neigh,
+        write_triangle_node,
+    };
+    use crate::file_io::{read_file, write_file};
+    use crate::{Error, Triangle};
+
+    #[test]
+    fn read_triangle_node_works() -> Result<(), Error> {
+        let file_path = "/tmp/tritet/test_read_triangle_node.node";
+        write_file(
+            file_path,
+            "# three points numbered from one\n\
+             3 2 1 1\n\
+             1 0.0 0.0 10.0 1\n\
+             2 1.0 0.0 20.0 1\n\
+             3 0.0 1.0 30.0 1\n",
+        )?;
+        let mut triangle = read_triangle_node(file_path)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.npoint(), 3);
+        assert_eq!(triangle.ntriangle(), 1);
+        assert_eq!(triangle.point(1, 0), 1.0);
+        assert_eq!(triangle.point(2, 1), 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn read_triangle_poly_captures_some_errors() -> Result<(), Error> {
+        match read_triangle_poly("/tmp/tritet/__does_not_exist__.poly").err() {
+            Some(Error::Io { message, path, source }) => {
+                assert_eq!(message, "cannot read file");
+                assert_eq!(path.as_deref(), Some("/tmp/tritet/__does_not_exist__.poly"));
+                assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+            }
+            _ => panic!("an Io error should be returned"),
+        }
+        let file_path = "/tmp/tritet/test_read_triangle_poly_errors.poly";
+        write_file(file_path, "3 3 0 0\n")?;
+        assert_eq!(
+            read_triangle_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 1,
+                message: "the space dimension must be 2"
+            })
+        );
+        write_file(
+            file_path,
+            "3 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 0.0 1.0\n3 0\n0 0 1\n1 1 2\n2 2 3\n",
+        )?;
+        assert_eq!(
+            read_triangle_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 8,
+                message: "the point id of segment is out of bounds"
+            })
+        );
+        write_file(
+            file_path,
+            "3 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 0.0 1.0\n3 0\n0 0 1\n1 1 2\n",
+        )?;
+        assert_eq!(
+            read_triangle_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 8,
+                message: "cannot read segment"
+            })
+        );
+        write_file(
+            file_path,
+            "3 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 0.0 1.0\n3 0\n0 0 1\n1 1 2\n2 2 0\n0\n1\n0 0.1 0.1 -1\n",
+        )?;
+        assert_eq!(
+            read_triangle_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 11,
+                message: "the attribute of region must be a non-negative integer"
+            })
+        );
+        // the numbers of segments and holes are not used to preallocate memory
+        write_file(
+            file_path,
+            "3 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 0.0 1.0\n99999999999999999 0\n0 0 1\n",
+        )?;
+        assert_eq!(
+            read_triangle_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 7,
+                message: "cannot read segment"
+            })
+        );
+        write_file(
+            file_path,
+            "3 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 0.0 1.0\n3 0\n0 0 1\n1 1 2\n2 2 0\n99999999999999999\n",
+        )?;
+        assert_eq!(
+            read_triangle_poly(file_path).err(),
+            Some(Error::InvalidFile {
+                line: 10,
+                message: "cannot read hole"
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_triangle_poly_works() -> Result<(), Error> {
+        // same as the mesh generation example in the documentation of Triangle, with one-based numbering
+        let file_path = "/tmp/tritet/test_read_triangle_poly.poly";
+        write_file(
+            file_path,
+            "# points\n\
+             12 2 0 0\n\
+             1 0.0 0.0\n2 1.0 0.0\n3 1.0 1.0\n4 0.0 1.0\n\
+             5 0.2 0.2\n6 0.8 0.2\n7 0.8 0.8\n8 0.2 0.8\n\
+             9 0.0 0.5\n10 0.2 0.5\n11 0.8 0.5\n12 1.0 0.5\n\
+             # segments\n\
+             10 1\n\
+             1 1 2 -10\n2 2 3 -20\n3 3 4 -30\n4 4 1 -40\n\
+             5 5 6 0\n6 6 7 0\n7 7 8 0\n8 8 5 0\n\
+             9 9 10 0\n10 11 12 0\n\
+             # holes\n\
+             1\n\
+             1 0.5 0.5\n\
+             # regions\n\
+             2\n\
+             1 0.1 0.1 1 -1\n\
+             2 0.1 0.9 2\n",
+        )?;
+        let mut triangle = read_triangle_poly(file_path)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        assert_eq!(triangle.ntriangle(), 14);
+        let mut bottom_markers = 0;
+        for index in 0..triangle.nsegment() {
+            let a = triangle.segment_point(index, 0);
+            let b = triangle.segment_point(index, 1);
+            if triangle.point(a, 1) == 0.0 && triangle.point(b, 1) == 0.0 {
+                assert_eq!(triangle.segment_marker(index), -10);
+                bottom_markers += 1;
+            }
+        }
+        assert!(bottom_markers > 0);
+        for index in 0..triangle.ntriangle() {
+            let y = triangle.point(triangle.triangle_node(index, 0), 1);
+            let attribute = triangle.triangle_attribute(index);
+            if y < 0.5 {
+                assert_eq!(attribute, 1);
+            } else if y > 0.5 {
+                assert_eq!(attribute, 2);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn read_triangle_poly_with_separate_node_file_works() -> Result<(), Error> {
+        let node_path = "/tmp/tritet/test_read_triangle_poly_separate.node";
+        let poly_path = "/tmp/tritet/test_read_triangle_poly_separate.poly";
+        write_file(node_path, "4 2 0 0\n0 0.0 0.0\n1 1.0 0.0\n2 1.0 1.0\n3 0.0 1.0\n")?;
+        write_file(poly_path, "0 2 0 0\n4 0\n0 0 1\n1 1 2\n2 2 3\n3 3 0\n0\n")?;
+        let mut triangle = read_triangle_poly(poly_path)?;
+        triangle.generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.npoint(), 4);
+        assert_eq!(triangle.ntriangle(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn write_methods_capture_some_errors() -> Result<(), Error> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        let file_path = "/tmp/tritet/test_write_triangle_errors.txt";
+        assert_eq!(
+            write_triangle_node(&triangle, file_path).err(),
+            Some(Error::InvalidInput("there are no points to write"))
+        );
+        assert_eq!(
+            write_triangle_ele(&triangle, file_path).err(),
+            Some(Error::InvalidInput("there are no triangles to write"))
+        );
+        assert_eq!(
+            write_triangle_edge(&triangle, file_path).err(),
+            Some(Error::InvalidInput("there are no edges to write"))
+        );
+        assert_eq!(
+            write_triangle_neigh(&triangle, file_path).err(),
+            Some(Error::InvalidInput("there are no triangles to write"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_methods_work() -> Result<(), Error> {
+        let mut triangle = Triangle::new(4, Some(4), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 1.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?;
+        triangle.set_segment_marker(0, -10)?;
+        triangle.generate_mesh(false, true, None, None)?;
+        assert_eq!(triangle.ntriangle(), 2);
+
+        // node
+        let file_path = "/tmp/tritet/test_write_triangle.node";
+        write_triangle_node(&triangle, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1 + triangle.npoint());
+        assert_eq!(lines[0], "9 2 0 1");
+        assert_eq!(lines[1], "0 0 0 -10");
+        assert_eq!(lines[3], "2 1 1 1");
+
+        // ele (Triangle's ordering of middle nodes)
+        let file_path = "/tmp/tritet/test_write_triangle.ele";
+        write_triangle_ele(&triangle, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "2 6 1");
+        for index in 0..2 {
+            let correct = format!(
+                "{} {} {} {} {} {} {} 0",
+                index,
+                triangle.triangle_node(index, 0),
+                triangle.triangle_node(index, 1),
+                triangle.triangle_node(index, 2),
+                triangle.triangle_node(index, 4),
+                triangle.triangle_node(index, 5),
+                triangle.triangle_node(index, 3)
+            );
+            assert_eq!(lines[1 + index], correct);
+        }
+
+        // edge
+        let file_path = "/tmp/tritet/test_write_triangle.edge";
+        write_triangle_edge(&triangle, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "5 1");
+        let interior: Vec<_> = lines[1..].iter().filter(|line| line.ends_with(" 0")).collect();
+        assert_eq!(interior.len(), 1);
+
+        // neigh (the neighbor is opposite to the corner not shared by the two triangles)
+        let file_path = "/tmp/tritet/test_write_triangle.neigh";
+        write_triangle_neigh(&triangle, file_path)?;
+        let contents = read_file(file_path)?;
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "2 3");
+        for index in 0..2 {
+            let fields: Vec<i32> = lines[1 + index]
+                .split_whitespace()
+                .map(|field| field.parse().unwrap())
+                .collect();
+            let other = 1 - index;
+            for corner in 0..3 {
+                let node = triangle.triangle_node(index, corner);
+                let shared = (0..3).any(|m| triangle.triangle_node(other, m) == node);
+                let correct = if shared { -1 } else { other as i32 };
+                assert_eq!(fields[1 + corner], correct);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,341 @@
+use crate::conversion::to_g;
+use crate::{Error, Triangle};
+use std::fmt;
+
+/// Holds the upper bounds of the bins of the aspect ratio histogram of triangles (as in Triangle)
+///
+/// The last bin (index 15) holds the aspect ratios greater than the last bound.
+pub const TRIANGLE_ASPECT_RATIO_BINS: [f64; 15] = [
+    1.5, 2.0, 2.5, 3.0, 4.0, 6.0, 10.0, 15.0, 25.0, 50.0, 100.0, 300.0, 1000.0, 10000.0, 100000.0,
+];
+
+/// Holds the quality metrics of a triangle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleMetrics {
+    /// Smallest interior angle (degrees)
+    pub min_angle: f64,
+
+    /// Largest interior angle (degrees)
+    pub max_angle: f64,
+
+    /// Longest edge divided by the shortest altitude (2/√3 ≈ 1.1547 for the equilateral triangle)
+    pub aspect_ratio: f64,
+
+    /// Radius of the circumcircle divided by the shortest edge (1/√3 ≈ 0.5774 for the equilateral triangle)
+    pub radius_edge_ratio: f64,
+
+    /// Area (positive if the nodes are counterclockwise)
+    pub area: f64,
+}
+
+/// Holds the quality metrics of a mesh of triangles
+///
+/// The statistics are equivalent to the ones printed by Triangle with the `-V` switch.
+/// The [fmt::Display] implementation prints the statistics in the same layout.
+#[derive(Clone, Debug)]
+pub struct TriangleQuality {
+    /// Metrics of each triangle
+    pub triangles: Vec<TriangleMetrics>,
+
+    /// Smallest area
+    pub min_area: f64,
+
+    /// Largest area
+    pub max_area: f64,
+
+    /// Shortest edge
+    pub min_edge: f64,
+
+    /// Longest edge
+    pub max_edge: f64,
+
+    /// Shortest altitude
+    pub min_altitude: f64,
+
+    /// Smallest angle (degrees)
+    pub min_angle: f64,
+
+    /// Largest angle (degrees)
+    pub max_angle: f64,
+
+    /// Largest aspect ratio
+    pub max_aspect_ratio: f64,
+
+    /// Largest radius-edge ratio
+    pub max_radius_edge_ratio: f64,
+
+    /// Number of triangles in each bin of aspect ratios (see [TRIANGLE_ASPECT_RATIO_BINS])
+    pub aspect_ratio_histogram: [usize; 16],
+
+    /// Number of angles in each 10-degree bin (0-10, 10-20, ..., 170-180)
+    pub angle_histogram: [usize; 18],
+}
+
+impl Triangle {
+    /// Computes the quality metrics of the generated triangles
+    ///
+    /// Only the corners of the triangles are used (i.e., the middle nodes are ignored).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Triangle};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let mut triangle = Triangle::new(4, Some(4), None, None)?;
+    ///     triangle
+    ///         .set_point(0, 0.0, 0.0)?
+    ///         .set_point(1, 1.0, 0.0)?
+    ///         .set_point(2, 1.0, 1.0)?
+    ///         .set_point(3, 0.0, 1.0)?;
+    ///     triangle
+    ///         .set_segment(0, 0, 1)?
+    ///         .set_segment(1, 1, 2)?
+    ///         .set_segment(2, 2, 3)?
+    ///         .set_segment(3, 3, 0)?;
+    ///     triangle.generate_mesh(false, false, Some(0.05), Some(25.0))?;
+    ///     let quality = triangle.quality()?;
+    ///     assert!(quality.min_angle >= 25.0);
+    ///     println!("{}", quality);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn quality(&self) -> Result<TriangleQuality, Error> {
+        let ntriangle = self.ntriangle();
+        if ntriangle < 1 {
+            return Err(Error::InvalidInput("there are no triangles to evaluate"));
+        }
+        let mut quality = TriangleQuality {
+            triangles: Vec::with_capacity(ntriangle),
+            min_area: f64::MAX,
+            max_area: f64::MIN,
+            min_edge: f64::MAX,
+            max_edge: 0.0,
+            min_altitude: f64::MAX,
+            min_angle: 180.0,
+            max_angle: 0.0,
+            max_aspect_ratio: 0.0,
+            max_radius_edge_ratio: 0.0,
+            aspect_ratio_histogram: [0; 16],
+            angle_histogram: [0; 18],
+        };
+        for x in self.triangle_points() {
+            // edges opposite to each corner
+            let mut edges = [[0.0; 2]; 3];
+            let mut lengths = [0.0; 3];
+            for i in 0..3 {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                edges[i] = [x[k][0] - x[j][0], x[k][1] - x[j][1]];
+                lengths[i] = f64::sqrt(edges[i][0] * edges[i][0] + edges[i][1] * edges[i][1]);
+            }
+            let area = 0.5 * (edges[2][0] * edges[0][1] - edges[2][1] * edges[0][0]);
+            let shortest = lengths.iter().copied().fold(f64::MAX, f64::min);
+            let longest = lengths.iter().copied().fold(0.0, f64::max);
+
+            // angle at each corner (between the two edges sharing the corner)
+            let mut min_angle = 180.0;
+            let mut max_angle = 0.0;
+            for i in 0..3 {
+                let (a, b) = (edges[(i + 1) % 3], edges[(i + 2) % 3]);
+                let cross = a[0] * b[1] - a[1] * b[0];
+                let dot = a[0] * b[0] + a[1] * b[1];
+                let angle = 180.0 - f64::atan2(f64::abs(cross), dot).to_degrees();
+                min_angle = f64::min(min_angle, angle);
+                max_angle = f64::max(max_angle, angle);
+                quality.angle_histogram[usize::min((angle / 10.0) as usize, 17)] += 1;
+            }
+
+            // ratios
+            let altitude = 2.0 * f64::abs(area) / longest;
+            let aspect_ratio = longest / altitude;
+            let circumradius = lengths[0] * lengths[1] * lengths[2] / (4.0 * f64::abs(area));
+            let radius_edge_ratio = circumradius / shortest;
+            let bin = TRIANGLE_ASPECT_RATIO_BINS
+                .iter()
+                .position(|bound| aspect_ratio <= *bound)
+                .unwrap_or(15);
+            quality.aspect_ratio_histogram[bin] += 1;
+
+            // statistics
+            quality.min_area = f64::min(quality.min_area, area);
+            quality.max_area = f64::max(quality.max_area, area);
+            quality.min_edge = f64::min(quality.min_edge, shortest);
+            quality.max_edge = f64::max(quality.max_edge, longest);
+            quality.min_altitude = f64::min(quality.min_altitude, altitude);
+            quality.min_angle = f64::min(quality.min_angle, min_angle);
+            quality.max_angle = f64::max(quality.max_angle, max_angle);
+            quality.max_aspect_ratio = f64::max(quality.max_aspect_ratio, aspect_ratio);
+            quality.max_radius_edge_ratio = f64::max(quality.max_radius_edge_ratio, radius_edge_ratio);
+            quality.triangles.push(TriangleMetrics {
+                min_angle,
+                max_angle,
+                aspect_ratio,
+                radius_edge_ratio,
+                area,
+            });
+        }
+        Ok(quality)
+    }
+}
+
+impl fmt::Display for TriangleQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bins = &TRIANGLE_ASPECT_RATIO_BINS;
+        let h = &self.aspect_ratio_histogram;
+        writeln!(f, "Mesh quality statistics:\n")?;
+        writeln!(
+            f,
+            "  Smallest area: {:>16}   |  Largest area: {:>16}",
+            to_g(self.min_area, 5),
+            to_g(self.max_area, 5)
+        )?;
+        writeln!(
+            f,
+            "  Shortest edge: {:>16}   |  Longest edge: {:>16}",
+            to_g(self.min_edge, 5),
+            to_g(self.max_edge, 5)
+        )?;
+        writeln!(
+            f,
+            "  Shortest altitude: {:>12}   |  Largest aspect ratio: {:>8}\n",
+            to_g(self.min_altitude, 5),
+            to_g(self.max_aspect_ratio, 5)
+        )?;
+        writeln!(f, "  Triangle aspect ratio histogram:")?;
+        writeln!(
+            f,
+            "  1.1547 - {:<6}    :  {:>8}    | {:>6} - {:<6}     :  {:>8}",
+            bins[0], h[0], bins[7], bins[8], h[8]
+        )?;
+        for i in 1..7 {
+            writeln!(
+                f,
+                "  {:>6} - {:<6}    :  {:>8}    | {:>6} - {:<6}     :  {:>8}",
+                bins[i - 1],
+                bins[i],
+                h[i],
+                bins[i + 7],
+                bins[i + 8],
+                h[i + 8]
+            )?;
+        }
+        writeln!(
+            f,
+            "  {:>6} - {:<6}    :  {:>8}    | {:>6} -            :  {:>8}",
+            bins[6], bins[7], h[7], bins[14], h[15]
+        )?;
+        writeln!(f, "  (Aspect ratio is longest edge divided by shortest altitude)\n")?;
+        writeln!(
+            f,
+            "  Smallest angle: {:>15}   |  Largest angle: {:>15}\n",
+            to_g(self.min_angle, 5),
+            to_g(self.max_angle, 5)
+        )?;
+        writeln!(f, "  Angle histogram:")?;
+        for i in 0..9 {
+            writeln!(
+                f,
+                "    {:>3} - {:>3} degrees:  {:>8}    |    {:>3} - {:>3} degrees:  {:>8}",
+                i * 10,
+                i * 10 + 10,
+                self.angle_histogram[i],
+                i * 10 + 90,
+                i * 10 + 100,
+                self.angle_histogram[i + 9]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Triangle};
+
+    #[test]
+    fn quality_captures_some_errors() -> Result<(), Error> {
+        let triangle = Triangle::new(3, None, None, None)?;
+        assert_eq!(
+            triangle.quality().err(),
+            Some(Error::InvalidInput("there are no triangles to evaluate"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn quality_works() -> Result<(), Error> {
+        // right triangle and equilateral triangle
+        let mut triangle = Triangle::new(4, None, None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?
+            .set_point(3, 0.5 + f64::sqrt(3.0) / 2.0, 0.5 + f64::sqrt(3.0) / 2.0)?;
+        triangle.generate_delaunay(false)?;
+        assert_eq!(triangle.ntriangle(), 2);
+        let quality = triangle.quality()?;
+        let (right, equilateral) = if quality.triangles[0].area > 0.49 {
+            (&quality.triangles[0], &quality.triangles[1])
+        } else {
+            (&quality.triangles[1], &quality.triangles[0])
+        };
+        let sqrt2 = f64::sqrt(2.0);
+        let sqrt3 = f64::sqrt(3.0);
+        assert!(f64::abs(right.area - 0.5) < 1e-15);
+        assert!(f64::abs(right.min_angle - 45.0) < 1e-13);
+        assert!(f64::abs(right.max_angle - 90.0) < 1e-13);
+        assert!(f64::abs(right.aspect_ratio - 2.0) < 1e-15); // √2 / (1/√2)
+        assert!(f64::abs(right.radius_edge_ratio - sqrt2 / 2.0) < 1e-15);
+        let a = sqrt2; // side of the equilateral triangle
+        assert!(f64::abs(equilateral.area - sqrt3 * a * a / 4.0) < 1e-15);
+        assert!(f64::abs(equilateral.min_angle - 60.0) < 1e-13);
+        assert!(f64::abs(equilateral.max_angle - 60.0) < 1e-13);
+        assert!(f64::abs(equilateral.aspect_ratio - 2.0 / sqrt3) < 1e-15);
+        assert!(f64::abs(equilateral.radius_edge_ratio - 1.0 / sqrt3) < 1e-15);
+
+        // statistics
+        assert_eq!(quality.min_area, equilateral.area.min(right.area));
+        assert_eq!(quality.max_edge, sqrt2);
+        assert!(f64::abs(quality.min_angle - 45.0) < 1e-13);
+        assert!(f64::abs(quality.max_angle - 90.0) < 1e-13);
+        assert_eq!(quality.max_aspect_ratio, right.aspect_ratio);
+        assert_eq!(quality.aspect_ratio_histogram[0], 1); // 1.1547 - 1.5
+        assert_eq!(quality.aspect_ratio_histogram.iter().sum::<usize>(), 2);
+        assert_eq!(quality.angle_histogram.iter().sum::<usize>(), 6);
+        assert_eq!(quality.angle_histogram[4], 2); // 45 degrees
+        assert_eq!(quality.angle_histogram[5] + quality.angle_histogram[6], 3); // 60 degrees (round-off)
+        assert_eq!(quality.angle_histogram[8] + quality.angle_histogram[9], 1); // 90 degrees (round-off)
+        let report = format!("{}", quality);
+        assert!(report.contains("  Smallest angle:              45   |  Largest angle:              90\n"));
+        assert!(report.contains("  1.1547 - 1.5       :         1    |     15 - 25         :         0\n"));
+        assert!(report.contains("     40 -  50 degrees:         2    |    130 - 140 degrees:         0\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn quality_honours_the_min_angle() -> Result<(), Error> {
+        let mut triangle = Triangle::new(4, Some(4), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 3.0, 0.0)?
+            .set_point(2, 3.0, 1.0)?
+            .set_point(3, 0.0, 1.0)?;
+        triangle
+            .set_segment(0, 0, 1)?
+            .set_segment(1, 1, 2)?
+            .set_segment(2, 2, 3)?
+            .set_segment(3, 3, 0)?;
+        triangle.generate_mesh(false, true, Some(0.1), Some(30.0))?;
+        let quality = triangle.quality()?;
+        assert_eq!(quality.triangles.len(), triangle.ntriangle());
+        assert!(quality.min_angle >= 30.0);
+        assert!(quality.max_area <= 0.1);
+        assert!(quality.triangles.iter().all(|t| t.area > 0.0));
+        let total: f64 = quality.triangles.iter().map(|t| t.area).sum();
+        assert!(f64::abs(total - 3.0) < 1e-14);
+        assert_eq!(quality.angle_histogram[0..3].iter().sum::<usize>(), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,421 @@
+use crate::{Error, Stage, Triangle};
+use std::fmt;
+
+/// Holds the problems found in the Planar Straight Line Graph (PSLG) given to Triangle
+///
+/// The report is returned by [Triangle::validate_pslg]. Triangle may abort the process or
+/// silently generate a wrong mesh if any of these problems is present.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PslgReport {
+    /// Pairs of points with the same coordinates
+    pub duplicate_points: Vec<[usize; 2]>,
+
+    /// Segments whose endpoints coincide
+    pub zero_length_segments: Vec<usize>,
+
+    /// Pairs of segments that intersect each other without sharing an endpoint
+    pub crossing_segments: Vec<[usize; 2]>,
+
+    /// Segments with an endpoint that is not shared with any other segment
+    pub dangling_segments: Vec<usize>,
+
+    /// Pairs of (hole, segment) indices where the hole lies exactly on the segment
+    pub holes_on_segments: Vec<[usize; 2]>,
+
+    /// Holes outside the bounding box of the points
+    pub holes_outside: Vec<usize>,
+
+    /// Pairs of (region, segment) indices where the region lies exactly on the segment
+    pub regions_on_segments: Vec<[usize; 2]>,
+
+    /// Regions outside the bounding box of the points
+    pub regions_outside: Vec<usize>,
+}
+
+impl PslgReport {
+    /// Returns true if no problem has been found
+    pub fn is_valid(&self) -> bool {
+        self.duplicate_points.is_empty()
+            && self.zero_length_segments.is_empty()
+            && self.crossing_segments.is_empty()
+            && self.dangling_segments.is_empty()
+            && self.holes_on_segments.is_empty()
+            && self.holes_outside.is_empty()
+            && self.regions_on_segments.is_empty()
+            && self.regions_outside.is_empty()
+    }
+}
+
+impl Triangle {
+    /// Validates the Planar Straight Line Graph (PSLG) before calling Triangle
+    ///
+    /// Detects duplicate points, zero-length segments, segments crossing each other without
+    /// a shared endpoint, dangling segments, and holes or regions lying exactly on a segment
+    /// or outside the bounding box of the points. The comparisons are exact (no tolerance).
+    ///
+    /// Call [Triangle::set_pslg_validation] to run this function automatically in `generate_mesh`.
+    ///
+    /// # Warning
+    ///
+    /// Dangling segments are accepted by Triangle (e.g., internal constraints); thus, they may
+    /// be harmless. However, they often indicate a gap on the boundary of the PSLG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use tritet::{Error, Triangle};
+    ///
+    /// fn main() -> Result<(), Error> {
+    ///     let triangle = Triangle::from_input(
+    ///         &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+    ///         &[[0, 2], [1, 3], [0, 1]],
+    ///         &[],
+    ///         &[[2.0, 0.5]],
+    ///     )?;
+    ///     let report = triangle.validate_pslg()?;
+    ///     assert_eq!(report.crossing_segments, &[[0, 1]]);
+    ///     assert_eq!(report.dangling_segments, &[0, 1]);
+    ///     assert_eq!(report.holes_outside, &[0]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn validate_pslg(&self) -> Result<PslgReport, Error> {
+        self.check_input_set(Stage::TriangleMesh)?;
+        // adding 0.0 turns -0.0 into 0.0; otherwise, total_cmp would not sort equal points together
+        let points: Vec<[f64; 2]> = self
+            .input_points()
+            .chunks_exact(2)
+            .map(|x| [x[0] + 0.0, x[1] + 0.0])
+            .collect();
+        let segments: Vec<[usize; 2]> = self
+            .input_segments()
+            .chunks_exact(2)
+            .map(|ab| [ab[0] as usize, ab[1] as usize])
+            .collect();
+        let regions: Vec<[f64; 2]> = self.input_regions().chunks_exact(4).map(|x| [x[0], x[1]]).collect();
+        let holes: Vec<[f64; 2]> = self.input_holes().chunks_exact(2).map(|x| [x[0], x[1]]).collect();
+        let mut report = PslgReport::default();
+
+        // duplicate points
+        let mut sorted: Vec<usize> = (0..points.len()).collect();
+        sorted.sort_by(|&i, &j| {
+            let (a, b) = (points[i], points[j]);
+            a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])).then(i.cmp(&j))
+        });
+        let mut first = 0;
+        for k in 1..sorted.len() {
+            if points[sorted[k]] == points[sorted[first]] {
+                report.duplicate_points.push([sorted[first], sorted[k]]);
+            } else {
+                first = k;
+            }
+        }
+        report.duplicate_points.sort();
+
+        // zero-length segments
+        let mut valid = vec![true; segments.len()];
+        for (s, [a, b]) in segments.iter().enumerate() {
+            if points[*a] == points[*b] {
+                report.zero_length_segments.push(s);
+                valid[s] = false;
+            }
+        }
+
+        // crossing segments (sweep along x)
+        let xmin = |s: usize| f64::min(points[segments[s][0]][0], points[segments[s][1]][0]);
+        let xmax = |s: usize| f64::max(points[segments[s][0]][0], points[segments[s][1]][0]);
+        let mut sweep: Vec<usize> = (0..segments.len()).filter(|s| valid[*s]).collect();
+        sweep.sort_by(|&s, &t| xmin(s).total_cmp(&xmin(t)));
+        for (k, &s) in sweep.iter().enumerate() {
+            let [a, b] = segments[s];
+            for &t in &sweep[(k + 1)..] {
+                if xmin(t) > xmax(s) {
+                    break;
+                }
+                let [c, d] = segments[t];
+                if a == c || a == d || b == c || b == d {
+                    continue;
+                }
+                if segments_intersect(&points[a], &points[b], &points[c], &points[d]) {
+                    report.crossing_segments.push([usize::min(s, t), usize::max(s, t)]);
+                }
+            }
+        }
+        report.crossing_segments.sort();
+
+        // dangling segments
+        let mut degree = vec![0; points.len()];
+        for [a, b] in segments.iter().zip(&valid).filter(|(_, ok)| **ok).map(|(ab, _)| ab) {
+            degree[*a] += 1;
+            degree[*b] += 1;
+        }
+        for (s, [a, b]) in segments.iter().enumerate() {
+            if valid[s] && (degree[*a] == 1 || degree[*b] == 1) {
+                report.dangling_segments.push(s);
+            }
+        }
+
+        // holes and regions
+        let mut min = [f64::MAX, f64::MAX];
+        let mut max = [f64::MIN, f64::MIN];
+        for x in &points {
+            for dim in 0..2 {
+                min[dim] = f64::min(min[dim], x[dim]);
+                max[dim] = f64::max(max[dim], x[dim]);
+            }
+        }
+        let outside = |x: &[f64; 2]| x[0] < min[0] || x[0] > max[0] || x[1] < min[1] || x[1] > max[1];
+        for (seeds, on_segments, outsiders) in [
+            (&holes, &mut report.holes_on_segments, &mut report.holes_outside),
+            (&regions, &mut report.regions_on_segments, &mut report.regions_outside),
+        ] {
+            for (i, x) in seeds.iter().enumerate() {
+                if outside(x) {
+                    outsiders.push(i);
+                    continue;
+                }
+                for (s, [a, b]) in segments.iter().enumerate() {
+                    if orientation(&points[*a], &points[*b], x) == 0.0 && in_box(&points[*a], &points[*b], x) {
+                        on_segments.push([i, s]);
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Returns twice the signed area of the triangle (a, b, c); zero if the points are collinear
+fn orientation(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Returns true if c is within the bounding box of the segment (a, b)
+fn in_box(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2]) -> bool {
+    c[0] >= f64::min(a[0], b[0])
+        && c[0] <= f64::max(a[0], b[0])
+        && c[1] >= f64::min(a[1], b[1])
+        && c[1] <= f64::max(a[1], b[1])
+}
+
+/// Returns true if the segments (a, b) and (c, d) intersect, including touching and overlapping
+fn segments_intersect(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], d: &[f64; 2]) -> bool {
+    let side = |v: f64| {
+        if v > 0.0 {
+            1
+        } else if v < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+    let d1 = side(orientation(c, d, a));
+    let d2 = side(orientation(c, d, b));
+    let d3 = side(orientation(a, b, c));
+    let d4 = side(orientation(a, b, d));
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true;
+    }
+    (d1 == 0 && in_box(c, d, a))
+        || (d2 == 0 && in_box(c, d, b))
+        || (d3 == 0 && in_box(a, b, c))
+        || (d4 == 0 && in_box(a, b, d))
+}
+
+/// Writes a list of items separated by commas
+pub(crate) fn write_list<T>(
+    f: &mut fmt::Formatter<'_>,
+    items: &[T],
+    mut write: impl FnMut(&mut fmt::Formatter<'_>, &T) -> fmt::Result,
+) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write(f, item)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for PslgReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "no problems found");
+        }
+        let pairs: [(&str, &Vec<[usize; 2]>); 4] = [
+            ("duplicate points", &self.duplicate_points),
+            ("crossing segments", &self.crossing_segments),
+            ("holes on segments", &self.holes_on_segments),
+            ("regions on segments", &self.regions_on_segments),
+        ];
+        let singles: [(&str, &Vec<usize>); 4] = [
+            ("zero-length segments", &self.zero_length_segments),
+            ("dangling segments", &self.dangling_segments),
+            ("holes outside the bounding box", &self.holes_outside),
+            ("regions outside the bounding box", &self.regions_outside),
+        ];
+        let mut first = true;
+        for (name, list) in pairs {
+            if !list.is_empty() {
+                write!(f, "{}{}: ", if first { "" } else { "; " }, name)?;
+                write_list(f, list, |f, [i, j]| write!(f, "{} & {}", i, j))?;
+                first = false;
+            }
+        }
+        for (name, list) in singles {
+            if !list.is_empty() {
+                write!(f, "{}{}: ", if first { "" } else { "; " }, name)?;
+                write_list(f, list, |f, i| write!(f, "{}", i))?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::{segments_intersect, PslgReport};
+    use crate::{Error, Item, Stage, Triangle};
+
+    #[test]
+    fn segments_intersect_works() {
+        let (a, b) = ([0.0, 0.0], [1.0, 0.0]);
+        assert!(segments_intersect(&a, &b, &[0.5, -1.0], &[0.5, 1.0])); // crossing
+        assert!(segments_intersect(&a, &b, &[0.5, 0.0], &[0.5, 1.0])); // touching (T-junction)
+        assert!(segments_intersect(&a, &b, &[0.5, 0.0], &[2.0, 0.0])); // overlapping
+        assert!(!segments_intersect(&a, &b, &[1.5, 0.0], &[2.0, 0.0])); // collinear but apart
+        assert!(!segments_intersect(&a, &b, &[0.5, 0.1], &[0.5, 1.0])); // apart
+        assert!(!segments_intersect(&a, &b, &[0.0, 1.0], &[1.0, 1.0])); // parallel
+    }
+
+    #[test]
+    fn validate_pslg_captures_some_errors() -> Result<(), Error> {
+        let mut triangle = Triangle::new(3, Some(3), None, None)?;
+        triangle
+            .set_point(0, 0.0, 0.0)?
+            .set_point(1, 1.0, 0.0)?
+            .set_point(2, 0.0, 1.0)?;
+        assert_eq!(
+            triangle.validate_pslg().err(),
+            Some(Error::NotAllSet {
+                item: Item::Segment,
+                stage: Stage::TriangleMesh,
+                missing: vec![0, 1, 2],
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn validate_pslg_works_with_valid_input() -> Result<(), Error> {
+        let triangle = Triangle::from_input(
+            &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.5, 0.0]],
+            &[[0, 4], [4, 1], [1, 2], [2, 3], [3, 0]],
+            &[(0.5, 0.5, 1, None)],
+            &[],
+        )?;
+        let report = triangle.validate_pslg()?;
+        assert_eq!(report, PslgReport::default());
+        assert!(report.is_valid());
+        assert_eq!(format!("{}", report), "no problems found");
+        Ok(())
+    }
+
+    #[test]
+    fn validate_pslg_finds_all_problems() -> Result<(), Error> {
+        let triangle = Triangle::from_input(
+            &[
+                [0.0, 0.0],  // 0
+                [1.0, 0.0],  // 1
+                [1.0, 1.0],  // 2
+                [0.0, 1.0],  // 3
+                [1.0, 0.0],  // 4 duplicate of 1
+                [0.5, -0.5], // 5
+                [0.5, 0.5],  // 6
+                [1.0, 0.0],  // 7 duplicate of 1
+            ],
+            &[
+                [0, 1], // 0
+                [1, 2], // 1
+                [2, 3], // 2
+                [3, 0], // 3
+                [5, 6], // 4 crosses 0 and dangles
+                [2, 2], // 5 zero-length
+                [4, 7], // 6 zero-length (duplicate points)
+            ],
+            &[
+                (0.25, 0.25, 1, None), // 0 ok
+                (0.5, 0.0, 2, None),   // 1 on segments 0 and 4
+                (-1.0, 0.0, 3, None),  // 2 outside
+            ],
+            &[
+                [0.5, 1.0], // 0 on segment 2
+                [2.0, 2.0], // 1 outside
+            ],
+        )?;
+        let report = triangle.validate_pslg()?;
+        assert_eq!(report.duplicate_points, &[[1, 4], [1, 7]]);
+        assert_eq!(report.zero_length_segments, &[5, 6]);
+        assert_eq!(report.crossing_segments, &[[0, 4]]);
+        assert_eq!(report.dangling_segments, &[4]);
+        assert_eq!(report.holes_on_segments, &[[0, 2]]);
+        assert_eq!(report.holes_outside, &[1]);
+        assert_eq!(report.regions_on_segments, &[[1, 0], [1, 4]]);
+        assert_eq!(report.regions_outside, &[2]);
+        assert!(!report.is_valid());
+        assert_eq!(
+            format!("{}", report),
+            "duplicate points: 1 & 4, 1 & 7; \
+             crossing segments: 0 & 4; \
+             holes on segments: 0 & 2; \
+             regions on segments: 1 & 0, 1 & 4; \
+             zero-length segments: 5, 6; \
+             dangling segments: 4; \
+             holes outside the bounding box: 1; \
+             regions outside the bounding box: 2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn validate_pslg_finds_duplicates_with_signed_zeros() -> Result<(), Error> {
+        // sorted by total_cmp without normalization: (-0, 1), (0, 0), (0, 1)
+        let triangle = Triangle::from_input(
+            &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [-0.0, 1.0]],
+            &[[0, 1], [1, 2], [2, 3], [3, 0]],
+            &[],
+            &[],
+        )?;
+        let report = triangle.validate_pslg()?;
+        assert_eq!(report.duplicate_points, &[[3, 4]]);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_mesh_validates_the_pslg() -> Result<(), Error> {
+        let points = &[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let mut triangle = Triangle::from_input(points, &[[0, 2], [1, 3], [0, 1]], &[], &[])?;
+        triangle.set_pslg_validation(true);
+        let error = triangle.generate_mesh(false, false, None, None).err().unwrap();
+        assert_eq!(
+            format!("{}", error),
+            "the PSLG is invalid: crossing segments: 0 & 1; dangling segments: 0, 1"
+        );
+        match error {
+            Error::InvalidPslg(report) => assert_eq!(report.crossing_segments, &[[0, 1]]),
+            _ => panic!("the error should be InvalidPslg"),
+        }
+        assert_eq!(triangle.ntriangle(), 0);
+
+        // valid PSLG
+        let mut triangle = Triangle::from_input(points, &[[0, 1], [1, 2], [2, 3], [3, 0]], &[], &[])?;
+        triangle
+            .set_pslg_validation(true)
+            .generate_mesh(false, false, None, None)?;
+        assert_eq!(triangle.ntriangle(), 2);
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 use plotpy::Plot;
-use tritet::{StrError, Triangle};
+use std::error::Error;
+use tritet::Triangle;
 
 #[test]
-fn test_triangle_mesh_1() -> Result<(), StrError> {
+fn test_triangle_mesh_1() -> Result<(), Box<dyn Error>> {
     let mut triangle = Triangle::new(12, Some(20), Some(7), Some(2))?;
     triangle
         .set_point(0, 0.0, 0.0)?